@@ -0,0 +1,315 @@
+use ark_ec::{pairing::Pairing, CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+use core::{borrow::Borrow, iter, marker::PhantomData, slice};
+
+use crate::{
+    fields::field_hashers::DigestGadget,
+    groups::hashing::{hash_to_curve, MapToCurveGadget},
+    pairing::PairingVar,
+    prelude::*,
+    Vec,
+};
+
+/// Trait for gadgets that hash messages to points of the group `C` that BLS
+/// signatures lie in.
+pub trait MessageHashGadget<C: CurveGroup, ConstraintF: PrimeField> {
+    /// The type of variables representing points on `C`.
+    type CurveVar: CurveVar<C, ConstraintF>;
+
+    /// Hashes `msg` to a point on `C`.
+    fn hash(&self, msg: &[UInt8<ConstraintF>]) -> Result<Self::CurveVar, SynthesisError>;
+}
+
+/// Hashes messages with [`hash_to_curve`], using the map `M`, the hash
+/// function `H`, and the domain separation tag `dst`.
+pub struct HashToCurveGadget<M, H> {
+    /// The domain separation tag.
+    pub dst: Vec<u8>,
+    _params: PhantomData<(M, H)>,
+}
+
+impl<M, H> HashToCurveGadget<M, H> {
+    /// Constructs `Self` with the domain separation tag `dst`.
+    pub fn new(dst: &[u8]) -> Self {
+        Self {
+            dst: dst.to_vec(),
+            _params: PhantomData,
+        }
+    }
+}
+
+impl<C, ConstraintF, M, H> MessageHashGadget<C, ConstraintF> for HashToCurveGadget<M, H>
+where
+    C: CurveGroup,
+    ConstraintF: PrimeField,
+    M: MapToCurveGadget<C, ConstraintF>,
+    H: DigestGadget<ConstraintF>,
+{
+    type CurveVar = M::CurveVar;
+
+    fn hash(&self, msg: &[UInt8<ConstraintF>]) -> Result<M::CurveVar, SynthesisError> {
+        hash_to_curve::<C, ConstraintF, M, H>(msg, &self.dst)
+    }
+}
+
+/// Specifies which groups the public keys and signatures of a BLS signature
+/// scheme lie in. This is implemented by [`MinPk`] and [`MinSig`].
+pub trait BlsVariant<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: PrimeField> {
+    /// The group that public keys lie in.
+    type PublicKeyGroup: CurveGroup;
+    /// The group that signatures and message hashes lie in.
+    type SignatureGroup: CurveGroup;
+    /// The type of variables representing public keys.
+    type PublicKeyVar: CurveVar<Self::PublicKeyGroup, ConstraintF>;
+    /// The type of variables representing signatures and message hashes.
+    type SignatureVar: CurveVar<Self::SignatureGroup, ConstraintF>;
+
+    /// Outputs a `Boolean` that is true if and only if the product of the
+    /// pairings of `public_keys` and `hashes` equals the pairing of the
+    /// generator with `signature`.
+    ///
+    /// As inputs in `G2` cannot be prepared if they are the identity, the
+    /// output is false if any of them is, rather than the constraint system
+    /// being unsatisfiable.
+    fn check_pairings(
+        public_keys: &[Self::PublicKeyVar],
+        hashes: &[Self::SignatureVar],
+        signature: &Self::SignatureVar,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError>;
+}
+
+/// The variant of BLS signatures with public keys in `G1` and signatures in
+/// `G2`, which minimizes the size of public keys.
+pub struct MinPk;
+
+/// The variant of BLS signatures with public keys in `G2` and signatures in
+/// `G1`, which minimizes the size of signatures.
+pub struct MinSig;
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: PrimeField>
+    BlsVariant<E, P, ConstraintF> for MinPk
+{
+    type PublicKeyGroup = E::G1;
+    type SignatureGroup = E::G2;
+    type PublicKeyVar = P::G1Var;
+    type SignatureVar = P::G2Var;
+
+    #[tracing::instrument(target = "r1cs", skip(public_keys, hashes, signature))]
+    fn check_pairings(
+        public_keys: &[P::G1Var],
+        hashes: &[P::G2Var],
+        signature: &P::G2Var,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        // Π e(pkᵢ, H(mᵢ)) * e(-g, σ) = 1
+        let neg_g = P::G1Var::constant(-E::G1::generator());
+        let ps = public_keys
+            .iter()
+            .chain(iter::once(&neg_g))
+            .map(P::prepare_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+        let (qs, non_zero) =
+            prepare_g2_non_zero::<E, P, ConstraintF>(hashes.iter().chain(iter::once(signature)))?;
+        non_zero.and(&P::product_of_pairings(&ps, &qs)?.is_one()?)
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: PrimeField>
+    BlsVariant<E, P, ConstraintF> for MinSig
+{
+    type PublicKeyGroup = E::G2;
+    type SignatureGroup = E::G1;
+    type PublicKeyVar = P::G2Var;
+    type SignatureVar = P::G1Var;
+
+    #[tracing::instrument(target = "r1cs", skip(public_keys, hashes, signature))]
+    fn check_pairings(
+        public_keys: &[P::G2Var],
+        hashes: &[P::G1Var],
+        signature: &P::G1Var,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        // Π e(H(mᵢ), pkᵢ) * e(σ, -g) = 1, where the line coefficients of the
+        // constant `-g` are precomputed natively.
+        let neg_g = P::G2PreparedVar::new_constant(
+            ConstraintSystemRef::None,
+            E::G2Prepared::from(-E::G2::generator()),
+        )?;
+        let ps = hashes
+            .iter()
+            .chain(iter::once(signature))
+            .map(P::prepare_g1)
+            .collect::<Result<Vec<_>, _>>()?;
+        let (mut qs, non_zero) = prepare_g2_non_zero::<E, P, ConstraintF>(public_keys)?;
+        qs.push(neg_g);
+        non_zero.and(&P::product_of_pairings(&ps, &qs)?.is_one()?)
+    }
+}
+
+/// Prepares `qs`, each of which is replaced by the generator if it is the
+/// identity. Also outputs a `Boolean` that is true if and only if none of
+/// `qs` is the identity.
+fn prepare_g2_non_zero<'a, E, P, ConstraintF>(
+    qs: impl IntoIterator<Item = &'a P::G2Var>,
+) -> Result<(Vec<P::G2PreparedVar>, Boolean<ConstraintF>), SynthesisError>
+where
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    let generator = P::G2Var::constant(E::G2::generator());
+    let mut non_zero = Boolean::TRUE;
+    let mut prepared = Vec::new();
+    for q in qs {
+        let is_zero = q.is_zero()?;
+        prepared.push(P::prepare_g2(&is_zero.select(&generator, q)?)?);
+        non_zero = non_zero.and(&is_zero.not())?;
+    }
+    Ok((prepared, non_zero))
+}
+
+/// Represents a BLS signature of the variant `V` over the pairing `E`, which
+/// can be verified using the pairing gadget `P`.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "V: BlsVariant<E, P, ConstraintF>"),
+    Debug(bound = "V: BlsVariant<E, P, ConstraintF>")
+)]
+pub struct BlsSignatureVar<
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    V: BlsVariant<E, P, ConstraintF>,
+    ConstraintF: PrimeField = <<E as Pairing>::G1 as CurveGroup>::BaseField,
+> {
+    /// The signature.
+    pub signature: V::SignatureVar,
+    _params: PhantomData<(E, P)>,
+}
+
+impl<E, P, V, ConstraintF> BlsSignatureVar<E, P, V, ConstraintF>
+where
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    V: BlsVariant<E, P, ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    /// Constructs `Self` from `signature`.
+    pub fn new(signature: V::SignatureVar) -> Self {
+        Self {
+            signature,
+            _params: PhantomData,
+        }
+    }
+
+    /// Outputs a `Boolean` that is true if and only if `self` is a valid
+    /// signature of `msg` under `public_key`, where messages are hashed with
+    /// `hasher`.
+    #[tracing::instrument(target = "r1cs", skip(self, public_key, msg, hasher))]
+    pub fn verify<H>(
+        &self,
+        public_key: &V::PublicKeyVar,
+        msg: &[UInt8<ConstraintF>],
+        hasher: &H,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError>
+    where
+        H: MessageHashGadget<V::SignatureGroup, ConstraintF, CurveVar = V::SignatureVar>,
+    {
+        self.verify_aggregate(slice::from_ref(public_key), &[msg], hasher)
+    }
+
+    /// Outputs a `Boolean` that is true if and only if `self` is a valid
+    /// aggregate signature of `msg` under all of `public_keys`, where
+    /// messages are hashed with `hasher`.
+    ///
+    /// As the public keys are aggregated before verification, they must come
+    /// with proofs of possession, to rule out rogue-key attacks.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `public_keys` is empty.
+    #[tracing::instrument(target = "r1cs", skip(self, public_keys, msg, hasher))]
+    pub fn verify_aggregate_same_message<H>(
+        &self,
+        public_keys: &[V::PublicKeyVar],
+        msg: &[UInt8<ConstraintF>],
+        hasher: &H,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError>
+    where
+        H: MessageHashGadget<V::SignatureGroup, ConstraintF, CurveVar = V::SignatureVar>,
+    {
+        assert!(!public_keys.is_empty(), "there must be a public key");
+        let all_non_zero = Self::all_non_zero(public_keys)?;
+        let public_key = public_keys
+            .iter()
+            .fold(V::PublicKeyVar::zero(), |acc, pk| acc + pk);
+        let hash = hasher.hash(msg)?;
+        let pairings = V::check_pairings(&[public_key], &[hash], &self.signature)?;
+        all_non_zero.and(&pairings)
+    }
+
+    /// Outputs a `Boolean` that is true if and only if `self` is a valid
+    /// aggregate signature of `msgs[i]` under `public_keys[i]` for every `i`,
+    /// where messages are hashed with `hasher`.
+    ///
+    /// To rule out rogue-key attacks without proofs of possession, the
+    /// messages must be distinct, which this method does not check.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `public_keys` is empty, or if
+    /// `public_keys.len() != msgs.len()`.
+    #[tracing::instrument(target = "r1cs", skip(self, public_keys, msgs, hasher))]
+    pub fn verify_aggregate<H>(
+        &self,
+        public_keys: &[V::PublicKeyVar],
+        msgs: &[&[UInt8<ConstraintF>]],
+        hasher: &H,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError>
+    where
+        H: MessageHashGadget<V::SignatureGroup, ConstraintF, CurveVar = V::SignatureVar>,
+    {
+        assert_eq!(
+            public_keys.len(),
+            msgs.len(),
+            "the number of public keys and messages must be equal"
+        );
+        assert!(!public_keys.is_empty(), "there must be a public key");
+        let all_non_zero = Self::all_non_zero(public_keys)?;
+        let hashes = msgs
+            .iter()
+            .map(|msg| hasher.hash(msg))
+            .collect::<Result<Vec<_>, _>>()?;
+        let pairings = V::check_pairings(public_keys, &hashes, &self.signature)?;
+        all_non_zero.and(&pairings)
+    }
+
+    /// Outputs a `Boolean` that is true if and only if none of `public_keys`
+    /// is the identity, which would otherwise be a valid public key for any
+    /// message with the identity as signature.
+    fn all_non_zero(
+        public_keys: &[V::PublicKeyVar],
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        let is_zero = public_keys
+            .iter()
+            .map(|pk| pk.is_zero())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Boolean::kary_or(&is_zero)?.not())
+    }
+}
+
+impl<E, P, V, ConstraintF> AllocVar<V::SignatureGroup, ConstraintF>
+    for BlsSignatureVar<E, P, V, ConstraintF>
+where
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    V: BlsVariant<E, P, ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    fn new_variable<T: Borrow<V::SignatureGroup>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        <V::SignatureVar as AllocVar<V::SignatureGroup, _>>::new_variable(cs, f, mode)
+            .map(Self::new)
+    }
+}
@@ -0,0 +1,135 @@
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_relations::r1cs::SynthesisError;
+
+use crate::{prelude::*, Vec};
+
+/// The security parameter `k` (in bits) used by [`hash_to_field`], which
+/// matches `ark_ff::field_hashers::DefaultFieldHasher`.
+const SEC_PARAM: usize = 128;
+
+/// The prefix that [`expand_message_xmd`] uses to hash domain separation
+/// tags that are longer than 255 bytes.
+const LONG_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// Trait for gadgets that compute a Merkle–Damgård hash function `H`, such as
+/// SHA-256, for use in [`expand_message_xmd`].
+pub trait DigestGadget<ConstraintF: PrimeField> {
+    /// The output size of `H`, in bytes (`b_in_bytes` in RFC 9380).
+    const OUTPUT_SIZE: usize;
+    /// The input block size of `H`, in bytes (`s_in_bytes` in RFC 9380).
+    const BLOCK_SIZE: usize;
+
+    /// Outputs `H(input)`, which must consist of `Self::OUTPUT_SIZE` bytes.
+    fn digest(input: &[UInt8<ConstraintF>]) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError>;
+}
+
+/// Implements `expand_message_xmd` from [RFC 9380, Section 5.3.1](https://www.rfc-editor.org/rfc/rfc9380.html#section-5.3.1),
+/// which outputs `len_in_bytes` uniformly random bytes derived from `msg` and
+/// the domain separation tag `dst`.
+///
+/// # Panics
+///
+/// This function panics if `len_in_bytes` is too large for the output size
+/// of `H`, as specified by RFC 9380.
+#[tracing::instrument(target = "r1cs", skip(msg))]
+pub fn expand_message_xmd<H, ConstraintF>(
+    msg: &[UInt8<ConstraintF>],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError>
+where
+    H: DigestGadget<ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    let ell = len_in_bytes.div_ceil(H::OUTPUT_SIZE);
+    assert!(ell <= 255, "`len_in_bytes` is too large");
+    assert!(len_in_bytes <= 65535, "`len_in_bytes` is too large");
+
+    let dst = if dst.len() > 255 {
+        let long_dst = UInt8::constant_vec(&[LONG_DST_PREFIX, dst].concat());
+        H::digest(&long_dst)?.value()?
+    } else {
+        dst.to_vec()
+    };
+    let mut dst_prime = UInt8::constant_vec(&dst);
+    dst_prime.push(UInt8::constant(dst.len() as u8));
+
+    let mut msg_prime = UInt8::constant_vec(&vec![0u8; H::BLOCK_SIZE]);
+    msg_prime.extend_from_slice(msg);
+    msg_prime.extend(UInt8::constant_vec(&(len_in_bytes as u16).to_be_bytes()));
+    msg_prime.push(UInt8::constant(0));
+    msg_prime.extend_from_slice(&dst_prime);
+    let b_0 = H::digest(&msg_prime)?;
+
+    let mut uniform_bytes = Vec::with_capacity(ell * H::OUTPUT_SIZE);
+    let mut b_i = b_0.clone();
+    for i in 1..=ell {
+        let mut input = if i == 1 {
+            b_0.clone()
+        } else {
+            b_0.iter()
+                .zip(&b_i)
+                .map(|(a, b)| a.xor(b))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        input.push(UInt8::constant(i as u8));
+        input.extend_from_slice(&dst_prime);
+        b_i = H::digest(&input)?;
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Implements `hash_to_field` from [RFC 9380, Section 5.2](https://www.rfc-editor.org/rfc/rfc9380.html#section-5.2),
+/// using [`expand_message_xmd`] with the hash function `H`. This outputs
+/// `count` elements of `F` derived from `msg` and the domain separation tag
+/// `dst`, and agrees with `ark_ff::field_hashers::DefaultFieldHasher`.
+#[tracing::instrument(target = "r1cs", skip(msg))]
+pub fn hash_to_field<F, ConstraintF, FV, H>(
+    msg: &[UInt8<ConstraintF>],
+    dst: &[u8],
+    count: usize,
+) -> Result<Vec<FV>, SynthesisError>
+where
+    F: Field,
+    ConstraintF: PrimeField,
+    FV: FieldVar<F, ConstraintF>,
+    H: DigestGadget<ConstraintF>,
+{
+    let m = F::extension_degree() as usize;
+    let len_per_elem = (F::BasePrimeField::MODULUS_BIT_SIZE as usize + SEC_PARAM).div_ceil(8);
+    let uniform_bytes = expand_message_xmd::<H, _>(msg, dst, count * m * len_per_elem)?;
+
+    // The coefficients of the (big-endian) bits of each component, reduced modulo
+    // the characteristic.
+    let two = F::BasePrimeField::from(2u8);
+    let mut powers_of_two = Vec::with_capacity(8 * len_per_elem);
+    let mut power = F::BasePrimeField::one();
+    for _ in 0..8 * len_per_elem {
+        powers_of_two.push(power);
+        power *= two;
+    }
+
+    let mut result = Vec::with_capacity(count);
+    for elem_bytes in uniform_bytes.chunks(m * len_per_elem) {
+        let mut elem = FV::zero();
+        for (j, component_bytes) in elem_bytes.chunks(len_per_elem).enumerate() {
+            let mut basis_element = vec![F::BasePrimeField::zero(); m];
+            basis_element[j] = F::BasePrimeField::one();
+            let basis_element = F::from_base_prime_field_elems(&basis_element).unwrap();
+
+            let mut bits = Vec::with_capacity(8 * len_per_elem);
+            for byte in component_bytes.iter().rev() {
+                bits.extend(byte.to_bits_le()?);
+            }
+            // Reducing a linear combination of bits modulo the characteristic
+            // costs no constraints.
+            for (bit, power) in bits.into_iter().zip(&powers_of_two) {
+                elem += FV::from(bit) * (basis_element * F::from_base_prime_field(*power));
+            }
+        }
+        result.push(elem);
+    }
+    Ok(result)
+}
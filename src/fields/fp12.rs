@@ -1,35 +1,70 @@
-use crate::fields::{fp2::Fp2Var, fp6_3over2::Fp6Var, quadratic_extension::*, FieldVar};
+use crate::fields::{
+    fp::FpVar, fp2::Fp2VarWithBase, fp6_3over2::Fp6VarWithBase, quadratic_extension::*,
+    FieldOpsBounds, FieldVar,
+};
 use ark_ff::{
     fields::{fp12_2over3over2::*, Field},
     fp6_3over2::Fp6Config,
-    QuadExtConfig,
+    PrimeField, QuadExtConfig,
 };
 use ark_relations::r1cs::SynthesisError;
 
 /// A degree-12 extension field constructed as the tower of a
 /// quadratic extension over a cubic extension over a quadratic extension field.
 /// This is the R1CS equivalent of `ark_ff::fp12_2over3over2::Fp12<P>`.
-pub type Fp12Var<P> = QuadExtVar<Fp6Var<<P as Fp12Config>::Fp6Config>, Fp12ConfigWrapper<P>>;
+pub type Fp12Var<P> = Fp12VarWithBase<P, FpVar<BasePrimeField<P>>, BasePrimeField<P>>;
 
-type Fp2Config<P> = <<P as Fp12Config>::Fp6Config as Fp6Config>::Fp2Config;
+/// A degree-12 extension field whose base prime field elements are
+/// represented by variables of type `F` over the constraint field
+/// `ConstraintF`.
+pub type Fp12VarWithBase<P, F, ConstraintF> = QuadExtVar<
+    Fp6VarWithBase<<P as Fp12Config>::Fp6Config, F, ConstraintF>,
+    Fp12ConfigWrapper<P>,
+    ConstraintF,
+>;
 
-impl<P: Fp12Config> QuadExtVarConfig<Fp6Var<P::Fp6Config>> for Fp12ConfigWrapper<P> {
-    fn mul_base_field_var_by_frob_coeff(fe: &mut Fp6Var<P::Fp6Config>, power: usize) {
+type Fp2Config<P> = <<P as Fp12Config>::Fp6Config as Fp6Config>::Fp2Config;
+type BasePrimeField<P> = <Fp2Config<P> as ark_ff::Fp2Config>::Fp;
+
+impl<P, F, ConstraintF> QuadExtVarConfig<Fp6VarWithBase<P::Fp6Config, F, ConstraintF>, ConstraintF>
+    for Fp12ConfigWrapper<P>
+where
+    P: Fp12Config,
+    F: FieldVar<BasePrimeField<P>, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, BasePrimeField<P>, F>,
+    ConstraintF: PrimeField,
+{
+    fn mul_base_field_var_by_frob_coeff(
+        fe: &mut Fp6VarWithBase<P::Fp6Config, F, ConstraintF>,
+        power: usize,
+    ) {
         fe.c0 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
         fe.c1 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
         fe.c2 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
     }
+
+    fn cyclotomic_square(
+        fe: &Fp12VarWithBase<P, F, ConstraintF>,
+    ) -> Result<Fp12VarWithBase<P, F, ConstraintF>, SynthesisError> {
+        fe.cyclotomic_square()
+    }
 }
 
-impl<P: Fp12Config> Fp12Var<P> {
+impl<P, F, ConstraintF> Fp12VarWithBase<P, F, ConstraintF>
+where
+    P: Fp12Config,
+    F: FieldVar<BasePrimeField<P>, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, BasePrimeField<P>, F>,
+    ConstraintF: PrimeField,
+{
     /// Multiplies by a sparse element of the form `(c0 = (c0, c1, 0), c1 = (0,
     /// d1, 0))`.
     #[inline]
     pub fn mul_by_014(
         &self,
-        c0: &Fp2Var<Fp2Config<P>>,
-        c1: &Fp2Var<Fp2Config<P>>,
-        d1: &Fp2Var<Fp2Config<P>>,
+        c0: &Fp2VarWithBase<Fp2Config<P>, F, ConstraintF>,
+        c1: &Fp2VarWithBase<Fp2Config<P>, F, ConstraintF>,
+        d1: &Fp2VarWithBase<Fp2Config<P>, F, ConstraintF>,
     ) -> Result<Self, SynthesisError> {
         let v0 = self.c0.mul_by_c0_c1_0(&c0, &c1)?;
         let v1 = self.c1.mul_by_0_c1_0(&d1)?;
@@ -44,14 +79,14 @@ impl<P: Fp12Config> Fp12Var<P> {
     #[inline]
     pub fn mul_by_034(
         &self,
-        c0: &Fp2Var<Fp2Config<P>>,
-        d0: &Fp2Var<Fp2Config<P>>,
-        d1: &Fp2Var<Fp2Config<P>>,
+        c0: &Fp2VarWithBase<Fp2Config<P>, F, ConstraintF>,
+        d0: &Fp2VarWithBase<Fp2Config<P>, F, ConstraintF>,
+        d1: &Fp2VarWithBase<Fp2Config<P>, F, ConstraintF>,
     ) -> Result<Self, SynthesisError> {
         let a0 = &self.c0.c0 * c0;
         let a1 = &self.c0.c1 * c0;
         let a2 = &self.c0.c2 * c0;
-        let a = Fp6Var::new(a0, a1, a2);
+        let a = Fp6VarWithBase::new(a0, a1, a2);
         let b = self.c1.mul_by_c0_c1_0(&d0, &d1)?;
 
         let c0 = c0 + d0;
@@ -133,8 +168,8 @@ impl<P: Fp12Config> Fp12Var<P> {
 
             // z5 = 3 * t3 + 2 * z5
             let c1_c2 = (&t3 + z5).double()? + &t3;
-            let c0 = Fp6Var::new(c0_c0, c0_c1, c0_c2);
-            let c1 = Fp6Var::new(c1_c0, c1_c1, c1_c2);
+            let c0 = Fp6VarWithBase::new(c0_c0, c0_c1, c0_c2);
+            let c1 = Fp6VarWithBase::new(c1_c0, c1_c1, c1_c2);
 
             Ok(Self::new(c0, c1))
         } else {
@@ -142,7 +177,8 @@ impl<P: Fp12Config> Fp12Var<P> {
         }
     }
 
-    /// Like `Self::cyclotomic_exp`, but additionally uses cyclotomic squaring.
+    /// Equivalent to `Self::cyclotomic_exp`, which also uses cyclotomic
+    /// squaring.
     pub fn optimized_cyclotomic_exp(
         &self,
         exponent: impl AsRef<[u64]>,
@@ -1,12 +1,26 @@
-use crate::fields::{fp::FpVar, quadratic_extension::*};
-use ark_ff::fields::{Fp2Config, Fp2ConfigWrapper, QuadExtConfig};
+use crate::fields::{fp::FpVar, quadratic_extension::*, FieldOpsBounds, FieldVar};
+use ark_ff::{
+    fields::{Fp2Config, Fp2ConfigWrapper, QuadExtConfig},
+    PrimeField,
+};
 
 /// A quadratic extension field constructed over a prime field.
 /// This is the R1CS equivalent of `ark_ff::Fp2<P>`.
-pub type Fp2Var<P> = QuadExtVar<FpVar<<P as Fp2Config>::Fp>, Fp2ConfigWrapper<P>>;
+pub type Fp2Var<P> = Fp2VarWithBase<P, FpVar<<P as Fp2Config>::Fp>, <P as Fp2Config>::Fp>;
 
-impl<P: Fp2Config> QuadExtVarConfig<FpVar<P::Fp>> for Fp2ConfigWrapper<P> {
-    fn mul_base_field_var_by_frob_coeff(fe: &mut FpVar<P::Fp>, power: usize) {
+/// A quadratic extension field constructed over a prime field, whose elements
+/// are represented by variables of type `F` over the constraint field
+/// `ConstraintF`, e.g. `NonNativeFieldVar<P::Fp, ConstraintF>`.
+pub type Fp2VarWithBase<P, F, ConstraintF> = QuadExtVar<F, Fp2ConfigWrapper<P>, ConstraintF>;
+
+impl<P, F, ConstraintF> QuadExtVarConfig<F, ConstraintF> for Fp2ConfigWrapper<P>
+where
+    P: Fp2Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    fn mul_base_field_var_by_frob_coeff(fe: &mut F, power: usize) {
         *fe *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
     }
 }
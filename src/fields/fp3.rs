@@ -1,19 +1,26 @@
-use crate::fields::{cubic_extension::*, fp::FpVar};
+use crate::fields::{cubic_extension::*, fp::FpVar, FieldOpsBounds, FieldVar};
 use ark_ff::{
     fields::{CubicExtConfig, Fp3ConfigWrapper},
-    Fp3Config,
+    Fp3Config, PrimeField,
 };
 
 /// A cubic extension field constructed over a prime field.
 /// This is the R1CS equivalent of `ark_ff::Fp3<P>`.
-pub type Fp3Var<P> = CubicExtVar<FpVar<<P as Fp3Config>::Fp>, Fp3ConfigWrapper<P>>;
+pub type Fp3Var<P> = Fp3VarWithBase<P, FpVar<<P as Fp3Config>::Fp>, <P as Fp3Config>::Fp>;
 
-impl<P: Fp3Config> CubicExtVarConfig<FpVar<P::Fp>> for Fp3ConfigWrapper<P> {
-    fn mul_base_field_vars_by_frob_coeff(
-        c1: &mut FpVar<P::Fp>,
-        c2: &mut FpVar<P::Fp>,
-        power: usize,
-    ) {
+/// A cubic extension field constructed over a prime field, whose elements are
+/// represented by variables of type `F` over the constraint field
+/// `ConstraintF`.
+pub type Fp3VarWithBase<P, F, ConstraintF> = CubicExtVar<F, Fp3ConfigWrapper<P>, ConstraintF>;
+
+impl<P, F, ConstraintF> CubicExtVarConfig<F, ConstraintF> for Fp3ConfigWrapper<P>
+where
+    P: Fp3Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    fn mul_base_field_vars_by_frob_coeff(c1: &mut F, c2: &mut F, power: usize) {
         *c1 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
         *c2 *= Self::FROBENIUS_COEFF_C2[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
     }
@@ -1,15 +1,164 @@
-use crate::fields::{fp3::Fp3Var, quadratic_extension::*};
-use ark_ff::{fields::fp6_2over3::*, QuadExtConfig};
+use crate::fields::{
+    fp::FpVar, fp3::Fp3VarWithBase, is_emulated, mul_polynomials_by_evaluation,
+    quadratic_extension::*, FieldOpsBounds, FieldVar,
+};
+use ark_ff::{fields::fp6_2over3::*, Fp3Config, One, PrimeField, QuadExtConfig, Zero};
+use ark_relations::r1cs::SynthesisError;
 
 /// A sextic extension field constructed as the tower of a
 /// quadratic extension over a cubic extension field.
 /// This is the R1CS equivalent of `ark_ff::fp6_2over3::Fp6<P>`.
-pub type Fp6Var<P> = QuadExtVar<Fp3Var<<P as Fp6Config>::Fp3Config>, Fp6ConfigWrapper<P>>;
+pub type Fp6Var<P> = Fp6VarWithBase<P, FpVar<BasePrimeField<P>>, BasePrimeField<P>>;
 
-impl<P: Fp6Config> QuadExtVarConfig<Fp3Var<P::Fp3Config>> for Fp6ConfigWrapper<P> {
-    fn mul_base_field_var_by_frob_coeff(fe: &mut Fp3Var<P::Fp3Config>, power: usize) {
+/// A sextic extension field, as a quadratic extension over a cubic extension
+/// field, whose base prime field elements are represented by variables of
+/// type `F` over the constraint field `ConstraintF`.
+pub type Fp6VarWithBase<P, F, ConstraintF> = QuadExtVar<
+    Fp3VarWithBase<<P as Fp6Config>::Fp3Config, F, ConstraintF>,
+    Fp6ConfigWrapper<P>,
+    ConstraintF,
+>;
+
+type BasePrimeField<P> = <<P as Fp6Config>::Fp3Config as Fp3Config>::Fp;
+
+impl<P, F, ConstraintF> QuadExtVarConfig<Fp3VarWithBase<P::Fp3Config, F, ConstraintF>, ConstraintF>
+    for Fp6ConfigWrapper<P>
+where
+    P: Fp6Config,
+    F: FieldVar<BasePrimeField<P>, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, BasePrimeField<P>, F>,
+    ConstraintF: PrimeField,
+{
+    fn mul_base_field_var_by_frob_coeff(
+        fe: &mut Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+        power: usize,
+    ) {
         fe.c0 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
         fe.c1 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
         fe.c2 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
     }
 }
+
+impl<P, F, ConstraintF> Fp6VarWithBase<P, F, ConstraintF>
+where
+    P: Fp6Config,
+    F: FieldVar<BasePrimeField<P>, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, BasePrimeField<P>, F>,
+    ConstraintF: PrimeField,
+{
+    /// Multiplies `self` by a sparse element of the form `(c0, y * c1)`,
+    /// where `y` is an element of the base prime field.
+    ///
+    /// This is the shape of the doubling line evaluated at a `G1` point in
+    /// the MNT6 Miller loop, where `c1 = c_h * TWIST` and `y` is the
+    /// `y`-coordinate of the `G1` point. Scaling `c1` by `y` costs three
+    /// constraints, instead of the six needed to multiply `c_h` by the
+    /// twisted coordinate `y * TWIST`. The multiplication itself
+    /// costs 11 constraints (instead of 18) for the MNT6 curves.
+    #[inline]
+    pub fn mul_by_c0_yc1(
+        &self,
+        c0: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+        c1: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+        y: &F,
+    ) -> Result<Self, SynthesisError> {
+        let c1 = Fp3VarWithBase::new(&c1.c0 * y, &c1.c1 * y, &c1.c2 * y);
+        self.mul_by_line(c0, &c1)
+    }
+
+    /// Multiplies `self` by a sparse element of the form `(y * c0, c1)`,
+    /// where `y` is an element of the base prime field.
+    ///
+    /// This is the shape of the addition line evaluated at a `G1` point in
+    /// the MNT6 Miller loop, where `c0 = c_rz * TWIST`.
+    #[inline]
+    pub fn mul_by_yc0_c1(
+        &self,
+        c0: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+        c1: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+        y: &F,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = Fp3VarWithBase::new(&c0.c0 * y, &c0.c1 * y, &c0.c2 * y);
+        self.mul_by_line(&c0, c1)
+    }
+
+    /// Multiplies `self` by `(c0, c1)`.
+    ///
+    /// When `P::NONRESIDUE = (0, 1, 0)`, as for the MNT6 curves, this field is
+    /// `Fp[X]/(X^6 - P::Fp3Config::NONRESIDUE)` with `X = (0, 1)`, and the
+    /// product is computed on the coefficients in this power basis, which
+    /// costs 11 constraints instead of the 18 of Karatsuba multiplication.
+    /// Emulated fields use Karatsuba multiplication, which is cheaper for them.
+    fn mul_by_line(
+        &self,
+        c0: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+        c1: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
+        let nonresidue = P::NONRESIDUE;
+        if !(nonresidue.c0.is_zero() && nonresidue.c1.is_one() && nonresidue.c2.is_zero())
+            || is_emulated::<BasePrimeField<P>, ConstraintF>()
+        {
+            return Ok(self * Self::new(c0.clone(), c1.clone()));
+        }
+        // `X^2 = (0, 1, 0)` is the generator of `Fp3` over `Fp`, so the
+        // coefficients of `c0` are those of the even powers of `X`, and the
+        // coefficients of `c1` are those of the odd powers.
+        let to_power_basis =
+            |c0: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>,
+             c1: &Fp3VarWithBase<P::Fp3Config, F, ConstraintF>| {
+                [&c0.c0, &c1.c0, &c0.c1, &c1.c1, &c0.c2, &c1.c2].map(F::clone)
+            };
+        let a = to_power_basis(&self.c0, &self.c1);
+        let b = to_power_basis(c0, c1);
+        let p = mul_polynomials_by_evaluation(&a, &b)?;
+        // Reduce using `X^6 = P::Fp3Config::NONRESIDUE`.
+        let [r0, r1, r2, r3, r4] =
+            [0, 1, 2, 3, 4].map(|i| &p[i] + &p[i + 6] * <P::Fp3Config as Fp3Config>::NONRESIDUE);
+        let r5 = p[5].clone();
+        Ok(Self::new(
+            Fp3VarWithBase::new(r0, r2, r4),
+            Fp3VarWithBase::new(r1, r3, r5),
+        ))
+    }
+
+    /// Multiplies `self` by a sparse element of the form
+    /// `(c0 = (c0, c1, 0), c1 = (0, c4, 0))`, with all coefficients in the
+    /// base prime field.
+    ///
+    /// This is the shape of a line evaluated at a `G1` point in the BW6 Miller
+    /// loop for M-type twists.
+    #[inline]
+    pub fn mul_by_014(&self, c0: &F, c1: &F, c4: &F) -> Result<Self, SynthesisError> {
+        let zero = F::zero();
+        let a = &self.c0 * Fp3VarWithBase::new(c0.clone(), c1.clone(), zero.clone());
+        // Multiplying by `c4 * X` shifts the coefficients of `self.c1`.
+        let b = Fp3VarWithBase::new(
+            Fp3VarWithBase::<P::Fp3Config, F, ConstraintF>::mul_base_field_by_nonresidue(
+                &(&self.c1.c2 * c4),
+            )?,
+            &self.c1.c0 * c4,
+            &self.c1.c1 * c4,
+        );
+        let e = (&self.c0 + &self.c1) * Fp3VarWithBase::new(c0.clone(), c1 + c4, zero);
+        let new_c1 = e - &a - &b;
+        let new_c0 = Self::mul_base_field_by_nonresidue(&b)? + &a;
+        Ok(Self::new(new_c0, new_c1))
+    }
+
+    /// Multiplies `self` by a sparse element of the form
+    /// `(c0 = (c0, 0, 0), c1 = (c3, c4, 0))`, with all coefficients in the
+    /// base prime field.
+    ///
+    /// This is the shape of a line evaluated at a `G1` point in the BW6 Miller
+    /// loop for D-type twists.
+    #[inline]
+    pub fn mul_by_034(&self, c0: &F, c3: &F, c4: &F) -> Result<Self, SynthesisError> {
+        let zero = F::zero();
+        let a = Fp3VarWithBase::new(&self.c0.c0 * c0, &self.c0.c1 * c0, &self.c0.c2 * c0);
+        let b = &self.c1 * Fp3VarWithBase::new(c3.clone(), c4.clone(), zero.clone());
+        let e = (&self.c0 + &self.c1) * Fp3VarWithBase::new(c0 + c3, c4.clone(), zero);
+        let new_c1 = e - &a - &b;
+        let new_c0 = Self::mul_base_field_by_nonresidue(&b)? + &a;
+        Ok(Self::new(new_c0, new_c1))
+    }
+}
@@ -1,7 +1,7 @@
-use crate::fields::{cubic_extension::*, fp2::*};
+use crate::fields::{cubic_extension::*, fp::FpVar, fp2::*, FieldOpsBounds, FieldVar};
 use ark_ff::{
     fields::{fp6_3over2::*, Fp2},
-    CubicExtConfig,
+    CubicExtConfig, Fp2Config, PrimeField,
 };
 use ark_relations::r1cs::SynthesisError;
 use ark_std::ops::MulAssign;
@@ -9,12 +9,30 @@ use ark_std::ops::MulAssign;
 /// A sextic extension field constructed as the tower of a
 /// cubic extension over a quadratic extension field.
 /// This is the R1CS equivalent of `ark_ff::fp6_3over3::Fp6<P>`.
-pub type Fp6Var<P> = CubicExtVar<Fp2Var<<P as Fp6Config>::Fp2Config>, Fp6ConfigWrapper<P>>;
-
-impl<P: Fp6Config> CubicExtVarConfig<Fp2Var<P::Fp2Config>> for Fp6ConfigWrapper<P> {
+pub type Fp6Var<P> = Fp6VarWithBase<P, FpVar<BasePrimeField<P>>, BasePrimeField<P>>;
+
+/// A sextic extension field, as a cubic extension over a quadratic extension
+/// field, whose base prime field elements are represented by variables of
+/// type `F` over the constraint field `ConstraintF`.
+pub type Fp6VarWithBase<P, F, ConstraintF> = CubicExtVar<
+    Fp2VarWithBase<<P as Fp6Config>::Fp2Config, F, ConstraintF>,
+    Fp6ConfigWrapper<P>,
+    ConstraintF,
+>;
+
+type BasePrimeField<P> = <<P as Fp6Config>::Fp2Config as Fp2Config>::Fp;
+
+impl<P, F, ConstraintF> CubicExtVarConfig<Fp2VarWithBase<P::Fp2Config, F, ConstraintF>, ConstraintF>
+    for Fp6ConfigWrapper<P>
+where
+    P: Fp6Config,
+    F: FieldVar<BasePrimeField<P>, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, BasePrimeField<P>, F>,
+    ConstraintF: PrimeField,
+{
     fn mul_base_field_vars_by_frob_coeff(
-        c1: &mut Fp2Var<P::Fp2Config>,
-        c2: &mut Fp2Var<P::Fp2Config>,
+        c1: &mut Fp2VarWithBase<P::Fp2Config, F, ConstraintF>,
+        c2: &mut Fp2VarWithBase<P::Fp2Config, F, ConstraintF>,
         power: usize,
     ) {
         *c1 *= Self::FROBENIUS_COEFF_C1[power % Self::DEGREE_OVER_BASE_PRIME_FIELD];
@@ -22,9 +40,18 @@ impl<P: Fp6Config> CubicExtVarConfig<Fp2Var<P::Fp2Config>> for Fp6ConfigWrapper<
     }
 }
 
-impl<P: Fp6Config> Fp6Var<P> {
+impl<P, F, ConstraintF> Fp6VarWithBase<P, F, ConstraintF>
+where
+    P: Fp6Config,
+    F: FieldVar<BasePrimeField<P>, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, BasePrimeField<P>, F>,
+    ConstraintF: PrimeField,
+{
     /// Multiplies `self` by a sparse element which has `c0 == c2 == zero`.
-    pub fn mul_by_0_c1_0(&self, c1: &Fp2Var<P::Fp2Config>) -> Result<Self, SynthesisError> {
+    pub fn mul_by_0_c1_0(
+        &self,
+        c1: &Fp2VarWithBase<P::Fp2Config, F, ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
         // Karatsuba multiplication
         // v0 = a0 * b0 = 0
 
@@ -54,8 +81,8 @@ impl<P: Fp6Config> Fp6Var<P> {
     /// Multiplies `self` by a sparse element which has `c2 == zero`.
     pub fn mul_by_c0_c1_0(
         &self,
-        c0: &Fp2Var<P::Fp2Config>,
-        c1: &Fp2Var<P::Fp2Config>,
+        c0: &Fp2VarWithBase<P::Fp2Config, F, ConstraintF>,
+        c1: &Fp2VarWithBase<P::Fp2Config, F, ConstraintF>,
     ) -> Result<Self, SynthesisError> {
         let v0 = &self.c0 * c0;
         let v1 = &self.c1 * c1;
@@ -79,7 +106,13 @@ impl<P: Fp6Config> Fp6Var<P> {
     }
 }
 
-impl<P: Fp6Config> MulAssign<Fp2<P::Fp2Config>> for Fp6Var<P> {
+impl<P, F, ConstraintF> MulAssign<Fp2<P::Fp2Config>> for Fp6VarWithBase<P, F, ConstraintF>
+where
+    P: Fp6Config,
+    F: FieldVar<BasePrimeField<P>, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, BasePrimeField<P>, F>,
+    ConstraintF: PrimeField,
+{
     fn mul_assign(&mut self, other: Fp2<P::Fp2Config>) {
         self.c0 *= other;
         self.c1 *= other;
@@ -5,7 +5,7 @@ use core::{
     ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
 };
 
-use crate::prelude::*;
+use crate::{prelude::*, Vec};
 
 /// This module contains a generic implementation of cubic extension field
 /// variables. That is, it implements the R1CS equivalent of
@@ -24,6 +24,11 @@ pub mod fp;
 /// variables. It emulates `Fp` arithmetic using `Fq` operations, where `p != q`.
 pub mod nonnative;
 
+/// This module contains gadgets for hashing byte strings to field elements,
+/// as specified in RFC 9380. That is, it implements the R1CS equivalent of
+/// `ark_ff::field_hashers`.
+pub mod field_hashers;
+
 /// This module contains a generic implementation of the degree-12 tower
 /// extension field. That is, it implements the R1CS equivalent of
 /// `ark_ff::Fp12`
@@ -228,4 +233,155 @@ pub trait FieldVar<F: Field, ConstraintF: Field>:
         }
         Ok(res)
     }
+
+    /// Returns a `Boolean` representing whether `self <= -self`, with respect
+    /// to the (lexicographic) ordering of `F`.
+    ///
+    /// This is the notion of sign that `ark-serialize` uses to distinguish
+    /// between `y` and `-y` when compressing curve points. In particular, zero
+    /// is positive.
+    #[tracing::instrument(target = "r1cs")]
+    fn is_positive(&self) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        // `self` is ordered by comparing its components over the base prime field,
+        // starting from the last one. The first component `c` (from the end) that is
+        // non-zero thus decides whether `self <= -self`, which holds iff
+        // `c <= (p - 1) / 2`.
+        let bits = self.to_bits_le()?;
+        let num_components = F::extension_degree() as usize;
+        let half_modulus = F::BasePrimeField::MODULUS_MINUS_ONE_DIV_TWO;
+
+        let mut result = Boolean::TRUE;
+        for component in bits.chunks(bits.len() / num_components) {
+            let is_zero = Boolean::kary_or(component)?.not();
+            let component_is_positive = bits_le_are_at_most(component, half_modulus)?;
+            result = is_zero.select(&result, &component_is_positive)?;
+        }
+        Ok(result)
+    }
+
+    /// Returns the square root of `self` whose sign (see
+    /// [`FieldVar::is_positive`]) is given by `is_positive`.
+    ///
+    /// The constraint system will be unsatisfiable if `self` is not a square,
+    /// or if `self == 0` and `is_positive` is false.
+    #[tracing::instrument(target = "r1cs")]
+    fn sqrt_with_sign(&self, is_positive: &Boolean<ConstraintF>) -> Result<Self, SynthesisError> {
+        let cs = self.cs().or(is_positive.cs());
+        let mode = if cs.is_none() {
+            AllocationMode::Constant
+        } else {
+            AllocationMode::Witness
+        };
+        let root = Self::new_variable(
+            ark_relations::ns!(cs, "sqrt"),
+            || {
+                let root = self.value()?.sqrt().unwrap_or_else(F::zero);
+                if (root <= -root) == is_positive.value()? {
+                    Ok(root)
+                } else {
+                    Ok(-root)
+                }
+            },
+            mode,
+        )?;
+        root.square_equals(self)?;
+        root.is_positive()?.enforce_equal(is_positive)?;
+        Ok(root)
+    }
+}
+
+/// Returns a `Boolean` representing whether the integer with little-endian
+/// representation `bits` is at most `element`.
+fn bits_le_are_at_most<F: Field>(
+    bits: &[Boolean<F>],
+    element: impl AsRef<[u64]>,
+) -> Result<Boolean<F>, SynthesisError> {
+    let element_bits = ark_ff::BitIteratorLE::new(element).chain(core::iter::repeat(false));
+    // Starting from the least significant bit, `result` records whether the bits
+    // seen so far encode an integer that is at most the corresponding bits of
+    // `element`.
+    let mut result = Boolean::TRUE;
+    for (bit, element_bit) in bits.iter().zip(element_bits) {
+        result = if element_bit {
+            bit.not().or(&result)?
+        } else {
+            bit.not().and(&result)?
+        };
+    }
+    Ok(result)
+}
+
+/// Returns whether `F` is emulated in `ConstraintF`, i.e. whether its
+/// variables are `NonNativeFieldVar`s rather than `FpVar`s.
+pub(crate) fn is_emulated<F: PrimeField, ConstraintF: PrimeField>() -> bool {
+    F::MODULUS.as_ref() != ConstraintF::MODULUS.as_ref()
+}
+
+/// Returns the coefficients of the product of the polynomials with
+/// coefficients `a` and `b`, in increasing order of degree.
+///
+/// The coefficients of the product are allocated as witnesses, and the
+/// product is checked by evaluating both sides at `0, 1, -1, 2, -2, ...` and
+/// at infinity. This costs one multiplication per coefficient of the product,
+/// instead of the `9` (resp. `18`) that Karatsuba multiplication needs for
+/// two polynomials with `4` (resp. `6`) coefficients.
+///
+/// If either polynomial is constant, schoolbook multiplication is used, as it
+/// requires no constraints at all.
+///
+/// This only pays off when allocating `V` and checking a product are cheap,
+/// as for `FpVar`. For emulated fields, every allocated coefficient has to be
+/// range-checked, and the evaluations at `±2, ±3, ...` have to be reduced
+/// before they can be multiplied, so that callers should use Karatsuba
+/// multiplication instead; see [`is_emulated`].
+pub(crate) fn mul_polynomials_by_evaluation<F, ConstraintF, V>(
+    a: &[V],
+    b: &[V],
+) -> Result<Vec<V>, SynthesisError>
+where
+    F: PrimeField,
+    ConstraintF: PrimeField,
+    V: FieldVar<F, ConstraintF>,
+    for<'a> &'a V: FieldOpsBounds<'a, F, V>,
+{
+    let num_coeffs = a.len() + b.len() - 1;
+    if a.is_constant() || b.is_constant() {
+        let mut product = vec![V::zero(); num_coeffs];
+        for (i, a_i) in a.iter().enumerate() {
+            for (j, b_j) in b.iter().enumerate() {
+                product[i + j] += a_i * b_j;
+            }
+        }
+        return Ok(product);
+    }
+
+    let cs = a.cs().or(b.cs());
+    let product = (0..num_coeffs)
+        .map(|k| {
+            V::new_witness(ark_relations::ns!(cs, "product coefficient"), || {
+                let (a, b) = (a.value()?, b.value()?);
+                Ok((0..=k)
+                    .filter(|i| *i < a.len() && k - i < b.len())
+                    .map(|i| a[i] * b[k - i])
+                    .sum::<F>())
+            })
+        })
+        .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+    let evaluate = |coeffs: &[V], point: F| {
+        coeffs
+            .iter()
+            .rev()
+            .fold(V::zero(), |acc, coeff| acc * point + coeff)
+    };
+    // The product has degree `num_coeffs - 1`, so it is determined by its
+    // evaluations at `num_coeffs - 1` finite points and its leading
+    // coefficient.
+    for i in 0..num_coeffs - 1 {
+        let point = F::from(i.div_ceil(2) as u64);
+        let point = if i % 2 == 0 { point } else { -point };
+        evaluate(a, point).mul_equals(&evaluate(b, point), &evaluate(&product, point))?;
+    }
+    a[a.len() - 1].mul_equals(&b[b.len() - 1], &product[num_coeffs - 1])?;
+    Ok(product)
 }
@@ -1,7 +1,7 @@
 use super::{
-    params::{get_params, OptimizationType},
+    params::{checked_params, DefaultNonNativeFieldParams, NonNativeFieldParams, OptimizationType},
     reduce::{bigint_to_basefield, limbs_to_bigint, Reducer},
-    AllocatedNonNativeFieldVar,
+    AllocatedNonNativeFieldVarWithParams,
 };
 use crate::{fields::fp::FpVar, prelude::*};
 use ark_ff::PrimeField;
@@ -9,13 +9,17 @@ use ark_relations::{
     ns,
     r1cs::{ConstraintSystemRef, OptimizationGoal, Result as R1CSResult},
 };
-use ark_std::{marker::PhantomData, vec::Vec};
+use ark_std::{cmp::min, marker::PhantomData, vec::Vec};
 use num_bigint::BigUint;
 
-/// The allocated form of `NonNativeFieldMulResultVar` (introduced below)
-#[derive(Debug)]
+/// The allocated form of `NonNativeFieldMulResultVarWithParams` (introduced below)
+#[derive(Clone, Debug)]
 #[must_use]
-pub struct AllocatedNonNativeFieldMulResultVar<TargetField: PrimeField, BaseField: PrimeField> {
+pub struct AllocatedNonNativeFieldMulResultVarWithParams<
+    TargetField: PrimeField,
+    BaseField: PrimeField,
+    P: NonNativeFieldParams,
+> {
     /// Constraint system reference
     pub cs: ConstraintSystemRef<BaseField>,
     /// Limbs of the intermediate representations
@@ -24,14 +28,25 @@ pub struct AllocatedNonNativeFieldMulResultVar<TargetField: PrimeField, BaseFiel
     pub prod_of_num_of_additions: BaseField,
     #[doc(hidden)]
     pub target_phantom: PhantomData<TargetField>,
+    #[doc(hidden)]
+    pub params_phantom: PhantomData<P>,
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField>
-    From<&AllocatedNonNativeFieldVar<TargetField, BaseField>>
-    for AllocatedNonNativeFieldMulResultVar<TargetField, BaseField>
+/// The allocated form of `NonNativeFieldMulResultVar`, with the default limb
+/// parameters.
+pub type AllocatedNonNativeFieldMulResultVar<TargetField, BaseField> =
+    AllocatedNonNativeFieldMulResultVarWithParams<
+        TargetField,
+        BaseField,
+        DefaultNonNativeFieldParams,
+    >;
+
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    From<&AllocatedNonNativeFieldVarWithParams<TargetField, BaseField, P>>
+    for AllocatedNonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>
 {
-    fn from(src: &AllocatedNonNativeFieldVar<TargetField, BaseField>) -> Self {
-        let params = get_params(
+    fn from(src: &AllocatedNonNativeFieldVarWithParams<TargetField, BaseField, P>) -> Self {
+        let params = checked_params::<P>(
             TargetField::MODULUS_BIT_SIZE as usize,
             BaseField::MODULUS_BIT_SIZE as usize,
             src.get_optimization_type(),
@@ -49,12 +64,13 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
             limbs,
             prod_of_num_of_additions,
             target_phantom: PhantomData,
+            params_phantom: PhantomData,
         }
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField>
-    AllocatedNonNativeFieldMulResultVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    AllocatedNonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>
 {
     /// Get the CS
     pub fn cs(&self) -> ConstraintSystemRef<BaseField> {
@@ -63,14 +79,14 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
 
     /// Get the value of the multiplication result
     pub fn value(&self) -> R1CSResult<TargetField> {
-        let params = get_params(
+        let params = checked_params::<P>(
             TargetField::MODULUS_BIT_SIZE as usize,
             BaseField::MODULUS_BIT_SIZE as usize,
             self.get_optimization_type(),
         );
 
         let p_representations =
-            AllocatedNonNativeFieldVar::<TargetField, BaseField>::get_limbs_representations_from_big_integer(
+            AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P>::get_limbs_representations_from_big_integer(
                 &<TargetField as PrimeField>::MODULUS,
                 self.get_optimization_type()
             )?;
@@ -88,8 +104,10 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
 
     /// Constraints for reducing the result of a multiplication mod p, to get an
     /// original representation.
-    pub fn reduce(&self) -> R1CSResult<AllocatedNonNativeFieldVar<TargetField, BaseField>> {
-        let params = get_params(
+    pub fn reduce(
+        &self,
+    ) -> R1CSResult<AllocatedNonNativeFieldVarWithParams<TargetField, BaseField, P>> {
+        let params = checked_params::<P>(
             TargetField::MODULUS_BIT_SIZE as usize,
             BaseField::MODULUS_BIT_SIZE as usize,
             self.get_optimization_type(),
@@ -97,7 +115,7 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
 
         // Step 1: get p
         let p_representations =
-            AllocatedNonNativeFieldVar::<TargetField, BaseField>::get_limbs_representations_from_big_integer(
+            AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P>::get_limbs_representations_from_big_integer(
                 &<TargetField as PrimeField>::MODULUS,
                 self.get_optimization_type()
             )?;
@@ -107,12 +125,13 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
         for limb in p_representations.iter() {
             p_gadget_limbs.push(FpVar::<BaseField>::new_constant(self.cs(), limb)?);
         }
-        let p_gadget = AllocatedNonNativeFieldVar::<TargetField, BaseField> {
+        let p_gadget = AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P> {
             cs: self.cs(),
             limbs: p_gadget_limbs,
             num_of_additions_over_normal_form: BaseField::one(),
             is_in_the_normal_form: false,
             target_phantom: PhantomData,
+            params_phantom: PhantomData,
         };
 
         // Step 2: compute surfeit
@@ -171,22 +190,24 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
             limbs
         };
 
-        let k_gadget = AllocatedNonNativeFieldVar::<TargetField, BaseField> {
+        let k_gadget = AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P> {
             cs: self.cs(),
             limbs: k_limbs,
             num_of_additions_over_normal_form: self.prod_of_num_of_additions,
             is_in_the_normal_form: false,
             target_phantom: PhantomData,
+            params_phantom: PhantomData,
         };
 
         let cs = self.cs();
 
-        let r_gadget = AllocatedNonNativeFieldVar::<TargetField, BaseField>::new_witness(
-            ns!(cs, "r"),
-            || Ok(self.value()?),
-        )?;
+        let r_gadget =
+            AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P>::new_witness(
+                ns!(cs, "r"),
+                || self.value(),
+            )?;
 
-        let params = get_params(
+        let params = checked_params::<P>(
             TargetField::MODULUS_BIT_SIZE as usize,
             BaseField::MODULUS_BIT_SIZE as usize,
             self.get_optimization_type(),
@@ -213,6 +234,7 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
                 + BaseField::one())
                 * (k_gadget.num_of_additions_over_normal_form + BaseField::one()),
             target_phantom: PhantomData,
+            params_phantom: PhantomData,
         };
 
         let kp_plus_r_limbs_len = kp_plus_r_gadget.limbs.len();
@@ -220,7 +242,7 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
             kp_plus_r_gadget.limbs[kp_plus_r_limbs_len - 1 - i] += limb;
         }
 
-        Reducer::<TargetField, BaseField>::group_and_check_equality(
+        Reducer::<TargetField, BaseField, P>::group_and_check_equality(
             surfeit,
             2 * params.bits_per_limb,
             params.bits_per_limb,
@@ -231,40 +253,118 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
         Ok(r_gadget)
     }
 
+    /// Returns an upper bound on the bit length of each limb of `self`, as
+    /// tracked by `prod_of_num_of_additions`.
+    pub fn limb_bit_bound(&self) -> usize {
+        Self::limb_bit_bound_for(self.prod_of_num_of_additions, self.get_optimization_type())
+    }
+
+    fn limb_bit_bound_for(
+        prod_of_num_of_additions: BaseField,
+        optimization_type: OptimizationType,
+    ) -> usize {
+        let params = checked_params::<P>(
+            TargetField::MODULUS_BIT_SIZE as usize,
+            BaseField::MODULUS_BIT_SIZE as usize,
+            optimization_type,
+        );
+
+        2 * params.bits_per_limb + overhead!(prod_of_num_of_additions + BaseField::one()) + 2
+    }
+
+    /// Check whether an unreduced element whose limbs have at most
+    /// `limb_bit_bound` bits can still be reduced by `reduce`.
+    fn is_reducible(limb_bit_bound: usize, optimization_type: OptimizationType) -> bool {
+        let params = checked_params::<P>(
+            TargetField::MODULUS_BIT_SIZE as usize,
+            BaseField::MODULUS_BIT_SIZE as usize,
+            optimization_type,
+        );
+
+        // `group_and_check_equality` needs room for at least one limb per group.
+        limb_bit_bound + params.bits_per_limb + 4 <= BaseField::MODULUS_BIT_SIZE as usize
+    }
+
+    /// Reduce `self` and convert it back into the intermediate representation.
+    fn renormalize(&self) -> R1CSResult<Self> {
+        Ok(Self::from(&self.reduce()?))
+    }
+
     /// Add unreduced elements.
+    ///
+    /// If the sum could no longer be reduced, the summand with the larger
+    /// bound is reduced first.
     #[tracing::instrument(target = "r1cs")]
     pub fn add(&self, other: &Self) -> R1CSResult<Self> {
-        assert_eq!(self.get_optimization_type(), other.get_optimization_type());
+        let optimization_type = self.get_optimization_type();
+        assert_eq!(optimization_type, other.get_optimization_type());
+
+        let mut this = self.clone();
+        let mut other = other.clone();
+        loop {
+            let bound = Self::limb_bit_bound_for(
+                this.prod_of_num_of_additions + other.prod_of_num_of_additions,
+                optimization_type,
+            );
+            if Self::is_reducible(bound, optimization_type) {
+                break;
+            }
+
+            let this_is_fresh = this.prod_of_num_of_additions.is_one();
+            let other_is_fresh = other.prod_of_num_of_additions.is_one();
+            if this_is_fresh && other_is_fresh {
+                break;
+            } else if !this_is_fresh
+                && (other_is_fresh
+                    || this.prod_of_num_of_additions >= other.prod_of_num_of_additions)
+            {
+                this = this.renormalize()?;
+            } else {
+                other = other.renormalize()?;
+            }
+        }
 
         let mut new_limbs = Vec::new();
 
-        for (l1, l2) in self.limbs.iter().zip(other.limbs.iter()) {
+        for (l1, l2) in this.limbs.iter().zip(other.limbs.iter()) {
             let new_limb = l1 + l2;
             new_limbs.push(new_limb);
         }
 
         Ok(Self {
-            cs: self.cs(),
+            cs: this.cs().or(other.cs()),
             limbs: new_limbs,
-            prod_of_num_of_additions: self.prod_of_num_of_additions
+            prod_of_num_of_additions: this.prod_of_num_of_additions
                 + other.prod_of_num_of_additions,
             target_phantom: PhantomData,
+            params_phantom: PhantomData,
         })
     }
 
     /// Add native constant elem
     #[tracing::instrument(target = "r1cs")]
     pub fn add_constant(&self, other: &TargetField) -> R1CSResult<Self> {
+        let optimization_type = self.get_optimization_type();
+        let bound = Self::limb_bit_bound_for(
+            self.prod_of_num_of_additions + BaseField::one(),
+            optimization_type,
+        );
+        let this = if Self::is_reducible(bound, optimization_type) {
+            self.clone()
+        } else {
+            self.renormalize()?
+        };
+
         let mut other_limbs =
-            AllocatedNonNativeFieldVar::<TargetField, BaseField>::get_limbs_representations(
+            AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P>::get_limbs_representations(
                 other,
-                self.get_optimization_type(),
+                this.get_optimization_type(),
             )?;
         other_limbs.reverse();
 
         let mut new_limbs = Vec::new();
 
-        for (i, limb) in self.limbs.iter().rev().enumerate() {
+        for (i, limb) in this.limbs.iter().rev().enumerate() {
             if i < other_limbs.len() {
                 new_limbs.push(limb + other_limbs[i]);
             } else {
@@ -274,14 +374,130 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
 
         new_limbs.reverse();
 
+        Ok(Self {
+            cs: this.cs(),
+            limbs: new_limbs,
+            prod_of_num_of_additions: this.prod_of_num_of_additions + BaseField::one(),
+            target_phantom: PhantomData,
+            params_phantom: PhantomData,
+        })
+    }
+
+    /// Negate an unreduced element.
+    ///
+    /// The result is `pad - self`, where `pad` is a multiple of the modulus
+    /// whose limbs are larger than those of `self`, so that all limbs stay
+    /// non-negative. This increases the bound on the limbs by
+    /// `log2(num_limbs) + 2` bits.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn negate(&self) -> R1CSResult<Self> {
+        let params = checked_params::<P>(
+            TargetField::MODULUS_BIT_SIZE as usize,
+            BaseField::MODULUS_BIT_SIZE as usize,
+            self.get_optimization_type(),
+        );
+        let surfeit = overhead!(self.prod_of_num_of_additions + BaseField::one()) + 2;
+
+        // Step 1: construct the padding
+        //
+        // `reduce` requires each limb to be smaller than `2^(2 * bits_per_limb +
+        // surfeit)`, and the whole value to be smaller than `p * 2^(|p| +
+        // surfeit)`, which also bounds the more significant limbs.
+        let mut pad_limbs_bits = Vec::new();
+        for i in 0..self.limbs.len() {
+            pad_limbs_bits.push(min(
+                2 * params.bits_per_limb + surfeit,
+                2 * TargetField::MODULUS_BIT_SIZE as usize + surfeit - params.bits_per_limb * i,
+            ));
+        }
+
+        // Step 2: prepare to pad the padding to k * p for some k
+        let two = TargetField::from(2u64);
+        let pad_value = pad_limbs_bits
+            .iter()
+            .enumerate()
+            .map(|(i, bits)| two.pow([(bits + params.bits_per_limb * i) as u64]))
+            .sum::<TargetField>();
+        let mut pad_to_kp_limbs =
+            AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P>::get_limbs_representations(
+                &-pad_value,
+                self.get_optimization_type(),
+            )?;
+        pad_to_kp_limbs.reverse();
+
+        // Step 3: the result is pad + pad_to_kp - self
+        let mut new_limbs = Vec::new();
+        for (i, (limb, bits)) in self.limbs.iter().rev().zip(pad_limbs_bits).enumerate() {
+            let mut pad_limb = BaseField::from(2u64).pow([bits as u64]);
+            if i < pad_to_kp_limbs.len() {
+                pad_limb += pad_to_kp_limbs[i];
+            }
+            new_limbs.push(FpVar::constant(pad_limb) - limb);
+        }
+        new_limbs.reverse();
+
         Ok(Self {
             cs: self.cs(),
             limbs: new_limbs,
-            prod_of_num_of_additions: self.prod_of_num_of_additions + BaseField::one(),
+            prod_of_num_of_additions: BaseField::from(2u64)
+                .pow([(surfeit - 2 + ark_std::log2(params.num_limbs) as usize) as u64]),
             target_phantom: PhantomData,
+            params_phantom: PhantomData,
         })
     }
 
+    /// Subtract unreduced elements.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn sub(&self, other: &Self) -> R1CSResult<Self> {
+        assert_eq!(self.get_optimization_type(), other.get_optimization_type());
+
+        self.add(&other.negate()?)
+    }
+
+    /// Multiply by a native constant elem.
+    ///
+    /// If the constant (or its negation) is small enough, the limbs are
+    /// scaled directly, which is free. Otherwise, `self` is reduced first.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn mul_constant(&self, other: &TargetField) -> R1CSResult<Self> {
+        let value: BigUint = (*other).into();
+        let neg_value: BigUint = (-*other).into();
+        let (scalar, is_negated) = if neg_value < value {
+            (neg_value, true)
+        } else {
+            (value, false)
+        };
+
+        let params = checked_params::<P>(
+            TargetField::MODULUS_BIT_SIZE as usize,
+            BaseField::MODULUS_BIT_SIZE as usize,
+            self.get_optimization_type(),
+        );
+        let mut scaled_limb_bit_bound = self.limb_bit_bound() + scalar.bits() as usize;
+        if is_negated {
+            scaled_limb_bit_bound += ark_std::log2(params.num_limbs) as usize + 2;
+        }
+        if Self::is_reducible(scaled_limb_bit_bound, self.get_optimization_type()) {
+            let scalar = bigint_to_basefield::<BaseField>(&scalar);
+            let scaled = Self {
+                cs: self.cs(),
+                limbs: self.limbs.iter().map(|limb| limb * scalar).collect(),
+                prod_of_num_of_additions: self.prod_of_num_of_additions * scalar,
+                target_phantom: PhantomData,
+                params_phantom: PhantomData,
+            };
+
+            if is_negated {
+                scaled.negate()
+            } else {
+                Ok(scaled)
+            }
+        } else {
+            let other = AllocatedNonNativeFieldVarWithParams::new_constant(self.cs(), other)?;
+            self.reduce()?.mul_without_reduce(&other)
+        }
+    }
+
     pub(crate) fn get_optimization_type(&self) -> OptimizationType {
         match self.cs().optimization_goal() {
             OptimizationGoal::None => OptimizationType::Constraints,
@@ -0,0 +1,664 @@
+use super::{params::DefaultNonNativeFieldParams, reduce::Reducer};
+use crate::{
+    boolean::Boolean,
+    fields::{fp::FpVar, FieldVar},
+    prelude::*,
+};
+use ark_ff::PrimeField;
+use ark_relations::{
+    ns,
+    r1cs::{ConstraintSystemRef, Namespace, SynthesisError},
+};
+use ark_std::{borrow::Borrow, cmp::max, vec, vec::Vec};
+use core::cmp::Ordering;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+/// The limb-level helpers of `Reducer` only depend on the constraint field.
+type LimbReducer<F> = Reducer<F, F, DefaultNonNativeFieldParams>;
+
+/// An emulated unsigned integer of arbitrary width.
+///
+/// Fixed-width arithmetic is available through [`BigUintVar::truncate`],
+/// [`BigUintVar::wrapping_add`] and [`BigUintVar::wrapping_mul`], which
+/// reduce modulo `2^num_bits`.
+///
+/// The integer is represented by limbs of `BITS_PER_LIMB` bits each, stored
+/// as elements of the constraint field `F`, least significant limb first.
+/// Additions and multiplications are performed lazily on the limbs; the
+/// bound on each limb is tracked, and the limbs are normalized (i.e. carried
+/// back into `BITS_PER_LIMB` bits) only when they would otherwise overflow.
+///
+/// The constraint field must be large enough to hold the product of two
+/// limbs, i.e. roughly `2 * BITS_PER_LIMB + log2(num_limbs) + 4` bits.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct BigUintVar<F: PrimeField> {
+    /// The limbs, least significant first.
+    pub limbs: Vec<FpVar<F>>,
+    /// Upper bounds (inclusive) on the value of each limb.
+    pub limb_bounds: Vec<BigUint>,
+}
+
+impl<F: PrimeField> BigUintVar<F> {
+    /// The number of bits of each limb of a normalized `BigUintVar`.
+    pub const BITS_PER_LIMB: usize = 64;
+
+    /// Construct a constant `BigUintVar` from `value`.
+    pub fn constant(value: &BigUint) -> Self {
+        let num_limbs = max(1, Self::num_limbs_for(value.bits() as usize));
+        let limb_values = Self::split(value, num_limbs);
+        Self {
+            limbs: limb_values
+                .iter()
+                .map(|limb| FpVar::constant(F::from(limb.clone())))
+                .collect(),
+            limb_bounds: limb_values,
+        }
+    }
+
+    /// Allocates a `BigUintVar` of `num_bits` bits in the given mode.
+    ///
+    /// In `AllocationMode::Witness` mode, each limb is range-checked, so that
+    /// the value is guaranteed to be smaller than `2^num_bits`. Unlike
+    /// [`AllocVar::new_variable`], the number of limbs does not depend on the
+    /// value, so this should be used whenever the value is not known when
+    /// the constraints are generated.
+    pub fn new_variable_with_num_bits<T: Borrow<BigUint>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        num_bits: usize,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        let num_limbs = max(1, Self::num_limbs_for(num_bits));
+        let limb_values = f().map(|v| Self::split(v.borrow(), num_limbs));
+
+        let mut limbs = Vec::new();
+        let mut limb_bounds = Vec::new();
+        for i in 0..num_limbs {
+            let limb_bits = num_bits
+                .saturating_sub(i * Self::BITS_PER_LIMB)
+                .min(Self::BITS_PER_LIMB);
+            let limb = FpVar::new_variable(
+                ns!(cs, "limb"),
+                || {
+                    limb_values
+                        .as_ref()
+                        .map(|limbs| F::from(limbs[i].clone()))
+                        .map_err(|e| *e)
+                },
+                mode,
+            )?;
+            if mode == AllocationMode::Witness {
+                LimbReducer::<F>::limb_to_bits(&limb, limb_bits)?;
+            }
+            limbs.push(limb);
+            limb_bounds.push((BigUint::one() << limb_bits) - 1u8);
+        }
+
+        Ok(Self { limbs, limb_bounds })
+    }
+
+    /// Allocates a witness `BigUintVar` of `num_bits` bits.
+    pub fn new_witness_with_num_bits<T: Borrow<BigUint>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        num_bits: usize,
+    ) -> Result<Self, SynthesisError> {
+        Self::new_variable_with_num_bits(cs, f, num_bits, AllocationMode::Witness)
+    }
+
+    /// Allocates a public input `BigUintVar` of `num_bits` bits.
+    pub fn new_input_with_num_bits<T: Borrow<BigUint>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        num_bits: usize,
+    ) -> Result<Self, SynthesisError> {
+        Self::new_variable_with_num_bits(cs, f, num_bits, AllocationMode::Input)
+    }
+
+    /// Constructs a `BigUintVar` from its little-endian bit decomposition.
+    ///
+    /// This does not generate any constraints.
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    pub fn from_bits_le(bits: &[Boolean<F>]) -> Result<Self, SynthesisError> {
+        if bits.is_empty() {
+            return Ok(Self::constant(&BigUint::zero()));
+        }
+
+        let mut limbs = Vec::new();
+        let mut limb_bounds = Vec::new();
+        for chunk in bits.chunks(Self::BITS_PER_LIMB) {
+            limbs.push(Boolean::le_bits_to_fp_var(chunk)?);
+            limb_bounds.push((BigUint::one() << chunk.len()) - 1u8);
+        }
+
+        Ok(Self { limbs, limb_bounds })
+    }
+
+    fn num_limbs_for(num_bits: usize) -> usize {
+        num_bits.div_ceil(Self::BITS_PER_LIMB)
+    }
+
+    /// Split `value` into `num_limbs` limbs of `BITS_PER_LIMB` bits.
+    fn split(value: &BigUint, num_limbs: usize) -> Vec<BigUint> {
+        let mask = (BigUint::one() << Self::BITS_PER_LIMB) - 1u8;
+        (0..num_limbs)
+            .map(|i| (value >> (i * Self::BITS_PER_LIMB)) & &mask)
+            .collect()
+    }
+
+    /// The largest bit length that the limbs may have before they can no
+    /// longer be compared with `Reducer::group_and_check_equality`.
+    fn max_limb_bits() -> usize {
+        F::MODULUS_BIT_SIZE as usize - 4
+    }
+
+    /// Returns an upper bound on the bit length of the limbs of `self`.
+    fn limb_bits(&self) -> usize {
+        self.limb_bounds
+            .iter()
+            .map(|bound| bound.bits() as usize)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns an upper bound on the value of `self`.
+    pub fn max_value(&self) -> BigUint {
+        self.limb_bounds
+            .iter()
+            .enumerate()
+            .map(|(i, bound)| bound << (i * Self::BITS_PER_LIMB))
+            .sum()
+    }
+
+    /// Returns an upper bound on the bit length of the value of `self`.
+    pub fn num_bits(&self) -> usize {
+        self.max_value().bits() as usize
+    }
+
+    /// Returns whether every limb of `self` fits in `BITS_PER_LIMB` bits.
+    pub fn is_normalized(&self) -> bool {
+        self.limb_bits() <= Self::BITS_PER_LIMB
+    }
+
+    /// Returns the limbs of `self`, padded with zeros to `num_limbs` limbs
+    /// and ordered most significant limb first.
+    fn padded_limbs_be(&self, num_limbs: usize) -> Vec<FpVar<F>> {
+        let mut limbs = self.limbs.clone();
+        limbs.resize(num_limbs, FpVar::zero());
+        limbs.reverse();
+        limbs
+    }
+
+    /// Outputs an equivalent `BigUintVar` whose limbs all fit in
+    /// `BITS_PER_LIMB` bits.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn normalize(&self) -> Result<Self, SynthesisError> {
+        if self.is_normalized() {
+            return Ok(self.clone());
+        }
+
+        let cs = self.cs();
+        if cs.is_none() {
+            return Ok(Self::constant(&self.value()?));
+        }
+
+        let normal_form = Self::new_witness_with_num_bits(
+            ns!(cs, "normal_form"),
+            || self.value(),
+            self.num_bits(),
+        )?;
+        self.enforce_equal_unnormalized(&normal_form)?;
+        Ok(normal_form)
+    }
+
+    /// Enforce that `self` and `other` represent the same integer, without
+    /// normalizing either of them first.
+    fn enforce_equal_unnormalized(&self, other: &Self) -> Result<(), SynthesisError> {
+        if self.is_constant() && other.is_constant() {
+            return if self.value()? == other.value()? {
+                Ok(())
+            } else {
+                Err(SynthesisError::Unsatisfiable)
+            };
+        }
+
+        let num_limbs = max(self.limbs.len(), other.limbs.len());
+        let limb_bits = max(
+            max(self.limb_bits(), other.limb_bits()),
+            Self::BITS_PER_LIMB,
+        );
+        assert!(
+            limb_bits <= Self::max_limb_bits(),
+            "The limbs are too large to be compared."
+        );
+
+        LimbReducer::<F>::group_and_check_equality(
+            0,
+            limb_bits,
+            Self::BITS_PER_LIMB,
+            &self.padded_limbs_be(num_limbs),
+            &other.padded_limbs_be(num_limbs),
+        )
+    }
+
+    /// Add two `BigUintVar`s.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn add(&self, other: &Self) -> Result<Self, SynthesisError> {
+        if max(self.limb_bits(), other.limb_bits()) + 1 > Self::max_limb_bits() {
+            return self.normalize()?.add(&other.normalize()?);
+        }
+
+        let num_limbs = max(self.limbs.len(), other.limbs.len());
+        let mut limbs = Vec::with_capacity(num_limbs);
+        let mut limb_bounds = Vec::with_capacity(num_limbs);
+        for i in 0..num_limbs {
+            let (limb, bound) = match (self.limbs.get(i), other.limbs.get(i)) {
+                (Some(a), Some(b)) => (a + b, &self.limb_bounds[i] + &other.limb_bounds[i]),
+                (Some(a), None) => (a.clone(), self.limb_bounds[i].clone()),
+                (None, Some(b)) => (b.clone(), other.limb_bounds[i].clone()),
+                (None, None) => unreachable!(),
+            };
+            limbs.push(limb);
+            limb_bounds.push(bound);
+        }
+
+        Ok(Self { limbs, limb_bounds })
+    }
+
+    /// Multiply two `BigUintVar`s.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn mul(&self, other: &Self) -> Result<Self, SynthesisError> {
+        let num_limbs = self.limbs.len() + other.limbs.len() - 1;
+        let mut limb_bounds = vec![BigUint::zero(); num_limbs];
+        for (i, a) in self.limb_bounds.iter().enumerate() {
+            for (j, b) in other.limb_bounds.iter().enumerate() {
+                limb_bounds[i + j] += a * b;
+            }
+        }
+
+        if limb_bounds
+            .iter()
+            .any(|bound| bound.bits() as usize > Self::max_limb_bits())
+        {
+            assert!(
+                !(self.is_normalized() && other.is_normalized()),
+                "The constraint field is too small for the product of the limbs."
+            );
+            return self.normalize()?.mul(&other.normalize()?);
+        }
+
+        let limbs = if self.is_constant() || other.is_constant() {
+            // Multiplying by constants is free, so the product limbs can be
+            // computed directly.
+            let mut limbs = vec![FpVar::zero(); num_limbs];
+            for (i, a) in self.limbs.iter().enumerate() {
+                for (j, b) in other.limbs.iter().enumerate() {
+                    limbs[i + j] += a * b;
+                }
+            }
+            limbs
+        } else {
+            let cs = self.cs().or(other.cs());
+            let mut limbs = Vec::with_capacity(num_limbs);
+            for k in 0..num_limbs {
+                limbs.push(FpVar::new_witness(ns!(cs, "limb product"), || {
+                    let mut z_k = F::zero();
+                    for i in 0..=k.min(self.limbs.len() - 1) {
+                        if let Some(b) = other.limbs.get(k - i) {
+                            z_k += self.limbs[i].value()? * b.value()?;
+                        }
+                    }
+                    Ok(z_k)
+                })?);
+            }
+
+            // Check that the product limbs are the coefficients of the
+            // product of the polynomials whose coefficients are the limbs of
+            // `self` and `other`, by evaluating at `num_limbs` points. Since
+            // the bounds of the product limbs are smaller than the modulus,
+            // this implies that the limbs are correct.
+            for c in 0..num_limbs {
+                let c_pows: Vec<_> = (0..num_limbs)
+                    .map(|i| F::from((c + 1) as u128).pow([i as u64]))
+                    .collect();
+                let eval = |limbs: &[FpVar<F>]| {
+                    limbs
+                        .iter()
+                        .zip(&c_pows)
+                        .map(|(limb, c_pow)| limb * *c_pow)
+                        .fold(FpVar::zero(), |sum, i| sum + i)
+                };
+
+                eval(&self.limbs).mul_equals(&eval(&other.limbs), &eval(&limbs))?;
+            }
+            limbs
+        };
+
+        Ok(Self { limbs, limb_bounds })
+    }
+
+    /// Outputs `self mod 2^num_bits`, i.e. the `num_bits` least significant
+    /// bits of `self`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn truncate(&self, num_bits: usize) -> Result<Self, SynthesisError> {
+        let self_bits = self.num_bits();
+        if self_bits <= num_bits {
+            return Ok(self.clone());
+        }
+
+        let mask = (BigUint::one() << num_bits) - 1u8;
+        let cs = self.cs();
+        if cs.is_none() {
+            return Ok(Self::constant(&(self.value()? & mask)));
+        }
+
+        // `self = high * 2^num_bits + low`, where `low < 2^num_bits` and
+        // `high < 2^(self_bits - num_bits)`.
+        let low = Self::new_witness_with_num_bits(
+            ns!(cs, "low"),
+            || Ok(self.value()? & &mask),
+            num_bits,
+        )?;
+        let high = Self::new_witness_with_num_bits(
+            ns!(cs, "high"),
+            || Ok(self.value()? >> num_bits),
+            self_bits - num_bits,
+        )?;
+        high.mul(&Self::constant(&(BigUint::one() << num_bits)))?
+            .add(&low)?
+            .enforce_equal_unnormalized(self)?;
+
+        Ok(low)
+    }
+
+    /// Outputs `(self + other) mod 2^num_bits`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn wrapping_add(&self, other: &Self, num_bits: usize) -> Result<Self, SynthesisError> {
+        self.add(other)?.truncate(num_bits)
+    }
+
+    /// Outputs `(self * other) mod 2^num_bits`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn wrapping_mul(&self, other: &Self, num_bits: usize) -> Result<Self, SynthesisError> {
+        self.mul(other)?.truncate(num_bits)
+    }
+
+    /// Outputs the quotient and the remainder of the division of `self` by
+    /// `modulus`.
+    ///
+    /// The quotient is allocated with as many bits as `self`, so this works
+    /// for any non-zero `modulus`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn div_rem(&self, modulus: &Self) -> Result<(Self, Self), SynthesisError> {
+        let cs = self.cs().or(modulus.cs());
+        if cs.is_none() {
+            let modulus = modulus.value()?;
+            if modulus.is_zero() {
+                return Err(SynthesisError::DivisionByZero);
+            }
+            let value = self.value()?;
+            return Ok((
+                Self::constant(&(&value / &modulus)),
+                Self::constant(&(value % modulus)),
+            ));
+        }
+
+        let values = || -> Result<(BigUint, BigUint), SynthesisError> {
+            let modulus = modulus.value()?;
+            if modulus.is_zero() {
+                return Err(SynthesisError::DivisionByZero);
+            }
+            let value = self.value()?;
+            Ok((&value / &modulus, value % modulus))
+        };
+        let quotient = Self::new_witness_with_num_bits(
+            ns!(cs, "quotient"),
+            || Ok(values()?.0),
+            self.num_bits(),
+        )?;
+        let remainder = Self::new_witness_with_num_bits(
+            ns!(cs, "remainder"),
+            || Ok(values()?.1),
+            modulus.num_bits(),
+        )?;
+
+        remainder.enforce_cmp(modulus, Ordering::Less, false)?;
+        quotient
+            .mul(modulus)?
+            .add(&remainder)?
+            .enforce_equal_unnormalized(self)?;
+
+        Ok((quotient, remainder))
+    }
+
+    /// Outputs `self mod modulus`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn rem(&self, modulus: &Self) -> Result<Self, SynthesisError> {
+        Ok(self.div_rem(modulus)?.1)
+    }
+
+    /// Outputs `(self * other) mod modulus`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn mul_mod(&self, other: &Self, modulus: &Self) -> Result<Self, SynthesisError> {
+        self.mul(other)?.rem(modulus)
+    }
+
+    /// Outputs `self^exp mod modulus`, where `exp` is given by its
+    /// little-endian bit decomposition `exp_bits_le`.
+    ///
+    /// Constant bits of the exponent (e.g. a public RSA exponent) do not
+    /// require any conditional selection.
+    #[tracing::instrument(target = "r1cs", skip(exp_bits_le))]
+    pub fn pow_mod_le(
+        &self,
+        exp_bits_le: &[Boolean<F>],
+        modulus: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let base = self.rem(modulus)?;
+
+        // Squaring and multiplying `result` are skipped while it is still one.
+        let mut result = Self::constant(&BigUint::one());
+        let mut is_one = true;
+        for bit in exp_bits_le.iter().rev() {
+            if !is_one {
+                result = result.mul_mod(&result, modulus)?;
+            }
+            if bit.is_constant() && !bit.value()? {
+                continue;
+            }
+
+            let product = if is_one {
+                base.clone()
+            } else {
+                result.mul_mod(&base, modulus)?
+            };
+            result = Self::conditionally_select(bit, &product, &result)?;
+            is_one = false;
+        }
+
+        if is_one {
+            result = result.rem(modulus)?;
+        }
+        Ok(result)
+    }
+
+    /// This function enforces the ordering between `self` and `other`. The
+    /// constraint system will not be satisfied otherwise. If `self` should
+    /// also be checked for equality, e.g. `self <= other` instead of `self <
+    /// other`, set `should_also_check_quality` to `true`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn enforce_cmp(
+        &self,
+        other: &Self,
+        ordering: Ordering,
+        should_also_check_equality: bool,
+    ) -> Result<(), SynthesisError> {
+        self.is_cmp(other, ordering, should_also_check_equality)?
+            .enforce_equal(&Boolean::TRUE)
+    }
+
+    /// This function checks the ordering between `self` and `other`. It outputs
+    /// a `Boolean` that contains the result - `1` if true, `0` otherwise.
+    /// The constraint system will be satisfied in any case. If `self`
+    /// should also be checked for equality, e.g. `self <= other` instead of
+    /// `self < other`, set `should_also_check_quality` to `true`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn is_cmp(
+        &self,
+        other: &Self,
+        ordering: Ordering,
+        should_also_check_equality: bool,
+    ) -> Result<Boolean<F>, SynthesisError> {
+        let (left, right) = match ordering {
+            Ordering::Less => (self, other),
+            Ordering::Greater => (other, self),
+            Ordering::Equal => return Err(SynthesisError::Unsatisfiable),
+        };
+        left.is_smaller_than(right, should_also_check_equality)
+    }
+
+    /// Helper function to check `self < other` (or `self <= other` if
+    /// `or_equal` is set) and output a result bit.
+    fn is_smaller_than(&self, other: &Self, or_equal: bool) -> Result<Boolean<F>, SynthesisError> {
+        let this = self.normalize()?;
+        let other = other.normalize()?;
+        let num_limbs = max(this.limbs.len(), other.limbs.len());
+        let shift = F::from(2u64).pow([Self::BITS_PER_LIMB as u64]);
+
+        // Compare the limbs from the least significant one: `self < other` iff
+        // the most significant limb where they differ is smaller in `self`.
+        let mut result = Boolean::constant(or_equal);
+        for (a, b) in this
+            .padded_limbs_be(num_limbs)
+            .iter()
+            .zip(&other.padded_limbs_be(num_limbs))
+            .rev()
+        {
+            // `a - b + 2^BITS_PER_LIMB` is positive and smaller than
+            // `2^(BITS_PER_LIMB + 1)`; its top bit is set iff `a >= b`.
+            let bits = LimbReducer::<F>::limb_to_bits(&(a - b + shift), Self::BITS_PER_LIMB + 1)?;
+            let is_smaller = bits[0].not();
+            let is_equal = a.is_eq(b)?;
+            result = is_smaller.or(&is_equal.and(&result)?)?;
+        }
+        Ok(result)
+    }
+}
+
+impl<F: PrimeField> AllocVar<BigUint, F> for BigUintVar<F> {
+    /// Allocates a `BigUintVar` with as many bits as the value.
+    ///
+    /// The number of limbs depends on the value, so this fails with
+    /// `SynthesisError::AssignmentMissing` if the value is not available.
+    /// Use [`BigUintVar::new_variable_with_num_bits`] to allocate a
+    /// fixed number of bits instead.
+    fn new_variable<T: Borrow<BigUint>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let value = f()?.borrow().clone();
+        if mode == AllocationMode::Constant {
+            return Ok(Self::constant(&value));
+        }
+        let num_bits = value.bits() as usize;
+        Self::new_variable_with_num_bits(ns, || Ok(value), num_bits, mode)
+    }
+}
+
+impl<F: PrimeField> R1CSVar<F> for BigUintVar<F> {
+    type Value = BigUint;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.limbs.cs()
+    }
+
+    fn value(&self) -> Result<Self::Value, SynthesisError> {
+        let mut value = BigUint::zero();
+        for (i, limb) in self.limbs.iter().enumerate() {
+            let limb: BigUint = limb.value()?.into();
+            value += limb << (i * Self::BITS_PER_LIMB);
+        }
+        Ok(value)
+    }
+}
+
+impl<F: PrimeField> EqGadget<F> for BigUintVar<F> {
+    #[tracing::instrument(target = "r1cs")]
+    fn is_eq(&self, other: &Self) -> Result<Boolean<F>, SynthesisError> {
+        let this = self.normalize()?;
+        let other = other.normalize()?;
+        let num_limbs = max(this.limbs.len(), other.limbs.len());
+        this.padded_limbs_be(num_limbs)
+            .is_eq(&other.padded_limbs_be(num_limbs))
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn conditional_enforce_equal(
+        &self,
+        other: &Self,
+        should_enforce: &Boolean<F>,
+    ) -> Result<(), SynthesisError> {
+        if should_enforce.is_constant() {
+            return if should_enforce.value()? {
+                self.enforce_equal_unnormalized(other)
+            } else {
+                Ok(())
+            };
+        }
+
+        let this = self.normalize()?;
+        let other = other.normalize()?;
+        let num_limbs = max(this.limbs.len(), other.limbs.len());
+        this.padded_limbs_be(num_limbs)
+            .conditional_enforce_equal(&other.padded_limbs_be(num_limbs), should_enforce)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn conditional_enforce_not_equal(
+        &self,
+        other: &Self,
+        should_enforce: &Boolean<F>,
+    ) -> Result<(), SynthesisError> {
+        self.is_eq(other)?
+            .conditional_enforce_equal(&Boolean::FALSE, should_enforce)
+    }
+}
+
+impl<F: PrimeField> CondSelectGadget<F> for BigUintVar<F> {
+    #[tracing::instrument(target = "r1cs")]
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let num_limbs = max(true_value.limbs.len(), false_value.limbs.len());
+        let zero = BigUint::zero();
+        let mut limbs = Vec::with_capacity(num_limbs);
+        let mut limb_bounds = Vec::with_capacity(num_limbs);
+        for i in 0..num_limbs {
+            let t = true_value.limbs.get(i).cloned().unwrap_or_else(FpVar::zero);
+            let f = false_value
+                .limbs
+                .get(i)
+                .cloned()
+                .unwrap_or_else(FpVar::zero);
+            limbs.push(cond.select(&t, &f)?);
+            limb_bounds.push(
+                max(
+                    true_value.limb_bounds.get(i).unwrap_or(&zero),
+                    false_value.limb_bounds.get(i).unwrap_or(&zero),
+                )
+                .clone(),
+            );
+        }
+
+        Ok(Self { limbs, limb_bounds })
+    }
+}
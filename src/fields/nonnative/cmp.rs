@@ -0,0 +1,254 @@
+use super::{params::NonNativeFieldParams, NonNativeFieldVarWithParams};
+use crate::{boolean::Boolean, fields::FieldVar, prelude::*, ToBitsGadget};
+use ark_ff::PrimeField;
+use ark_relations::r1cs::SynthesisError;
+use core::cmp::Ordering;
+
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    NonNativeFieldVarWithParams<TargetField, BaseField, P>
+{
+    /// This function enforces the ordering between `self` and `other`. The
+    /// constraint system will not be satisfied otherwise. If `self` should
+    /// also be checked for equality, e.g. `self <= other` instead of `self <
+    /// other`, set `should_also_check_quality` to `true`. This variant
+    /// verifies `self` and `other` are `<= (p-1)/2`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn enforce_cmp(
+        &self,
+        other: &Self,
+        ordering: Ordering,
+        should_also_check_equality: bool,
+    ) -> Result<(), SynthesisError> {
+        let (left, right) = self.process_cmp_inputs(other, ordering, should_also_check_equality)?;
+        left.enforce_smaller_than(&right)
+    }
+
+    /// This function enforces the ordering between `self` and `other`. The
+    /// constraint system will not be satisfied otherwise. If `self` should
+    /// also be checked for equality, e.g. `self <= other` instead of `self <
+    /// other`, set `should_also_check_quality` to `true`. This variant
+    /// assumes `self` and `other` are `<= (p-1)/2` and does not generate
+    /// constraints to verify that.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn enforce_cmp_unchecked(
+        &self,
+        other: &Self,
+        ordering: Ordering,
+        should_also_check_equality: bool,
+    ) -> Result<(), SynthesisError> {
+        let (left, right) = self.process_cmp_inputs(other, ordering, should_also_check_equality)?;
+        left.enforce_smaller_than_unchecked(&right)
+    }
+
+    /// This function checks the ordering between `self` and `other`. It outputs
+    /// a `Boolean` that contains the result - `1` if true, `0` otherwise.
+    /// The constraint system will be satisfied in any case. If `self`
+    /// should also be checked for equality, e.g. `self <= other` instead of
+    /// `self < other`, set `should_also_check_quality` to `true`. This
+    /// variant verifies `self` and `other` are `<= (p-1)/2`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn is_cmp(
+        &self,
+        other: &Self,
+        ordering: Ordering,
+        should_also_check_equality: bool,
+    ) -> Result<Boolean<BaseField>, SynthesisError> {
+        let (left, right) = self.process_cmp_inputs(other, ordering, should_also_check_equality)?;
+        left.is_smaller_than(&right)
+    }
+
+    /// This function checks the ordering between `self` and `other`. It outputs
+    /// a `Boolean` that contains the result - `1` if true, `0` otherwise.
+    /// The constraint system will be satisfied in any case. If `self`
+    /// should also be checked for equality, e.g. `self <= other` instead of
+    /// `self < other`, set `should_also_check_quality` to `true`. This
+    /// variant assumes `self` and `other` are `<= (p-1)/2` and does not
+    /// generate constraints to verify that.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn is_cmp_unchecked(
+        &self,
+        other: &Self,
+        ordering: Ordering,
+        should_also_check_equality: bool,
+    ) -> Result<Boolean<BaseField>, SynthesisError> {
+        let (left, right) = self.process_cmp_inputs(other, ordering, should_also_check_equality)?;
+        left.is_smaller_than_unchecked(&right)
+    }
+
+    fn process_cmp_inputs(
+        &self,
+        other: &Self,
+        ordering: Ordering,
+        should_also_check_equality: bool,
+    ) -> Result<(Self, Self), SynthesisError> {
+        let (left, right) = match ordering {
+            Ordering::Less => (self, other),
+            Ordering::Greater => (other, self),
+            Ordering::Equal => return Err(SynthesisError::Unsatisfiable),
+        };
+        let right_for_check = if should_also_check_equality {
+            right + TargetField::one()
+        } else {
+            right.clone()
+        };
+
+        Ok((left.clone(), right_for_check))
+    }
+
+    /// Helper function to enforce that `self <= (p-1)/2`, where `p` is the
+    /// modulus of `TargetField`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn enforce_smaller_or_equal_than_mod_minus_one_div_two(
+        &self,
+    ) -> Result<(), SynthesisError> {
+        // `to_bits_le` fully reduces `self` and enforces that the limbs are the
+        // canonical representation of an element `< p`, so the bits are unique.
+        let _ = Boolean::enforce_smaller_or_equal_than_le(
+            &self.to_bits_le()?,
+            TargetField::MODULUS_MINUS_ONE_DIV_TWO,
+        )?;
+        Ok(())
+    }
+
+    /// Helper function to check `self < other` and output a result bit. This
+    /// function verifies `self` and `other` are `<= (p-1)/2`.
+    fn is_smaller_than(&self, other: &Self) -> Result<Boolean<BaseField>, SynthesisError> {
+        self.enforce_smaller_or_equal_than_mod_minus_one_div_two()?;
+        other.enforce_smaller_or_equal_than_mod_minus_one_div_two()?;
+        self.is_smaller_than_unchecked(other)
+    }
+
+    /// Helper function to check `self < other` and output a result bit. This
+    /// function assumes `self` and `other` are `<= (p-1)/2` and does not
+    /// generate constraints to verify that.
+    fn is_smaller_than_unchecked(
+        &self,
+        other: &Self,
+    ) -> Result<Boolean<BaseField>, SynthesisError> {
+        // If `self, other <= (p-1)/2`, then `2 * (self - other)` does not wrap
+        // around when `self >= other`, and wraps around (and hence is odd)
+        // exactly when `self < other`.
+        Ok((self - other)
+            .double()?
+            .to_bits_le()?
+            .first()
+            .unwrap()
+            .clone())
+    }
+
+    /// Helper function to enforce `self < other`. This function verifies `self`
+    /// and `other` are `<= (p-1)/2`.
+    fn enforce_smaller_than(&self, other: &Self) -> Result<(), SynthesisError> {
+        self.enforce_smaller_or_equal_than_mod_minus_one_div_two()?;
+        other.enforce_smaller_or_equal_than_mod_minus_one_div_two()?;
+        self.enforce_smaller_than_unchecked(other)
+    }
+
+    /// Helper function to enforce `self < other`. This function assumes `self`
+    /// and `other` are `<= (p-1)/2` and does not generate constraints to
+    /// verify that.
+    fn enforce_smaller_than_unchecked(&self, other: &Self) -> Result<(), SynthesisError> {
+        self.is_smaller_than_unchecked(other)?
+            .enforce_equal(&Boolean::TRUE)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{cmp::Ordering, rand::Rng};
+
+    use crate::{alloc::AllocVar, fields::nonnative::NonNativeFieldVar, R1CSVar};
+    use ark_ff::{PrimeField, UniformRand};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_test_curves::{bls12_381::Fr, mnt4_753::Fr as BaseField};
+
+    fn rand_in_range<R: Rng>(rng: &mut R) -> Fr {
+        let pminusonedivtwo: Fr = Fr::MODULUS_MINUS_ONE_DIV_TWO.into();
+        let mut r;
+        loop {
+            r = Fr::rand(rng);
+            if r <= pminusonedivtwo {
+                break;
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn test_cmp() {
+        let mut rng = ark_std::test_rng();
+        for _ in 0..5 {
+            let cs = ConstraintSystem::<BaseField>::new_ref();
+            let a = rand_in_range(&mut rng);
+            let a_var =
+                NonNativeFieldVar::<Fr, BaseField>::new_witness(cs.clone(), || Ok(a)).unwrap();
+            let b = rand_in_range(&mut rng);
+            let b_var =
+                NonNativeFieldVar::<Fr, BaseField>::new_witness(cs.clone(), || Ok(b)).unwrap();
+
+            for ordering in [Ordering::Less, Ordering::Greater] {
+                for check_equality in [false, true] {
+                    let expected = if check_equality {
+                        a.cmp(&b) == ordering || a == b
+                    } else {
+                        a.cmp(&b) == ordering
+                    };
+                    let is_cmp = a_var.is_cmp(&b_var, ordering, check_equality).unwrap();
+                    assert_eq!(is_cmp.value().unwrap(), expected);
+                }
+            }
+
+            match a.cmp(&b) {
+                Ordering::Less => {
+                    a_var.enforce_cmp(&b_var, Ordering::Less, false).unwrap();
+                    a_var.enforce_cmp(&b_var, Ordering::Less, true).unwrap();
+                },
+                Ordering::Greater => {
+                    a_var.enforce_cmp(&b_var, Ordering::Greater, false).unwrap();
+                    a_var.enforce_cmp(&b_var, Ordering::Greater, true).unwrap();
+                },
+                _ => {},
+            }
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        for _ in 0..5 {
+            let cs = ConstraintSystem::<BaseField>::new_ref();
+            let a = rand_in_range(&mut rng);
+            let a_var =
+                NonNativeFieldVar::<Fr, BaseField>::new_witness(cs.clone(), || Ok(a)).unwrap();
+            let b = rand_in_range(&mut rng);
+            let b_var =
+                NonNativeFieldVar::<Fr, BaseField>::new_witness(cs.clone(), || Ok(b)).unwrap();
+
+            match b.cmp(&a) {
+                Ordering::Less => {
+                    a_var.enforce_cmp(&b_var, Ordering::Less, false).unwrap();
+                },
+                Ordering::Greater => {
+                    a_var.enforce_cmp(&b_var, Ordering::Greater, false).unwrap();
+                },
+                _ => {},
+            }
+            assert!(!cs.is_satisfied().unwrap());
+        }
+
+        let cs = ConstraintSystem::<BaseField>::new_ref();
+        let a = rand_in_range(&mut rng);
+        let a_var = NonNativeFieldVar::<Fr, BaseField>::new_witness(cs.clone(), || Ok(a)).unwrap();
+        a_var.enforce_cmp(&a_var, Ordering::Less, true).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        a_var.enforce_cmp(&a_var, Ordering::Less, false).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // Values above `(p-1)/2` are rejected.
+        let cs = ConstraintSystem::<BaseField>::new_ref();
+        let a_var =
+            NonNativeFieldVar::<Fr, BaseField>::new_witness(cs.clone(), || Ok(-Fr::from(1u8)))
+                .unwrap();
+        a_var
+            .enforce_smaller_or_equal_than_mod_minus_one_div_two()
+            .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
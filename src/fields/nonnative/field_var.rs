@@ -1,4 +1,7 @@
-use super::{params::OptimizationType, AllocatedNonNativeFieldVar, NonNativeFieldMulResultVar};
+use super::{
+    params::{DefaultNonNativeFieldParams, NonNativeFieldParams, OptimizationType},
+    AllocatedNonNativeFieldVarWithParams, NonNativeFieldMulResultVarWithParams,
+};
 use crate::{
     boolean::Boolean,
     fields::{fp::FpVar, FieldVar},
@@ -14,18 +17,27 @@ use ark_std::{
 };
 
 /// A gadget for representing non-native (`TargetField`) field elements over the
-/// constraint field (`BaseField`).
+/// constraint field (`BaseField`), with the limb parameters given by `P`.
 #[derive(Clone, Debug)]
 #[must_use]
-pub enum NonNativeFieldVar<TargetField: PrimeField, BaseField: PrimeField> {
+pub enum NonNativeFieldVarWithParams<
+    TargetField: PrimeField,
+    BaseField: PrimeField,
+    P: NonNativeFieldParams,
+> {
     /// Constant
     Constant(TargetField),
     /// Allocated gadget
-    Var(AllocatedNonNativeFieldVar<TargetField, BaseField>),
+    Var(AllocatedNonNativeFieldVarWithParams<TargetField, BaseField, P>),
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> PartialEq
-    for NonNativeFieldVar<TargetField, BaseField>
+/// A gadget for representing non-native (`TargetField`) field elements over the
+/// constraint field (`BaseField`), with the default limb parameters.
+pub type NonNativeFieldVar<TargetField, BaseField> =
+    NonNativeFieldVarWithParams<TargetField, BaseField, DefaultNonNativeFieldParams>;
+
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams> PartialEq
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     fn eq(&self, other: &Self) -> bool {
         self.value()
@@ -34,21 +46,21 @@ impl<TargetField: PrimeField, BaseField: PrimeField> PartialEq
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> Eq
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams> Eq
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> Hash
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams> Hash
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.value().unwrap_or_default().hash(state);
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> R1CSVar<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams> R1CSVar<BaseField>
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     type Value = TargetField;
 
@@ -67,8 +79,8 @@ impl<TargetField: PrimeField, BaseField: PrimeField> R1CSVar<BaseField>
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> From<Boolean<BaseField>>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    From<Boolean<BaseField>> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     fn from(other: Boolean<BaseField>) -> Self {
         if let Boolean::Constant(b) = other {
@@ -82,28 +94,29 @@ impl<TargetField: PrimeField, BaseField: PrimeField> From<Boolean<BaseField>>
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField>
-    From<AllocatedNonNativeFieldVar<TargetField, BaseField>>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    From<AllocatedNonNativeFieldVarWithParams<TargetField, BaseField, P>>
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
-    fn from(other: AllocatedNonNativeFieldVar<TargetField, BaseField>) -> Self {
+    fn from(other: AllocatedNonNativeFieldVarWithParams<TargetField, BaseField, P>) -> Self {
         Self::Var(other)
     }
 }
 
-impl<'a, TargetField: PrimeField, BaseField: PrimeField> FieldOpsBounds<'a, TargetField, Self>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<'a, TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    FieldOpsBounds<'a, TargetField, Self>
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
 }
 
-impl<'a, TargetField: PrimeField, BaseField: PrimeField>
-    FieldOpsBounds<'a, TargetField, NonNativeFieldVar<TargetField, BaseField>>
-    for &'a NonNativeFieldVar<TargetField, BaseField>
+impl<'a, TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    FieldOpsBounds<'a, TargetField, NonNativeFieldVarWithParams<TargetField, BaseField, P>>
+    for &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> FieldVar<TargetField, BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    FieldVar<TargetField, BaseField> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     fn zero() -> Self {
         Self::Constant(TargetField::zero())
@@ -147,33 +160,33 @@ impl<TargetField: PrimeField, BaseField: PrimeField> FieldVar<TargetField, BaseF
 }
 
 impl_bounded_ops!(
-    NonNativeFieldVar<TargetField, BaseField>,
+    NonNativeFieldVarWithParams<TargetField, BaseField, P>,
     TargetField,
     Add,
     add,
     AddAssign,
     add_assign,
-    |this: &'a NonNativeFieldVar<TargetField, BaseField>, other: &'a NonNativeFieldVar<TargetField, BaseField>| {
-        use NonNativeFieldVar::*;
+    |this: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>, other: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>| {
+        use NonNativeFieldVarWithParams::*;
         match (this, other) {
             (Constant(c1), Constant(c2)) => Constant(*c1 + c2),
             (Constant(c), Var(v)) | (Var(v), Constant(c)) => Var(v.add_constant(c).unwrap()),
             (Var(v1), Var(v2)) => Var(v1.add(v2).unwrap()),
         }
     },
-    |this: &'a NonNativeFieldVar<TargetField, BaseField>, other: TargetField| { this + &NonNativeFieldVar::Constant(other) },
-    (TargetField: PrimeField, BaseField: PrimeField),
+    |this: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>, other: TargetField| { this + &NonNativeFieldVarWithParams::Constant(other) },
+    (TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams),
 );
 
 impl_bounded_ops!(
-    NonNativeFieldVar<TargetField, BaseField>,
+    NonNativeFieldVarWithParams<TargetField, BaseField, P>,
     TargetField,
     Sub,
     sub,
     SubAssign,
     sub_assign,
-    |this: &'a NonNativeFieldVar<TargetField, BaseField>, other: &'a NonNativeFieldVar<TargetField, BaseField>| {
-        use NonNativeFieldVar::*;
+    |this: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>, other: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>| {
+        use NonNativeFieldVarWithParams::*;
         match (this, other) {
             (Constant(c1), Constant(c2)) => Constant(*c1 - c2),
             (Var(v), Constant(c)) => Var(v.sub_constant(c).unwrap()),
@@ -181,42 +194,42 @@ impl_bounded_ops!(
             (Var(v1), Var(v2)) => Var(v1.sub(v2).unwrap()),
         }
     },
-    |this: &'a NonNativeFieldVar<TargetField, BaseField>, other: TargetField| {
-        this - &NonNativeFieldVar::Constant(other)
+    |this: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>, other: TargetField| {
+        this - &NonNativeFieldVarWithParams::Constant(other)
     },
-    (TargetField: PrimeField, BaseField: PrimeField),
+    (TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams),
 );
 
 impl_bounded_ops!(
-    NonNativeFieldVar<TargetField, BaseField>,
+    NonNativeFieldVarWithParams<TargetField, BaseField, P>,
     TargetField,
     Mul,
     mul,
     MulAssign,
     mul_assign,
-    |this: &'a NonNativeFieldVar<TargetField, BaseField>, other: &'a NonNativeFieldVar<TargetField, BaseField>| {
-        use NonNativeFieldVar::*;
+    |this: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>, other: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>| {
+        use NonNativeFieldVarWithParams::*;
         match (this, other) {
             (Constant(c1), Constant(c2)) => Constant(*c1 * c2),
             (Constant(c), Var(v)) | (Var(v), Constant(c)) => Var(v.mul_constant(c).unwrap()),
             (Var(v1), Var(v2)) => Var(v1.mul(v2).unwrap()),
         }
     },
-    |this: &'a NonNativeFieldVar<TargetField, BaseField>, other: TargetField| {
+    |this: &'a NonNativeFieldVarWithParams<TargetField, BaseField, P>, other: TargetField| {
         if other.is_zero() {
-            NonNativeFieldVar::zero()
+            NonNativeFieldVarWithParams::zero()
         } else {
-            this * &NonNativeFieldVar::Constant(other)
+            this * &NonNativeFieldVarWithParams::Constant(other)
         }
     },
-    (TargetField: PrimeField, BaseField: PrimeField),
+    (TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams),
 );
 
 /// *************************************************************************
 /// *************************************************************************
 
-impl<TargetField: PrimeField, BaseField: PrimeField> EqGadget<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams> EqGadget<BaseField>
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     #[tracing::instrument(target = "r1cs")]
     fn is_eq(&self, other: &Self) -> R1CSResult<Boolean<BaseField>> {
@@ -250,7 +263,7 @@ impl<TargetField: PrimeField, BaseField: PrimeField> EqGadget<BaseField>
             },
             (Self::Constant(c), Self::Var(v)) | (Self::Var(v), Self::Constant(c)) => {
                 let cs = v.cs();
-                let c = AllocatedNonNativeFieldVar::new_constant(cs, c)?;
+                let c = AllocatedNonNativeFieldVarWithParams::new_constant(cs, c)?;
                 c.conditional_enforce_equal(v, should_enforce)
             },
             (Self::Var(v1), Self::Var(v2)) => v1.conditional_enforce_equal(v2, should_enforce),
@@ -272,7 +285,7 @@ impl<TargetField: PrimeField, BaseField: PrimeField> EqGadget<BaseField>
             },
             (Self::Constant(c), Self::Var(v)) | (Self::Var(v), Self::Constant(c)) => {
                 let cs = v.cs();
-                let c = AllocatedNonNativeFieldVar::new_constant(cs, c)?;
+                let c = AllocatedNonNativeFieldVarWithParams::new_constant(cs, c)?;
                 c.conditional_enforce_not_equal(v, should_enforce)
             },
             (Self::Var(v1), Self::Var(v2)) => v1.conditional_enforce_not_equal(v2, should_enforce),
@@ -280,8 +293,8 @@ impl<TargetField: PrimeField, BaseField: PrimeField> EqGadget<BaseField>
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> ToBitsGadget<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    ToBitsGadget<BaseField> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     #[tracing::instrument(target = "r1cs")]
     fn to_bits_le(&self) -> R1CSResult<Vec<Boolean<BaseField>>> {
@@ -304,8 +317,8 @@ impl<TargetField: PrimeField, BaseField: PrimeField> ToBitsGadget<BaseField>
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> ToBytesGadget<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    ToBytesGadget<BaseField> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     /// Outputs the unique byte decomposition of `self` in *little-endian*
     /// form.
@@ -331,8 +344,8 @@ impl<TargetField: PrimeField, BaseField: PrimeField> ToBytesGadget<BaseField>
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> CondSelectGadget<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    CondSelectGadget<BaseField> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     #[tracing::instrument(target = "r1cs")]
     fn conditionally_select(
@@ -346,11 +359,13 @@ impl<TargetField: PrimeField, BaseField: PrimeField> CondSelectGadget<BaseField>
             _ => {
                 let cs = cond.cs();
                 let true_value = match true_value {
-                    Self::Constant(f) => AllocatedNonNativeFieldVar::new_constant(cs.clone(), f)?,
+                    Self::Constant(f) => {
+                        AllocatedNonNativeFieldVarWithParams::new_constant(cs.clone(), f)?
+                    },
                     Self::Var(v) => v.clone(),
                 };
                 let false_value = match false_value {
-                    Self::Constant(f) => AllocatedNonNativeFieldVar::new_constant(cs, f)?,
+                    Self::Constant(f) => AllocatedNonNativeFieldVarWithParams::new_constant(cs, f)?,
                     Self::Var(v) => v.clone(),
                 };
                 cond.select(&true_value, &false_value).map(Self::Var)
@@ -361,8 +376,8 @@ impl<TargetField: PrimeField, BaseField: PrimeField> CondSelectGadget<BaseField>
 
 /// Uses two bits to perform a lookup into a table
 /// `b` is little-endian: `b[0]` is LSB.
-impl<TargetField: PrimeField, BaseField: PrimeField> TwoBitLookupGadget<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    TwoBitLookupGadget<BaseField> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     type TableConstant = TargetField;
 
@@ -378,13 +393,14 @@ impl<TargetField: PrimeField, BaseField: PrimeField> TwoBitLookupGadget<BaseFiel
             let index = lsb + (msb << 1);
             Ok(Self::Constant(c[index]))
         } else {
-            AllocatedNonNativeFieldVar::two_bit_lookup(b, c).map(Self::Var)
+            AllocatedNonNativeFieldVarWithParams::two_bit_lookup(b, c).map(Self::Var)
         }
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> ThreeBitCondNegLookupGadget<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    ThreeBitCondNegLookupGadget<BaseField>
+    for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     type TableConstant = TargetField;
 
@@ -413,13 +429,14 @@ impl<TargetField: PrimeField, BaseField: PrimeField> ThreeBitCondNegLookupGadget
             };
             Ok(Self::Constant(y))
         } else {
-            AllocatedNonNativeFieldVar::three_bit_cond_neg_lookup(b, b0b1, c).map(Self::Var)
+            AllocatedNonNativeFieldVarWithParams::three_bit_cond_neg_lookup(b, b0b1, c)
+                .map(Self::Var)
         }
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> AllocVar<TargetField, BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    AllocVar<TargetField, BaseField> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     fn new_variable<T: Borrow<TargetField>>(
         cs: impl Into<Namespace<BaseField>>,
@@ -432,13 +449,13 @@ impl<TargetField: PrimeField, BaseField: PrimeField> AllocVar<TargetField, BaseF
         if cs == ConstraintSystemRef::None || mode == AllocationMode::Constant {
             Ok(Self::Constant(*f()?.borrow()))
         } else {
-            AllocatedNonNativeFieldVar::new_variable(cs, f, mode).map(Self::Var)
+            AllocatedNonNativeFieldVarWithParams::new_variable(cs, f, mode).map(Self::Var)
         }
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> ToConstraintFieldGadget<BaseField>
-    for NonNativeFieldVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    ToConstraintFieldGadget<BaseField> for NonNativeFieldVarWithParams<TargetField, BaseField, P>
 {
     #[tracing::instrument(target = "r1cs")]
     fn to_constraint_field(&self) -> R1CSResult<Vec<FpVar<BaseField>>> {
@@ -447,9 +464,12 @@ impl<TargetField: PrimeField, BaseField: PrimeField> ToConstraintFieldGadget<Bas
         // By default, the constant is converted in the weight-optimized type, because
         // it results in fewer elements.
         match self {
-            Self::Constant(c) => Ok(AllocatedNonNativeFieldVar::get_limbs_representations(
-                c,
-                OptimizationType::Weight,
+            Self::Constant(c) => Ok(AllocatedNonNativeFieldVarWithParams::<
+                TargetField,
+                BaseField,
+                P,
+            >::get_limbs_representations(
+                c, OptimizationType::Weight
             )?
             .into_iter()
             .map(FpVar::constant)
@@ -459,38 +479,43 @@ impl<TargetField: PrimeField, BaseField: PrimeField> ToConstraintFieldGadget<Bas
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField> NonNativeFieldVar<TargetField, BaseField> {
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    NonNativeFieldVarWithParams<TargetField, BaseField, P>
+{
     /// The `mul_without_reduce` for `NonNativeFieldVar`
     #[tracing::instrument(target = "r1cs")]
     pub fn mul_without_reduce(
         &self,
         other: &Self,
-    ) -> R1CSResult<NonNativeFieldMulResultVar<TargetField, BaseField>> {
+    ) -> R1CSResult<NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>> {
         match self {
             Self::Constant(c) => match other {
-                Self::Constant(other_c) => Ok(NonNativeFieldMulResultVar::Constant(*c * other_c)),
+                Self::Constant(other_c) => {
+                    Ok(NonNativeFieldMulResultVarWithParams::Constant(*c * other_c))
+                },
                 Self::Var(other_v) => {
                     let self_v =
-                        AllocatedNonNativeFieldVar::<TargetField, BaseField>::new_constant(
+                        AllocatedNonNativeFieldVarWithParams::<TargetField, BaseField, P>::new_constant(
                             self.cs(),
                             c,
                         )?;
-                    Ok(NonNativeFieldMulResultVar::Var(
+                    Ok(NonNativeFieldMulResultVarWithParams::Var(
                         other_v.mul_without_reduce(&self_v)?,
                     ))
                 },
             },
             Self::Var(v) => {
                 let other_v = match other {
-                    Self::Constant(other_c) => {
-                        AllocatedNonNativeFieldVar::<TargetField, BaseField>::new_constant(
-                            self.cs(),
-                            other_c,
-                        )?
-                    },
+                    Self::Constant(other_c) => AllocatedNonNativeFieldVarWithParams::<
+                        TargetField,
+                        BaseField,
+                        P,
+                    >::new_constant(
+                        self.cs(), other_c
+                    )?,
                     Self::Var(other_v) => other_v.clone(),
                 };
-                Ok(NonNativeFieldMulResultVar::Var(
+                Ok(NonNativeFieldMulResultVarWithParams::Var(
                     v.mul_without_reduce(&other_v)?,
                 ))
             },
@@ -200,3 +200,8 @@ pub use field_var::*;
 
 mod mul_result;
 pub use mul_result::*;
+
+mod cmp;
+
+mod biguint;
+pub use biguint::*;
@@ -1,4 +1,7 @@
-use super::{AllocatedNonNativeFieldMulResultVar, NonNativeFieldVar};
+use super::{
+    params::{DefaultNonNativeFieldParams, NonNativeFieldParams},
+    AllocatedNonNativeFieldMulResultVarWithParams, NonNativeFieldVarWithParams,
+};
 use ark_ff::PrimeField;
 use ark_relations::r1cs::Result as R1CSResult;
 
@@ -8,19 +11,32 @@ use ark_relations::r1cs::Result as R1CSResult;
 ///
 /// That is, instead of calling `mul`, one can call `mul_without_reduce` to
 /// obtain this intermediate representation, which can still be added.
-/// Then, one can call `reduce` to reduce it back to `NonNativeFieldVar`.
+/// Then, one can call `reduce` to reduce it back to `NonNativeFieldVarWithParams`.
 /// This may help cut the number of reduce operations.
-#[derive(Debug)]
+///
+/// It also supports subtraction, negation, and multiplication by constants.
+/// The bit-growth of the limbs is tracked, and the representation is reduced
+/// automatically only when it would otherwise become too large to be reduced.
+#[derive(Clone, Debug)]
 #[must_use]
-pub enum NonNativeFieldMulResultVar<TargetField: PrimeField, BaseField: PrimeField> {
+pub enum NonNativeFieldMulResultVarWithParams<
+    TargetField: PrimeField,
+    BaseField: PrimeField,
+    P: NonNativeFieldParams,
+> {
     /// as a constant
     Constant(TargetField),
     /// as an allocated gadget
-    Var(AllocatedNonNativeFieldMulResultVar<TargetField, BaseField>),
+    Var(AllocatedNonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>),
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField>
-    NonNativeFieldMulResultVar<TargetField, BaseField>
+/// The result of a multiplication of `NonNativeFieldVar`s, with the default
+/// limb parameters.
+pub type NonNativeFieldMulResultVar<TargetField, BaseField> =
+    NonNativeFieldMulResultVarWithParams<TargetField, BaseField, DefaultNonNativeFieldParams>;
+
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>
 {
     /// Create a zero `NonNativeFieldMulResultVar` (used for additions)
     pub fn zero() -> Self {
@@ -34,46 +50,87 @@ impl<TargetField: PrimeField, BaseField: PrimeField>
 
     /// Reduce the `NonNativeFieldMulResultVar` back to NonNativeFieldVar
     #[tracing::instrument(target = "r1cs")]
-    pub fn reduce(&self) -> R1CSResult<NonNativeFieldVar<TargetField, BaseField>> {
+    pub fn reduce(&self) -> R1CSResult<NonNativeFieldVarWithParams<TargetField, BaseField, P>> {
+        match self {
+            Self::Constant(c) => Ok(NonNativeFieldVarWithParams::Constant(*c)),
+            Self::Var(v) => Ok(NonNativeFieldVarWithParams::Var(v.reduce()?)),
+        }
+    }
+
+    /// Negate the `NonNativeFieldMulResultVar` without reducing it
+    #[tracing::instrument(target = "r1cs")]
+    pub fn negate(&self) -> R1CSResult<Self> {
+        match self {
+            Self::Constant(c) => Ok(Self::Constant(-*c)),
+            Self::Var(v) => Ok(Self::Var(v.negate()?)),
+        }
+    }
+
+    /// Multiply the `NonNativeFieldMulResultVar` by a constant, reducing it
+    /// only if necessary
+    #[tracing::instrument(target = "r1cs")]
+    pub fn mul_constant(&self, other: &TargetField) -> R1CSResult<Self> {
         match self {
-            Self::Constant(c) => Ok(NonNativeFieldVar::Constant(*c)),
-            Self::Var(v) => Ok(NonNativeFieldVar::Var(v.reduce()?)),
+            Self::Constant(c) => Ok(Self::Constant(*c * other)),
+            Self::Var(_) if other.is_zero() => Ok(Self::zero()),
+            Self::Var(v) => Ok(Self::Var(v.mul_constant(other)?)),
         }
     }
 }
 
-impl<TargetField: PrimeField, BaseField: PrimeField>
-    From<&NonNativeFieldVar<TargetField, BaseField>>
-    for NonNativeFieldMulResultVar<TargetField, BaseField>
+impl<TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams>
+    From<&NonNativeFieldVarWithParams<TargetField, BaseField, P>>
+    for NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>
 {
-    fn from(src: &NonNativeFieldVar<TargetField, BaseField>) -> Self {
+    fn from(src: &NonNativeFieldVarWithParams<TargetField, BaseField, P>) -> Self {
         match src {
-            NonNativeFieldVar::Constant(c) => NonNativeFieldMulResultVar::Constant(*c),
-            NonNativeFieldVar::Var(v) => {
-                NonNativeFieldMulResultVar::Var(AllocatedNonNativeFieldMulResultVar::<
-                    TargetField,
-                    BaseField,
-                >::from(v))
+            NonNativeFieldVarWithParams::Constant(c) => {
+                NonNativeFieldMulResultVarWithParams::Constant(*c)
             },
+            NonNativeFieldVarWithParams::Var(v) => NonNativeFieldMulResultVarWithParams::Var(
+                AllocatedNonNativeFieldMulResultVarWithParams::<TargetField, BaseField, P>::from(v),
+            ),
         }
     }
 }
 
 impl_bounded_ops!(
-    NonNativeFieldMulResultVar<TargetField, BaseField>,
+    NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>,
     TargetField,
     Add,
     add,
     AddAssign,
     add_assign,
-    |this: &'a NonNativeFieldMulResultVar<TargetField, BaseField>, other: &'a NonNativeFieldMulResultVar<TargetField, BaseField>| {
-        use NonNativeFieldMulResultVar::*;
+    |this: &'a NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>, other: &'a NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>| {
+        use NonNativeFieldMulResultVarWithParams::*;
         match (this, other) {
             (Constant(c1), Constant(c2)) => Constant(*c1 + c2),
             (Constant(c), Var(v)) | (Var(v), Constant(c)) => Var(v.add_constant(c).unwrap()),
             (Var(v1), Var(v2)) => Var(v1.add(v2).unwrap()),
         }
     },
-    |this: &'a NonNativeFieldMulResultVar<TargetField, BaseField>, other: TargetField| { this + &NonNativeFieldMulResultVar::Constant(other) },
-    (TargetField: PrimeField, BaseField: PrimeField),
+    |this: &'a NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>, other: TargetField| { this + &NonNativeFieldMulResultVarWithParams::Constant(other) },
+    (TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams),
+);
+
+impl_bounded_ops!(
+    NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>,
+    TargetField,
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    |this: &'a NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>, other: &'a NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>| {
+        use NonNativeFieldMulResultVarWithParams::*;
+        match (this, other) {
+            (Constant(c1), Constant(c2)) => Constant(*c1 - c2),
+            (Var(v), Constant(c)) => Var(v.add_constant(&-*c).unwrap()),
+            (Constant(c), Var(v)) => Var(v.negate().unwrap().add_constant(c).unwrap()),
+            (Var(v1), Var(v2)) => Var(v1.sub(v2).unwrap()),
+        }
+    },
+    |this: &'a NonNativeFieldMulResultVarWithParams<TargetField, BaseField, P>, other: TargetField| {
+        this - &NonNativeFieldMulResultVarWithParams::Constant(other)
+    },
+    (TargetField: PrimeField, BaseField: PrimeField, P: NonNativeFieldParams),
 );
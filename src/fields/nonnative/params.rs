@@ -1,4 +1,86 @@
 use super::NonNativeFieldConfig;
+use ark_std::fmt::Debug;
+
+/// A choice of limb parameters for `NonNativeFieldVar` and its related
+/// gadgets.
+///
+/// Implement this trait to override the number of limbs and the size of each
+/// limb, e.g. to match the representation used by an external verifier.
+/// The returned parameters must be able to represent every `TargetField`
+/// element (i.e. `num_limbs * bits_per_limb >= target_field_size`) without
+/// any limb lying entirely above the modulus (i.e.
+/// `(num_limbs - 1) * bits_per_limb < target_field_size`, which together
+/// with `bits_per_limb <= target_field_size` keeps every limb within the
+/// `BigInt` of `TargetField`), and must leave enough headroom in `BaseField`
+/// for multiplication, i.e.
+/// `2 * bits_per_limb + log2(num_limbs) < base_field_size - 1`.
+pub trait NonNativeFieldParams: 'static + Clone + Debug + Send + Sync {
+    /// Returns the limb parameters for simulating a field of
+    /// `target_field_size` bits inside a field of `base_field_size` bits.
+    fn params(
+        target_field_size: usize,
+        base_field_size: usize,
+        optimization_type: OptimizationType,
+    ) -> NonNativeFieldConfig;
+}
+
+/// Returns `P::params(target_field_size, base_field_size, optimization_type)`,
+/// after checking that the parameters satisfy the requirements documented on
+/// [`NonNativeFieldParams`].
+///
+/// # Panics
+///
+/// Panics if the parameters cannot represent every `TargetField` element,
+/// use more limbs (or wider limbs) than a `TargetField` element has bits, or
+/// leave too little headroom in `BaseField` for multiplication.
+pub(crate) fn checked_params<P: NonNativeFieldParams>(
+    target_field_size: usize,
+    base_field_size: usize,
+    optimization_type: OptimizationType,
+) -> NonNativeFieldConfig {
+    let params = P::params(target_field_size, base_field_size, optimization_type);
+    assert!(
+        params.num_limbs * params.bits_per_limb >= target_field_size,
+        "{} limbs of {} bits cannot represent a {}-bit field",
+        params.num_limbs,
+        params.bits_per_limb,
+        target_field_size,
+    );
+    assert!(
+        params.num_limbs > 0
+            && (params.num_limbs - 1) * params.bits_per_limb < target_field_size
+            && params.bits_per_limb <= target_field_size,
+        "{} limbs of {} bits are too many for a {}-bit field",
+        params.num_limbs,
+        params.bits_per_limb,
+        target_field_size,
+    );
+    let product_bits = 2 * params.bits_per_limb + ark_std::log2(params.num_limbs) as usize;
+    assert!(
+        product_bits < base_field_size - 1,
+        "limbs of {} bits leave too little headroom in a {}-bit field",
+        params.bits_per_limb,
+        base_field_size,
+    );
+    params
+}
+
+/// The default limb parameters, which are chosen by [`get_params`] to
+/// minimize either the number of constraints or the weight, according to the
+/// optimization goal of the constraint system.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DefaultNonNativeFieldParams;
+
+impl NonNativeFieldParams for DefaultNonNativeFieldParams {
+    #[inline]
+    fn params(
+        target_field_size: usize,
+        base_field_size: usize,
+        optimization_type: OptimizationType,
+    ) -> NonNativeFieldConfig {
+        get_params(target_field_size, base_field_size, optimization_type)
+    }
+}
 
 /// Obtain the parameters from a `ConstraintSystem`'s cache or generate a new
 /// one
@@ -44,11 +126,11 @@ pub const fn find_parameters(
     let mut limb_size = 1;
 
     while limb_size <= max_limb_size {
-        let num_of_limbs = (target_field_prime_bit_length + limb_size - 1) / limb_size;
+        let num_of_limbs = target_field_prime_bit_length.div_ceil(limb_size);
 
         let group_size =
-            (base_field_prime_length - 1 - surfeit - 1 - 1 - limb_size + limb_size - 1) / limb_size;
-        let num_of_groups = (2 * num_of_limbs - 1 + group_size - 1) / group_size;
+            (base_field_prime_length - 1 - surfeit - 1 - 1 - limb_size).div_ceil(limb_size);
+        let num_of_groups = (2 * num_of_limbs - 1).div_ceil(group_size);
 
         let mut this_cost = 0;
 
@@ -1,5 +1,5 @@
 use ark_ff::{
-    fields::{Field, QuadExtConfig, QuadExtField},
+    fields::{Field, PrimeField, QuadExtConfig, QuadExtField},
     Zero,
 };
 use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
@@ -16,8 +16,11 @@ use crate::{
 #[derive(Derivative)]
 #[derivative(Debug(bound = "BF: core::fmt::Debug"), Clone(bound = "BF: Clone"))]
 #[must_use]
-pub struct QuadExtVar<BF: FieldVar<P::BaseField, P::BasePrimeField>, P: QuadExtVarConfig<BF>>
-where
+pub struct QuadExtVar<
+    BF: FieldVar<P::BaseField, ConstraintF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField = <P as QuadExtConfig>::BasePrimeField,
+> where
     for<'a> &'a BF: FieldOpsBounds<'a, P::BaseField, BF>,
 {
     /// The zero-th coefficient of this field element.
@@ -25,24 +28,37 @@ where
     /// The first coefficient of this field element.
     pub c1: BF,
     #[derivative(Debug = "ignore")]
-    _params: PhantomData<P>,
+    _params: PhantomData<(P, ConstraintF)>,
 }
 
 /// This trait describes parameters that are used to implement arithmetic for
 /// `QuadExtVar`.
-pub trait QuadExtVarConfig<BF: FieldVar<Self::BaseField, Self::BasePrimeField>>:
-    QuadExtConfig
-where
+pub trait QuadExtVarConfig<
+    BF: FieldVar<Self::BaseField, ConstraintF>,
+    ConstraintF: PrimeField = <Self as QuadExtConfig>::BasePrimeField,
+>: QuadExtConfig where
     for<'a> &'a BF: FieldOpsBounds<'a, Self::BaseField, BF>,
 {
     /// Multiply the base field of the `QuadExtVar` by the appropriate Frobenius
     /// coefficient. This is equivalent to
     /// `Self::mul_base_field_by_frob_coeff(power)`.
     fn mul_base_field_var_by_frob_coeff(fe: &mut BF, power: usize);
+
+    /// Squares `fe`, which is *known* to be in the cyclotomic subgroup.
+    /// Extensions with a cheaper squaring formula for this subgroup should
+    /// override the default, which uses the generic squaring.
+    fn cyclotomic_square(
+        fe: &QuadExtVar<BF, Self, ConstraintF>,
+    ) -> Result<QuadExtVar<BF, Self, ConstraintF>, SynthesisError> {
+        fe.square()
+    }
 }
 
-impl<BF: FieldVar<P::BaseField, P::BasePrimeField>, P: QuadExtVarConfig<BF>> QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> QuadExtVar<BF, P, ConstraintF>
 where
+    BF: FieldVar<P::BaseField, ConstraintF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a BF: FieldOpsBounds<'a, P::BaseField, BF>,
 {
     /// Constructs a `QuadExtVar` from the underlying coefficients.
@@ -88,7 +104,7 @@ where
     #[tracing::instrument(target = "r1cs", skip(exponent))]
     pub fn cyclotomic_exp(&self, exponent: impl AsRef<[u64]>) -> Result<Self, SynthesisError>
     where
-        Self: FieldVar<QuadExtField<P>, P::BasePrimeField>,
+        Self: FieldVar<QuadExtField<P>, ConstraintF>,
     {
         let mut res = Self::one();
         let self_inverse = self.unitary_inverse()?;
@@ -98,7 +114,7 @@ where
 
         for &value in naf.iter().rev() {
             if found_nonzero {
-                res.square_in_place()?;
+                res = P::cyclotomic_square(&res)?;
             }
 
             if value != 0 {
@@ -114,17 +130,75 @@ where
 
         Ok(res)
     }
+
+    /// Computes `self^k`, where `bits` is the little-endian bit
+    /// representation of `k`. Unlike [`Self::cyclotomic_exp`], `k` may be a
+    /// witness.
+    ///
+    /// `k` is recoded into signed digits in `{±1, ±3}`, one for every two
+    /// bits, so that each step costs two cyclotomic squarings and a single
+    /// multiplication, and negative digits only cost a unitary inverse.
+    ///
+    /// This is only to be used when the element is *known* to be in the
+    /// cyclotomic subgroup.
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    pub fn cyclotomic_pow_le(&self, bits: &[Boolean<ConstraintF>]) -> Result<Self, SynthesisError>
+    where
+        Self: FieldVar<QuadExtField<P>, ConstraintF>,
+    {
+        if bits.is_constant() {
+            let mut exponent = vec![0u64; bits.len().div_ceil(64)];
+            for (i, bit) in bits.iter().enumerate() {
+                if bit.value()? {
+                    exponent[i / 64] |= 1 << (i % 64);
+                }
+            }
+            return self.cyclotomic_exp(exponent);
+        }
+
+        // Let `k' = k | 1`, with bits `c_i`. Then `k'` equals
+        // `2^(n - 1) + Σ_{i < n - 1} 2^i * (2 * c_{i + 1} - 1)`, whose digits are
+        // in `{-1, 1}`. Pairs of consecutive digits are combined into a single
+        // digit in `{±1, ±3}`, whose sign is that of the upper digit and whose
+        // absolute value is 3 iff both digits agree. We pad `bits` to even
+        // length, which leaves `k'` unchanged.
+        let mut bits = bits.to_vec();
+        if bits.len() % 2 == 1 {
+            bits.push(Boolean::FALSE);
+        }
+        let self_cubed = P::cyclotomic_square(self)? * self;
+        let lookup = |lo: &Boolean<ConstraintF>,
+                      hi: &Boolean<ConstraintF>|
+         -> Result<Self, SynthesisError> {
+            let is_three = lo.xor(hi)?.not();
+            let digit = is_three.select(&self_cubed, self)?;
+            hi.select(&digit, &digit.unitary_inverse()?)
+        };
+
+        // The top digit is always positive.
+        let n = bits.len();
+        let mut res = bits[n - 1].select(&self_cubed, self)?;
+        for i in (0..n / 2 - 1).rev() {
+            res = P::cyclotomic_square(&P::cyclotomic_square(&res)?)?;
+            res *= lookup(&bits[2 * i + 1], &bits[2 * i + 2])?;
+        }
+
+        // Finally, divide by `self` if `k` was even.
+        let corrected = res.clone() * &self.unitary_inverse()?;
+        bits[0].select(&res, &corrected)
+    }
 }
 
-impl<BF, P> R1CSVar<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> R1CSVar<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'a> &'a BF: FieldOpsBounds<'a, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     type Value = QuadExtField<P>;
 
-    fn cs(&self) -> ConstraintSystemRef<P::BasePrimeField> {
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF> {
         [&self.c0, &self.c1].cs()
     }
 
@@ -137,39 +211,45 @@ where
     }
 }
 
-impl<BF, P> From<Boolean<P::BasePrimeField>> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> From<Boolean<ConstraintF>> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'a> &'a BF: FieldOpsBounds<'a, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
-    fn from(other: Boolean<P::BasePrimeField>) -> Self {
+    fn from(other: Boolean<ConstraintF>) -> Self {
         let c0 = BF::from(other);
         let c1 = BF::zero();
         Self::new(c0, c1)
     }
 }
 
-impl<'a, BF, P> FieldOpsBounds<'a, QuadExtField<P>, QuadExtVar<BF, P>> for QuadExtVar<BF, P>
+impl<'a, BF, P, ConstraintF> FieldOpsBounds<'a, QuadExtField<P>, QuadExtVar<BF, P, ConstraintF>>
+    for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
 }
-impl<'a, BF, P> FieldOpsBounds<'a, QuadExtField<P>, QuadExtVar<BF, P>> for &'a QuadExtVar<BF, P>
+impl<'a, BF, P, ConstraintF> FieldOpsBounds<'a, QuadExtField<P>, QuadExtVar<BF, P, ConstraintF>>
+    for &'a QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
 }
 
-impl<BF, P> FieldVar<QuadExtField<P>, P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> FieldVar<QuadExtField<P>, ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'a> &'a BF: FieldOpsBounds<'a, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     fn constant(other: QuadExtField<P>) -> Self {
         let c0 = BF::constant(other.c0);
@@ -294,49 +374,49 @@ where
 }
 
 impl_bounded_ops!(
-    QuadExtVar<BF, P>,
+    QuadExtVar<BF, P, ConstraintF>,
     QuadExtField<P>,
     Add,
     add,
     AddAssign,
     add_assign,
-    |this: &'a QuadExtVar<BF, P>, other: &'a QuadExtVar<BF, P>| {
+    |this: &'a QuadExtVar<BF, P, ConstraintF>, other: &'a QuadExtVar<BF, P, ConstraintF>| {
         let c0 = &this.c0 + &other.c0;
         let c1 = &this.c1 + &other.c1;
         QuadExtVar::new(c0, c1)
     },
-    |this: &'a QuadExtVar<BF, P>, other: QuadExtField<P>| {
+    |this: &'a QuadExtVar<BF, P, ConstraintF>, other: QuadExtField<P>| {
         this + QuadExtVar::constant(other)
     },
-    (BF: FieldVar<P::BaseField, P::BasePrimeField>, P: QuadExtVarConfig<BF>),
+    (BF: FieldVar<P::BaseField, ConstraintF>, P: QuadExtVarConfig<BF, ConstraintF>, ConstraintF: PrimeField),
     for <'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>
 );
 impl_bounded_ops!(
-    QuadExtVar<BF, P>,
+    QuadExtVar<BF, P, ConstraintF>,
     QuadExtField<P>,
     Sub,
     sub,
     SubAssign,
     sub_assign,
-    |this: &'a QuadExtVar<BF, P>, other: &'a QuadExtVar<BF, P>| {
+    |this: &'a QuadExtVar<BF, P, ConstraintF>, other: &'a QuadExtVar<BF, P, ConstraintF>| {
         let c0 = &this.c0 - &other.c0;
         let c1 = &this.c1 - &other.c1;
         QuadExtVar::new(c0, c1)
     },
-    |this: &'a QuadExtVar<BF, P>, other: QuadExtField<P>| {
+    |this: &'a QuadExtVar<BF, P, ConstraintF>, other: QuadExtField<P>| {
         this - QuadExtVar::constant(other)
     },
-    (BF: FieldVar<P::BaseField, P::BasePrimeField>, P: QuadExtVarConfig<BF>),
+    (BF: FieldVar<P::BaseField, ConstraintF>, P: QuadExtVarConfig<BF, ConstraintF>, ConstraintF: PrimeField),
     for <'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>
 );
 impl_bounded_ops!(
-    QuadExtVar<BF, P>,
+    QuadExtVar<BF, P, ConstraintF>,
     QuadExtField<P>,
     Mul,
     mul,
     MulAssign,
     mul_assign,
-    |this: &'a QuadExtVar<BF, P>, other: &'a QuadExtVar<BF, P>| {
+    |this: &'a QuadExtVar<BF, P, ConstraintF>, other: &'a QuadExtVar<BF, P, ConstraintF>| {
         // Karatsuba multiplication for Fp2:
         //     v0 = A.c0 * B.c0
         //     v1 = A.c1 * B.c1
@@ -357,24 +437,25 @@ impl_bounded_ops!(
         result.c1 *= &other.c0 + &other.c1;
         result.c1 -= &v0;
         result.c1 -= &v1;
-        result.c0 = v0 + &QuadExtVar::<BF, P>::mul_base_field_by_nonresidue(&v1).unwrap();
+        result.c0 = v0 + &QuadExtVar::<BF, P, ConstraintF>::mul_base_field_by_nonresidue(&v1).unwrap();
         result
     },
-    |this: &'a QuadExtVar<BF, P>, other: QuadExtField<P>| {
+    |this: &'a QuadExtVar<BF, P, ConstraintF>, other: QuadExtField<P>| {
         this * QuadExtVar::constant(other)
     },
-    (BF: FieldVar<P::BaseField, P::BasePrimeField>, P: QuadExtVarConfig<BF>),
+    (BF: FieldVar<P::BaseField, ConstraintF>, P: QuadExtVarConfig<BF, ConstraintF>, ConstraintF: PrimeField),
     for <'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>
 );
 
-impl<BF, P> EqGadget<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> EqGadget<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn is_eq(&self, other: &Self) -> Result<Boolean<P::BasePrimeField>, SynthesisError> {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<ConstraintF>, SynthesisError> {
         let b0 = self.c0.is_eq(&other.c0)?;
         let b1 = self.c1.is_eq(&other.c1)?;
         b0.and(&b1)
@@ -385,7 +466,7 @@ where
     fn conditional_enforce_equal(
         &self,
         other: &Self,
-        condition: &Boolean<P::BasePrimeField>,
+        condition: &Boolean<ConstraintF>,
     ) -> Result<(), SynthesisError> {
         self.c0.conditional_enforce_equal(&other.c0, condition)?;
         self.c1.conditional_enforce_equal(&other.c1, condition)?;
@@ -397,7 +478,7 @@ where
     fn conditional_enforce_not_equal(
         &self,
         other: &Self,
-        condition: &Boolean<P::BasePrimeField>,
+        condition: &Boolean<ConstraintF>,
     ) -> Result<(), SynthesisError> {
         let is_equal = self.is_eq(other)?;
         is_equal
@@ -406,14 +487,15 @@ where
     }
 }
 
-impl<BF, P> ToBitsGadget<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> ToBitsGadget<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn to_bits_le(&self) -> Result<Vec<Boolean<P::BasePrimeField>>, SynthesisError> {
+    fn to_bits_le(&self) -> Result<Vec<Boolean<ConstraintF>>, SynthesisError> {
         let mut c0 = self.c0.to_bits_le()?;
         let mut c1 = self.c1.to_bits_le()?;
         c0.append(&mut c1);
@@ -421,7 +503,7 @@ where
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bits_le(&self) -> Result<Vec<Boolean<P::BasePrimeField>>, SynthesisError> {
+    fn to_non_unique_bits_le(&self) -> Result<Vec<Boolean<ConstraintF>>, SynthesisError> {
         let mut c0 = self.c0.to_non_unique_bits_le()?;
         let mut c1 = self.c1.to_non_unique_bits_le()?;
         c0.append(&mut c1);
@@ -429,14 +511,15 @@ where
     }
 }
 
-impl<BF, P> ToBytesGadget<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> ToBytesGadget<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(&self) -> Result<Vec<UInt8<P::BasePrimeField>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut c0 = self.c0.to_bytes()?;
         let mut c1 = self.c1.to_bytes()?;
         c0.append(&mut c1);
@@ -444,7 +527,7 @@ where
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<P::BasePrimeField>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut c0 = self.c0.to_non_unique_bytes()?;
         let mut c1 = self.c1.to_non_unique_bytes()?;
         c0.append(&mut c1);
@@ -452,15 +535,16 @@ where
     }
 }
 
-impl<BF, P> ToConstraintFieldGadget<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> ToConstraintFieldGadget<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'a> &'a BF: FieldOpsBounds<'a, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
-    BF: ToConstraintFieldGadget<P::BasePrimeField>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
+    BF: ToConstraintFieldGadget<ConstraintF>,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn to_constraint_field(&self) -> Result<Vec<FpVar<P::BasePrimeField>>, SynthesisError> {
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<ConstraintF>>, SynthesisError> {
         let mut res = Vec::new();
 
         res.extend_from_slice(&self.c0.to_constraint_field()?);
@@ -470,15 +554,16 @@ where
     }
 }
 
-impl<BF, P> CondSelectGadget<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> CondSelectGadget<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     #[inline]
     fn conditionally_select(
-        cond: &Boolean<P::BasePrimeField>,
+        cond: &Boolean<ConstraintF>,
         true_value: &Self,
         false_value: &Self,
     ) -> Result<Self, SynthesisError> {
@@ -488,18 +573,19 @@ where
     }
 }
 
-impl<BF, P> TwoBitLookupGadget<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> TwoBitLookupGadget<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>
-        + TwoBitLookupGadget<P::BasePrimeField, TableConstant = P::BaseField>,
+    BF: FieldVar<P::BaseField, ConstraintF>
+        + TwoBitLookupGadget<ConstraintF, TableConstant = P::BaseField>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     type TableConstant = QuadExtField<P>;
 
     #[tracing::instrument(target = "r1cs")]
     fn two_bit_lookup(
-        b: &[Boolean<P::BasePrimeField>],
+        b: &[Boolean<ConstraintF>],
         c: &[Self::TableConstant],
     ) -> Result<Self, SynthesisError> {
         let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
@@ -510,19 +596,20 @@ where
     }
 }
 
-impl<BF, P> ThreeBitCondNegLookupGadget<P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> ThreeBitCondNegLookupGadget<ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>
-        + ThreeBitCondNegLookupGadget<P::BasePrimeField, TableConstant = P::BaseField>,
+    BF: FieldVar<P::BaseField, ConstraintF>
+        + ThreeBitCondNegLookupGadget<ConstraintF, TableConstant = P::BaseField>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     type TableConstant = QuadExtField<P>;
 
     #[tracing::instrument(target = "r1cs")]
     fn three_bit_cond_neg_lookup(
-        b: &[Boolean<P::BasePrimeField>],
-        b0b1: &Boolean<P::BasePrimeField>,
+        b: &[Boolean<ConstraintF>],
+        b0b1: &Boolean<ConstraintF>,
         c: &[Self::TableConstant],
     ) -> Result<Self, SynthesisError> {
         let c0s = c.iter().map(|f| f.c0).collect::<Vec<_>>();
@@ -533,14 +620,15 @@ where
     }
 }
 
-impl<BF, P> AllocVar<QuadExtField<P>, P::BasePrimeField> for QuadExtVar<BF, P>
+impl<BF, P, ConstraintF> AllocVar<QuadExtField<P>, ConstraintF> for QuadExtVar<BF, P, ConstraintF>
 where
-    BF: FieldVar<P::BaseField, P::BasePrimeField>,
+    BF: FieldVar<P::BaseField, ConstraintF>,
     for<'b> &'b BF: FieldOpsBounds<'b, P::BaseField, BF>,
-    P: QuadExtVarConfig<BF>,
+    P: QuadExtVarConfig<BF, ConstraintF>,
+    ConstraintF: PrimeField,
 {
     fn new_variable<T: Borrow<QuadExtField<P>>>(
-        cs: impl Into<Namespace<P::BasePrimeField>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -0,0 +1,329 @@
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ff::Field;
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
+use core::{borrow::Borrow, marker::PhantomData};
+
+use crate::{groups::power_of_two_multiples, pairing::PairingVar, prelude::*, Vec};
+
+/// Represents a Groth16 proof.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: PairingVar<E, ConstraintF>"),
+    Debug(bound = "P: PairingVar<E, ConstraintF>")
+)]
+pub struct ProofVar<
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    ConstraintF: Field = <<E as Pairing>::G1 as CurveGroup>::BaseField,
+> {
+    /// The `A` element in `G1`.
+    pub a: P::G1Var,
+    /// The `B` element in `G2`.
+    pub b: P::G2Var,
+    /// The `C` element in `G1`.
+    pub c: P::G1Var,
+}
+
+/// Represents a Groth16 verifying key.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: PairingVar<E, ConstraintF>"),
+    Debug(bound = "P: PairingVar<E, ConstraintF>")
+)]
+pub struct VerifyingKeyVar<
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    ConstraintF: Field = <<E as Pairing>::G1 as CurveGroup>::BaseField,
+> {
+    /// The `alpha * G`, where `G` is the generator of `G1`.
+    pub alpha_g1: P::G1Var,
+    /// The `beta * H`, where `H` is the generator of `G2`.
+    pub beta_g2: P::G2Var,
+    /// The `gamma * H`, where `H` is the generator of `G2`.
+    pub gamma_g2: P::G2Var,
+    /// The `delta * H`, where `H` is the generator of `G2`.
+    pub delta_g2: P::G2Var,
+    /// The `gamma^{-1} * (beta * a_i + alpha * b_i + c_i) * G`, where `G` is
+    /// the generator of `G1`.
+    pub gamma_abc_g1: Vec<P::G1Var>,
+}
+
+/// Represents a Groth16 verifying key with the precomputation that is shared
+/// by all verifications.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: PairingVar<E, ConstraintF>"),
+    Debug(bound = "P: PairingVar<E, ConstraintF>")
+)]
+pub struct PreparedVerifyingKeyVar<
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    ConstraintF: Field = <<E as Pairing>::G1 as CurveGroup>::BaseField,
+> {
+    /// The pairing of `alpha_g1` and `beta_g2`.
+    pub alpha_g1_beta_g2: P::GTVar,
+    /// The preparation of `-gamma_g2`.
+    pub gamma_g2_neg_pc: P::G2PreparedVar,
+    /// The preparation of `-delta_g2`.
+    pub delta_g2_neg_pc: P::G2PreparedVar,
+    /// The `gamma_abc_g1` of the verifying key.
+    pub gamma_abc_g1: Vec<P::G1Var>,
+}
+
+/// Verifies Groth16 proofs over the pairing `E`, using the pairing gadget `P`.
+pub struct Groth16VerifierGadget<
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    ConstraintF: Field = <<E as Pairing>::G1 as CurveGroup>::BaseField,
+>(PhantomData<(E, P, ConstraintF)>);
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: Field>
+    Groth16VerifierGadget<E, P, ConstraintF>
+{
+    /// Performs the precomputation on `vk` that is shared by all
+    /// verifications.
+    #[tracing::instrument(target = "r1cs", skip(vk))]
+    pub fn prepare_verifying_key(
+        vk: &VerifyingKeyVar<E, P, ConstraintF>,
+    ) -> Result<PreparedVerifyingKeyVar<E, P, ConstraintF>, SynthesisError> {
+        let alpha_g1_pc = P::prepare_g1(&vk.alpha_g1)?;
+        let beta_g2_pc = P::prepare_g2(&vk.beta_g2)?;
+        let alpha_g1_beta_g2 = P::pairing(alpha_g1_pc, beta_g2_pc)?;
+        let gamma_g2_neg_pc = P::prepare_g2(&vk.gamma_g2.negate()?)?;
+        let delta_g2_neg_pc = P::prepare_g2(&vk.delta_g2.negate()?)?;
+        Ok(PreparedVerifyingKeyVar {
+            alpha_g1_beta_g2,
+            gamma_g2_neg_pc,
+            delta_g2_neg_pc,
+            gamma_abc_g1: vk.gamma_abc_g1.clone(),
+        })
+    }
+
+    /// Outputs a `Boolean` that is true if and only if `proof` is valid for
+    /// `vk` and `public_inputs`, where each public input is given as a
+    /// little-endian `Boolean` representation.
+    ///
+    /// This method returns `SynthesisError::MalformedVerifyingKey` if the
+    /// number of public inputs does not match `vk`.
+    #[tracing::instrument(target = "r1cs", skip(vk, public_inputs, proof))]
+    pub fn verify(
+        vk: &VerifyingKeyVar<E, P, ConstraintF>,
+        public_inputs: &[Vec<Boolean<ConstraintF>>],
+        proof: &ProofVar<E, P, ConstraintF>,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        let pvk = Self::prepare_verifying_key(vk)?;
+        Self::verify_with_prepared_vk(&pvk, public_inputs, proof)
+    }
+
+    /// Outputs a `Boolean` that is true if and only if `proof` is valid for
+    /// `pvk` and `public_inputs`, where each public input is given as a
+    /// little-endian `Boolean` representation.
+    ///
+    /// This method returns `SynthesisError::MalformedVerifyingKey` if the
+    /// number of public inputs does not match `pvk`.
+    #[tracing::instrument(target = "r1cs", skip(pvk, public_inputs, proof))]
+    pub fn verify_with_prepared_vk(
+        pvk: &PreparedVerifyingKeyVar<E, P, ConstraintF>,
+        public_inputs: &[Vec<Boolean<ConstraintF>>],
+        proof: &ProofVar<E, P, ConstraintF>,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        if public_inputs.len() + 1 != pvk.gamma_abc_g1.len() {
+            return Err(SynthesisError::MalformedVerifyingKey);
+        }
+        let (g_ic, bases) = pvk.gamma_abc_g1.split_first().unwrap();
+        let g_ic = Self::input_msm(bases, public_inputs)? + g_ic;
+
+        let a = P::prepare_g1(&proof.a)?;
+        let b = P::prepare_g2(&proof.b)?;
+        let c = P::prepare_g1(&proof.c)?;
+        let g_ic = P::prepare_g1(&g_ic)?;
+        let test = P::product_of_pairings(
+            &[a, g_ic, c],
+            &[b, pvk.gamma_g2_neg_pc.clone(), pvk.delta_g2_neg_pc.clone()],
+        )?;
+        test.is_eq(&pvk.alpha_g1_beta_g2)
+    }
+
+    /// Computes `Σᵢ(public_inputsᵢ * basesᵢ)`.
+    fn input_msm(
+        bases: &[P::G1Var],
+        public_inputs: &[Vec<Boolean<ConstraintF>>],
+    ) -> Result<P::G1Var, SynthesisError> {
+        if !bases.is_constant() {
+            return P::G1Var::variable_base_msm(bases, public_inputs);
+        }
+        // If the verifying key is a constant, the power-of-two multiples of its
+        // bases can be computed outside the circuit.
+        let multiples = bases
+            .iter()
+            .zip(public_inputs)
+            // The value should exist because `base` is a constant.
+            .map(|(base, bits)| Ok(power_of_two_multiples(base.value()?, bits.len())))
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        P::G1Var::precomputed_base_multiscalar_mul_le(&multiples, public_inputs.iter())
+    }
+}
+
+/// A native Groth16 proof, given as its elements `(a, b, c)`.
+pub type Proof<E> = (
+    <E as Pairing>::G1Affine,
+    <E as Pairing>::G2Affine,
+    <E as Pairing>::G1Affine,
+);
+
+/// A native Groth16 verifying key, given as its elements
+/// `(alpha_g1, beta_g2, gamma_g2, delta_g2, gamma_abc_g1)`.
+pub type VerifyingKey<E> = (
+    <E as Pairing>::G1Affine,
+    <E as Pairing>::G2Affine,
+    <E as Pairing>::G2Affine,
+    <E as Pairing>::G2Affine,
+    Vec<<E as Pairing>::G1Affine>,
+);
+
+/// A native Groth16 prepared verifying key, given as its elements
+/// `(alpha_g1_beta_g2, gamma_g2_neg_pc, delta_g2_neg_pc, gamma_abc_g1)`.
+pub type PreparedVerifyingKey<E> = (
+    <E as Pairing>::TargetField,
+    <E as Pairing>::G2Prepared,
+    <E as Pairing>::G2Prepared,
+    Vec<<E as Pairing>::G1Affine>,
+);
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: Field> AllocVar<Proof<E>, ConstraintF>
+    for ProofVar<E, P, ConstraintF>
+{
+    #[tracing::instrument(target = "r1cs", skip(cs, f))]
+    fn new_variable<T: Borrow<Proof<E>>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        let proof = f().map(|p| *p.borrow());
+        let proof = proof.as_ref().map_err(|e| *e);
+        let a = <P::G1Var as AllocVar<E::G1Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "a"),
+            || proof.map(|p| p.0),
+            mode,
+        )?;
+        let b = <P::G2Var as AllocVar<E::G2Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "b"),
+            || proof.map(|p| p.1),
+            mode,
+        )?;
+        let c = <P::G1Var as AllocVar<E::G1Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "c"),
+            || proof.map(|p| p.2),
+            mode,
+        )?;
+        Ok(Self { a, b, c })
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: Field>
+    AllocVar<VerifyingKey<E>, ConstraintF> for VerifyingKeyVar<E, P, ConstraintF>
+{
+    /// Allocates `Self`. As the number of public inputs determines the shape of
+    /// the circuit, `f` must output a value even in setup mode.
+    #[tracing::instrument(target = "r1cs", skip(cs, f))]
+    fn new_variable<T: Borrow<VerifyingKey<E>>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        let (alpha_g1, beta_g2, gamma_g2, delta_g2, gamma_abc_g1) = f()?.borrow().clone();
+        let alpha_g1 = <P::G1Var as AllocVar<E::G1Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "alpha_g1"),
+            || Ok(alpha_g1),
+            mode,
+        )?;
+        let beta_g2 = <P::G2Var as AllocVar<E::G2Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "beta_g2"),
+            || Ok(beta_g2),
+            mode,
+        )?;
+        let gamma_g2 = <P::G2Var as AllocVar<E::G2Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "gamma_g2"),
+            || Ok(gamma_g2),
+            mode,
+        )?;
+        let delta_g2 = <P::G2Var as AllocVar<E::G2Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "delta_g2"),
+            || Ok(delta_g2),
+            mode,
+        )?;
+        let gamma_abc_g1 = new_g1_vars::<E, P, _>(cs, &gamma_abc_g1, mode)?;
+        Ok(Self {
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            gamma_abc_g1,
+        })
+    }
+}
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: Field>
+    AllocVar<PreparedVerifyingKey<E>, ConstraintF> for PreparedVerifyingKeyVar<E, P, ConstraintF>
+{
+    /// Allocates `Self`. As the number of public inputs determines the shape of
+    /// the circuit, `f` must output a value even in setup mode.
+    #[tracing::instrument(target = "r1cs", skip(cs, f))]
+    fn new_variable<T: Borrow<PreparedVerifyingKey<E>>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        let (alpha_g1_beta_g2, gamma_g2_neg_pc, delta_g2_neg_pc, gamma_abc_g1) =
+            f()?.borrow().clone();
+        let alpha_g1_beta_g2 = P::GTVar::new_variable(
+            ark_relations::ns!(cs, "alpha_g1_beta_g2"),
+            || Ok(alpha_g1_beta_g2),
+            mode,
+        )?;
+        let gamma_g2_neg_pc = P::G2PreparedVar::new_variable(
+            ark_relations::ns!(cs, "gamma_g2_neg_pc"),
+            || Ok(gamma_g2_neg_pc),
+            mode,
+        )?;
+        let delta_g2_neg_pc = P::G2PreparedVar::new_variable(
+            ark_relations::ns!(cs, "delta_g2_neg_pc"),
+            || Ok(delta_g2_neg_pc),
+            mode,
+        )?;
+        let gamma_abc_g1 = new_g1_vars::<E, P, _>(cs, &gamma_abc_g1, mode)?;
+        Ok(Self {
+            alpha_g1_beta_g2,
+            gamma_g2_neg_pc,
+            delta_g2_neg_pc,
+            gamma_abc_g1,
+        })
+    }
+}
+
+/// Allocates a variable for each element of `points`.
+fn new_g1_vars<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: Field>(
+    cs: ConstraintSystemRef<ConstraintF>,
+    points: &[E::G1Affine],
+    mode: AllocationMode,
+) -> Result<Vec<P::G1Var>, SynthesisError> {
+    points
+        .iter()
+        .map(|p| {
+            <P::G1Var as AllocVar<E::G1Affine, _>>::new_variable(
+                ark_relations::ns!(cs, "gamma_abc_g1"),
+                || Ok(*p),
+                mode,
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,164 @@
+use ark_ec::{
+    short_weierstrass::{Affine as SWAffine, SWCurveConfig},
+    AffineRepr,
+};
+use ark_ff::{Field, PrimeField};
+use ark_relations::r1cs::SynthesisError;
+use ark_serialize::CanonicalSerialize;
+
+use crate::{prelude::*, Vec};
+
+/// Returns the number of bytes that `ark-serialize` uses for each component of
+/// an element of `F` over its base prime field, when serializing the element
+/// with `num_flags` flag bits.
+fn component_sizes<F: Field>(num_flags: usize) -> Vec<usize> {
+    let num_components = F::extension_degree() as usize;
+    let modulus_bits = F::BasePrimeField::MODULUS_BIT_SIZE as usize;
+    let mut sizes = vec![modulus_bits.div_ceil(8); num_components];
+    sizes[num_components - 1] = (modulus_bits + num_flags).div_ceil(8);
+    sizes
+}
+
+/// Serializes `f` in the format of `ark-serialize`'s `serialize_with_flags`,
+/// where `flags[i]` is stored in bit `8 - flags.len() + i` of the last byte.
+pub(crate) fn field_to_bytes_with_flags<F, ConstraintF, FV>(
+    f: &FV,
+    flags: &[Boolean<ConstraintF>],
+) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError>
+where
+    F: Field,
+    ConstraintF: PrimeField,
+    FV: FieldVar<F, ConstraintF>,
+{
+    let modulus_bits = F::BasePrimeField::MODULUS_BIT_SIZE as usize;
+    let sizes = component_sizes::<F>(flags.len());
+    let bits = f.to_bits_le()?;
+
+    let mut bytes = Vec::new();
+    for (i, component) in bits.chunks(bits.len() / sizes.len()).enumerate() {
+        let num_flags = if i == sizes.len() - 1 { flags.len() } else { 0 };
+        // The components are canonical, so any bits beyond `modulus_bits` are zero.
+        let mut component = component[..modulus_bits].to_vec();
+        component.resize(8 * sizes[i] - num_flags, Boolean::FALSE);
+        component.extend_from_slice(&flags[..num_flags]);
+        bytes.extend(component.chunks(8).map(UInt8::from_bits_le));
+    }
+    Ok(bytes)
+}
+
+/// Deserializes a field element and `num_flags` flags from `bytes`, in the
+/// format of `ark-serialize`'s `deserialize_with_flags`.
+///
+/// The constraint system is unsatisfiable if the components of the field
+/// element are not canonical.
+///
+/// # Panics
+///
+/// This function panics if `bytes` does not have the serialized size of an
+/// element of `F` with `num_flags` flags.
+pub(crate) fn field_from_bytes_with_flags<F, ConstraintF, FV>(
+    bytes: &[UInt8<ConstraintF>],
+    num_flags: usize,
+) -> Result<(FV, Vec<Boolean<ConstraintF>>), SynthesisError>
+where
+    F: Field,
+    ConstraintF: PrimeField,
+    FV: FieldVar<F, ConstraintF>,
+{
+    let modulus_bits = F::BasePrimeField::MODULUS_BIT_SIZE as usize;
+    let sizes = component_sizes::<F>(num_flags);
+    assert_eq!(
+        bytes.len(),
+        sizes.iter().sum::<usize>(),
+        "unexpected number of bytes"
+    );
+
+    let cs = bytes.cs();
+    let mut components = Vec::with_capacity(sizes.len());
+    let mut flags = Vec::new();
+    let mut bytes = bytes;
+    for (i, size) in sizes.iter().enumerate() {
+        let (component_bytes, rest) = bytes.split_at(*size);
+        bytes = rest;
+        let mut bits = component_bytes.to_bits_le()?;
+        if i == sizes.len() - 1 {
+            flags = bits.split_off(bits.len() - num_flags);
+        }
+        // The bits between the component and the flags must be zero.
+        for bit in bits.split_off(modulus_bits) {
+            bit.enforce_equal(&Boolean::FALSE)?;
+        }
+        components.push(bits);
+    }
+
+    let mode = if cs.is_none() {
+        AllocationMode::Constant
+    } else {
+        AllocationMode::Witness
+    };
+    let f = FV::new_variable(
+        ark_relations::ns!(cs, "field element"),
+        || {
+            let elements = components
+                .iter()
+                .map(|bits| {
+                    let bytes = bits
+                        .chunks(8)
+                        .map(|byte| {
+                            byte.iter().rev().try_fold(0u8, |acc, bit| {
+                                Ok::<_, SynthesisError>((acc << 1) | bit.value()? as u8)
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(F::BasePrimeField::from_le_bytes_mod_order(&bytes))
+                })
+                .collect::<Result<Vec<_>, SynthesisError>>()?;
+            Ok(F::from_base_prime_field_elems(&elements).unwrap())
+        },
+        mode,
+    )?;
+    // `to_bits_le` returns canonical bits, so this also checks that the
+    // components are canonical.
+    let f_bits = f.to_bits_le()?;
+    for (f_component, component) in f_bits
+        .chunks(f_bits.len() / components.len())
+        .zip(&components)
+    {
+        f_component[..modulus_bits].enforce_equal(component)?;
+    }
+    Ok((f, flags))
+}
+
+/// The byte layouts of compressed points on short Weierstrass curves that are
+/// supported by the compression gadgets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SWCompressedLayout {
+    /// The default layout of `ark-serialize`: the `x`-coordinate in
+    /// little-endian order, with the infinity and sign flags in the two most
+    /// significant bits of the last byte.
+    Arkworks,
+    /// The layout of Zcash, used by BLS12-381: the `x`-coordinate in big-endian
+    /// order (starting from its last component), with the compression, infinity
+    /// and sign flags in the three most significant bits of the first byte.
+    Zcash,
+}
+
+/// Determines which layout `CanonicalSerialize` uses for compressed points of
+/// `P`, based on the serialization of the point at infinity.
+///
+/// # Panics
+///
+/// This function panics if `P` overrides the serialization with a different
+/// layout.
+pub(crate) fn sw_compressed_layout<P: SWCurveConfig>() -> SWCompressedLayout {
+    let mut bytes = Vec::new();
+    SWAffine::<P>::zero()
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    let all_zero = |bytes: &[u8]| bytes.iter().all(|b| *b == 0);
+    match (bytes.split_first(), bytes.split_last()) {
+        (_, Some((&0b0100_0000, rest))) if all_zero(rest) => SWCompressedLayout::Arkworks,
+        (Some((&0b1100_0000, rest)), _) if all_zero(rest) => SWCompressedLayout::Zcash,
+        _ => panic!("unsupported serialization of compressed points"),
+    }
+}
@@ -1,62 +1,107 @@
 use ark_ec::{
     bls12::{Bls12Config, G1Prepared, G2Prepared, TwistType},
-    short_weierstrass::Affine as GroupAffine,
+    hashing::curve_maps::wb::WBConfig,
+    short_weierstrass::{Affine as GroupAffine, Projective as GroupProjective},
+    CurveGroup,
+};
+use ark_ff::{
+    fields::{Fp12Config, Fp2, Fp6Config},
+    BitIteratorBE, Field, One, PrimeField,
 };
-use ark_ff::{BitIteratorBE, Field, One};
 use ark_relations::r1cs::{Namespace, SynthesisError};
 
 use crate::{
-    fields::{fp::FpVar, fp2::Fp2Var, FieldVar},
-    groups::curves::short_weierstrass::*,
+    fields::{fp::FpVar, fp2::Fp2VarWithBase, FieldOpsBounds, FieldVar},
+    groups::{
+        curves::short_weierstrass::*,
+        hashing::{MapToCurveGadget, WBMapGadget},
+    },
     Vec,
 };
-use core::fmt::Debug;
+use core::{fmt::Debug, marker::PhantomData};
 
 /// Represents a projective point in G1.
-pub type G1Var<P> = ProjectiveVar<<P as Bls12Config>::G1Config, FpVar<<P as Bls12Config>::Fp>>;
+///
+/// The coordinates are variables of type `F` over the constraint field
+/// `ConstraintF`. By default, these are `FpVar`s over `P::Fp`; to work over
+/// another constraint field, `F` can be a `NonNativeFieldVar`.
+///
+/// [`CurveVar::clear_cofactor`] uses [`clear_g1_cofactor`].
+pub type G1Var<P, F = BaseFieldVar<P>, ConstraintF = <P as Bls12Config>::Fp> =
+    ProjectiveVar<<P as Bls12Config>::G1Config, F, ConstraintF, G1ClearCofactor<P>>;
 
 /// Represents an affine point on G1. Should be used only for comparison and
 /// when a canonical representation of a point is required, and not for
 /// arithmetic.
-pub type G1AffineVar<P> = AffineVar<<P as Bls12Config>::G1Config, FpVar<<P as Bls12Config>::Fp>>;
+pub type G1AffineVar<P, F = BaseFieldVar<P>, ConstraintF = <P as Bls12Config>::Fp> =
+    AffineVar<<P as Bls12Config>::G1Config, F, ConstraintF>;
 
 /// Represents a projective point in G2.
-pub type G2Var<P> = ProjectiveVar<<P as Bls12Config>::G2Config, Fp2G<P>>;
+///
+/// [`CurveVar::clear_cofactor`] uses [`clear_g2_cofactor`].
+pub type G2Var<P, F = BaseFieldVar<P>, ConstraintF = <P as Bls12Config>::Fp> = ProjectiveVar<
+    <P as Bls12Config>::G2Config,
+    Fp2G<P, F, ConstraintF>,
+    ConstraintF,
+    G2ClearCofactor<P>,
+>;
 /// Represents an affine point on G2. Should be used only for comparison and
 /// when a canonical representation of a point is required, and not for
 /// arithmetic.
-pub type G2AffineVar<P> = AffineVar<<P as Bls12Config>::G2Config, Fp2G<P>>;
+pub type G2AffineVar<P, F = BaseFieldVar<P>, ConstraintF = <P as Bls12Config>::Fp> =
+    AffineVar<<P as Bls12Config>::G2Config, Fp2G<P, F, ConstraintF>, ConstraintF>;
+
+type BaseFieldVar<P> = FpVar<<P as Bls12Config>::Fp>;
 
 /// Represents the cached precomputation that can be performed on a G1 element
 /// which enables speeding up pairing computation.
 #[derive(Derivative)]
-#[derivative(Clone(bound = "G1Var<P>: Clone"), Debug(bound = "G1Var<P>: Debug"))]
-pub struct G1PreparedVar<P: Bls12Config>(pub AffineVar<P::G1Config, FpVar<P::Fp>>);
+#[derivative(Clone(bound = "F: Clone"), Debug(bound = "F: Debug"))]
+pub struct G1PreparedVar<
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as Bls12Config>::Fp,
+>(pub AffineVar<P::G1Config, F, ConstraintF>)
+where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>;
 
-impl<P: Bls12Config> G1PreparedVar<P> {
+impl<P, F, ConstraintF> G1PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     /// Returns the value assigned to `self` in the underlying constraint
     /// system.
     pub fn value(&self) -> Result<G1Prepared<P>, SynthesisError> {
         let x = self.0.x.value()?;
         let y = self.0.y.value()?;
         let infinity = self.0.infinity.value()?;
-        let g = infinity
-            .then_some(GroupAffine::identity())
-            .unwrap_or(GroupAffine::new(x, y))
-            .into();
-        Ok(g)
+        let g = if infinity {
+            GroupAffine::identity()
+        } else {
+            GroupAffine::new_unchecked(x, y)
+        };
+        Ok(g.into())
     }
 
     /// Constructs `Self` from a `G1Var`.
-    pub fn from_group_var(q: &G1Var<P>) -> Result<Self, SynthesisError> {
+    pub fn from_group_var(q: &G1Var<P, F, ConstraintF>) -> Result<Self, SynthesisError> {
         let g = q.to_affine()?;
         Ok(Self(g))
     }
 }
 
-impl<P: Bls12Config> AllocVar<G1Prepared<P>, P::Fp> for G1PreparedVar<P> {
+impl<P, F, ConstraintF> AllocVar<G1Prepared<P>, ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     fn new_variable<T: Borrow<G1Prepared<P>>>(
-        cs: impl Into<Namespace<P::Fp>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -64,8 +109,8 @@ impl<P: Bls12Config> AllocVar<G1Prepared<P>, P::Fp> for G1PreparedVar<P> {
         let cs = ns.cs();
         let g1_prep = f().map(|b| b.borrow().0);
 
-        let x = FpVar::new_variable(ark_relations::ns!(cs, "x"), || g1_prep.map(|g| g.x), mode)?;
-        let y = FpVar::new_variable(ark_relations::ns!(cs, "y"), || g1_prep.map(|g| g.y), mode)?;
+        let x = F::new_variable(ark_relations::ns!(cs, "x"), || g1_prep.map(|g| g.x), mode)?;
+        let y = F::new_variable(ark_relations::ns!(cs, "y"), || g1_prep.map(|g| g.y), mode)?;
         let infinity = Boolean::new_variable(
             ark_relations::ns!(cs, "inf"),
             || g1_prep.map(|g| g.infinity),
@@ -76,10 +121,16 @@ impl<P: Bls12Config> AllocVar<G1Prepared<P>, P::Fp> for G1PreparedVar<P> {
     }
 }
 
-impl<P: Bls12Config> ToBytesGadget<P::Fp> for G1PreparedVar<P> {
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[inline]
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut bytes = self.0.x.to_bytes()?;
         let y_bytes = self.0.y.to_bytes()?;
         let inf_bytes = self.0.infinity.to_bytes()?;
@@ -89,7 +140,7 @@ impl<P: Bls12Config> ToBytesGadget<P::Fp> for G1PreparedVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut bytes = self.0.x.to_non_unique_bytes()?;
         let y_bytes = self.0.y.to_non_unique_bytes()?;
         let inf_bytes = self.0.infinity.to_non_unique_bytes()?;
@@ -99,58 +150,44 @@ impl<P: Bls12Config> ToBytesGadget<P::Fp> for G1PreparedVar<P> {
     }
 }
 
-type Fp2G<P> = Fp2Var<<P as Bls12Config>::Fp2Config>;
-type LCoeff<P> = (Fp2G<P>, Fp2G<P>);
+type Fp2G<P, F = BaseFieldVar<P>, ConstraintF = <P as Bls12Config>::Fp> =
+    Fp2VarWithBase<<P as Bls12Config>::Fp2Config, F, ConstraintF>;
+type LCoeff<P, F, ConstraintF> = (Fp2G<P, F, ConstraintF>, Fp2G<P, F, ConstraintF>);
+type NativeLCoeff<P> = (
+    Fp2<<P as Bls12Config>::Fp2Config>,
+    Fp2<<P as Bls12Config>::Fp2Config>,
+);
 /// Represents the cached precomputation that can be performed on a G2 element
 /// which enables speeding up pairing computation.
 #[derive(Derivative)]
-#[derivative(
-    Clone(bound = "Fp2Var<P::Fp2Config>: Clone"),
-    Debug(bound = "Fp2Var<P::Fp2Config>: Debug")
-)]
-pub struct G2PreparedVar<P: Bls12Config> {
+#[derivative(Clone(bound = "F: Clone"), Debug(bound = "F: Debug"))]
+pub struct G2PreparedVar<
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as Bls12Config>::Fp,
+> where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
     #[doc(hidden)]
-    pub ell_coeffs: Vec<LCoeff<P>>,
+    pub ell_coeffs: Vec<LCoeff<P, F, ConstraintF>>,
 }
 
-impl<P: Bls12Config> AllocVar<G2Prepared<P>, P::Fp> for G2PreparedVar<P> {
+impl<P, F, ConstraintF> AllocVar<G2Prepared<P>, ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[tracing::instrument(target = "r1cs", skip(cs, f, mode))]
     fn new_variable<T: Borrow<G2Prepared<P>>>(
-        cs: impl Into<Namespace<P::Fp>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
         let ns = cs.into();
         let cs = ns.cs();
-        let g2_prep = f().map(|b| {
-            let projective_coeffs = &b.borrow().ell_coeffs;
-            match P::TWIST_TYPE {
-                TwistType::M => {
-                    let mut z_s = projective_coeffs
-                        .iter()
-                        .map(|(_, _, z)| *z)
-                        .collect::<Vec<_>>();
-                    ark_ff::fields::batch_inversion(&mut z_s);
-                    projective_coeffs
-                        .iter()
-                        .zip(z_s)
-                        .map(|((x, y, _), z_inv)| (*x * &z_inv, *y * &z_inv))
-                        .collect::<Vec<_>>()
-                },
-                TwistType::D => {
-                    let mut z_s = projective_coeffs
-                        .iter()
-                        .map(|(z, ..)| *z)
-                        .collect::<Vec<_>>();
-                    ark_ff::fields::batch_inversion(&mut z_s);
-                    projective_coeffs
-                        .iter()
-                        .zip(z_s)
-                        .map(|((_, x, y), z_inv)| (*x * &z_inv, *y * &z_inv))
-                        .collect::<Vec<_>>()
-                },
-            }
-        });
+        let g2_prep = f().map(|b| Self::affine_ell_coeffs(b.borrow()));
 
         let l = Vec::new_variable(
             ark_relations::ns!(cs, "l"),
@@ -171,10 +208,16 @@ impl<P: Bls12Config> AllocVar<G2Prepared<P>, P::Fp> for G2PreparedVar<P> {
     }
 }
 
-impl<P: Bls12Config> ToBytesGadget<P::Fp> for G2PreparedVar<P> {
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[inline]
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut bytes = Vec::new();
         for coeffs in &self.ell_coeffs {
             bytes.extend_from_slice(&coeffs.0.to_bytes()?);
@@ -184,7 +227,7 @@ impl<P: Bls12Config> ToBytesGadget<P::Fp> for G2PreparedVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut bytes = Vec::new();
         for coeffs in &self.ell_coeffs {
             bytes.extend_from_slice(&coeffs.0.to_non_unique_bytes()?);
@@ -194,10 +237,64 @@ impl<P: Bls12Config> ToBytesGadget<P::Fp> for G2PreparedVar<P> {
     }
 }
 
-impl<P: Bls12Config> G2PreparedVar<P> {
+impl<P, F, ConstraintF> G2PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    /// Constructs a constant `Self` from `q`, whose line coefficients are
+    /// precomputed natively. Unlike [`Self::from_group_var`], this requires no
+    /// constraints, and the Miller loop needs none to evaluate its lines.
+    pub fn constant(q: G2Prepared<P>) -> Self {
+        let ell_coeffs = Self::affine_ell_coeffs(&q)
+            .into_iter()
+            .map(|(l, r)| {
+                (
+                    Fp2G::<P, F, ConstraintF>::constant(l),
+                    Fp2G::<P, F, ConstraintF>::constant(r),
+                )
+            })
+            .collect();
+        Self { ell_coeffs }
+    }
+
+    /// Normalizes the projective line coefficients of `q` to the affine ones
+    /// used by [`Self`].
+    fn affine_ell_coeffs(q: &G2Prepared<P>) -> Vec<NativeLCoeff<P>> {
+        let projective_coeffs = &q.ell_coeffs;
+        match P::TWIST_TYPE {
+            TwistType::M => {
+                let mut z_s = projective_coeffs
+                    .iter()
+                    .map(|(_, _, z)| *z)
+                    .collect::<Vec<_>>();
+                ark_ff::fields::batch_inversion(&mut z_s);
+                projective_coeffs
+                    .iter()
+                    .zip(z_s)
+                    .map(|((x, y, _), z_inv)| (*x * &z_inv, *y * &z_inv))
+                    .collect::<Vec<_>>()
+            },
+            TwistType::D => {
+                let mut z_s = projective_coeffs
+                    .iter()
+                    .map(|(z, ..)| *z)
+                    .collect::<Vec<_>>();
+                ark_ff::fields::batch_inversion(&mut z_s);
+                projective_coeffs
+                    .iter()
+                    .zip(z_s)
+                    .map(|((_, x, y), z_inv)| (*x * &z_inv, *y * &z_inv))
+                    .collect::<Vec<_>>()
+            },
+        }
+    }
+
     /// Constructs `Self` from a `G2Var`.
     #[tracing::instrument(target = "r1cs")]
-    pub fn from_group_var(q: &G2Var<P>) -> Result<Self, SynthesisError> {
+    pub fn from_group_var(q: &G2Var<P, F, ConstraintF>) -> Result<Self, SynthesisError> {
         let q = q.to_affine()?;
         let two_inv = P::Fp::one().double().inverse().unwrap();
         // Enforce that `q` is not the point at infinity.
@@ -217,7 +314,10 @@ impl<P: Bls12Config> G2PreparedVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn double(r: &mut G2AffineVar<P>, two_inv: &P::Fp) -> Result<LCoeff<P>, SynthesisError> {
+    fn double(
+        r: &mut G2AffineVar<P, F, ConstraintF>,
+        two_inv: &P::Fp,
+    ) -> Result<LCoeff<P, F, ConstraintF>, SynthesisError> {
         let a = r.y.inverse()?;
         let mut b = r.x.square()?;
         let b_tmp = b.clone();
@@ -241,7 +341,10 @@ impl<P: Bls12Config> G2PreparedVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn add(r: &mut G2AffineVar<P>, q: &G2AffineVar<P>) -> Result<LCoeff<P>, SynthesisError> {
+    fn add(
+        r: &mut G2AffineVar<P, F, ConstraintF>,
+        q: &G2AffineVar<P, F, ConstraintF>,
+    ) -> Result<LCoeff<P, F, ConstraintF>, SynthesisError> {
         let a = (&q.x - &r.x).inverse()?;
         let b = &q.y - &r.y;
         let c = &a * &b;
@@ -261,3 +364,175 @@ impl<P: Bls12Config> G2PreparedVar<P> {
         }
     }
 }
+
+/// Computes `[x] p`, where `x` is the parameter of the BLS12 curve (that is,
+/// `P::X`, negated if `P::X_IS_NEGATIVE`).
+fn mul_by_x<P, C, ConstraintF, V>(p: &V) -> Result<V, SynthesisError>
+where
+    P: Bls12Config,
+    C: CurveGroup,
+    ConstraintF: PrimeField,
+    V: CurveVar<C, ConstraintF>,
+{
+    let mut result = p.clone();
+    for b in BitIteratorBE::without_leading_zeros(P::X).skip(1) {
+        result.double_in_place()?;
+        if b {
+            result += p;
+        }
+    }
+    if P::X_IS_NEGATIVE {
+        result = result.negate()?;
+    }
+    Ok(result)
+}
+
+/// Applies the endomorphism `ψ` ("untwist-Frobenius-twist") of the twist to
+/// `p`. Since the Frobenius map is linear, this costs no constraints.
+fn psi<P, F, ConstraintF, H>(
+    p: &ProjectiveVar<P::G2Config, F, ConstraintF, H>,
+) -> Result<ProjectiveVar<P::G2Config, F, ConstraintF, H>, SynthesisError>
+where
+    P: Bls12Config,
+    F: FieldVar<Fp2<P::Fp2Config>, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P::G2Config>,
+    for<'a> &'a F: FieldOpsBounds<'a, Fp2<P::Fp2Config>, F>,
+{
+    // For a sextic twist with non-residue `ξ`, `ψ` multiplies the coordinates
+    // of the Frobenius of `p` by `ξ^((p - 1) / 3)` and `ξ^((p - 1) / 2)`
+    // (or by their inverses, for an M-type twist).
+    let c_x: Fp2<P::Fp2Config> = P::Fp6Config::FROBENIUS_COEFF_FP6_C1[1];
+    let c_y: Fp2<P::Fp2Config> = P::Fp12Config::FROBENIUS_COEFF_FP12_C1[1].pow([3]);
+    let (c_x, c_y) = match P::TWIST_TYPE {
+        TwistType::M => (c_x.inverse().unwrap(), c_y.inverse().unwrap()),
+        TwistType::D => (c_x, c_y),
+    };
+    Ok(ProjectiveVar::new(
+        p.x.frobenius_map(1)? * c_x,
+        p.y.frobenius_map(1)? * c_y,
+        p.z.frobenius_map(1)?,
+    ))
+}
+
+/// Maps `p` into the prime-order subgroup of G1, by multiplying it by the
+/// effective cofactor `|x - 1|` instead of the cofactor `(x - 1)^2 / 3`.
+///
+/// This agrees with `clear_cofactor` of BLS12-377 and BLS12-381 (where
+/// `|x - 1| = 1 - x` is `h_eff` of RFC 9380).
+#[tracing::instrument(target = "r1cs")]
+pub fn clear_g1_cofactor<P, F, ConstraintF, H>(
+    p: &ProjectiveVar<P::G1Config, F, ConstraintF, H>,
+) -> Result<ProjectiveVar<P::G1Config, F, ConstraintF, H>, SynthesisError>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P::G1Config>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
+    let result = mul_by_x::<P, _, _, _>(p)? - p;
+    if P::X_IS_NEGATIVE {
+        result.negate()
+    } else {
+        Ok(result)
+    }
+}
+
+/// Maps `p` into the prime-order subgroup of G2, by multiplying it by the
+/// effective cofactor `h(ψ)` of Budroni and Pintore
+/// (<https://eprint.iacr.org/2017/419>, Section 4.1):
+/// `[x^2 - x - 1] p + [x - 1] ψ(p) + ψ^2(2p)`.
+///
+/// This costs two multiplications by `x` and a handful of additions, instead
+/// of a multiplication by the much larger cofactor of G2, and agrees with
+/// `clear_cofactor` of BLS12-377 and BLS12-381 (where it is `h_eff` of
+/// RFC 9380).
+#[tracing::instrument(target = "r1cs")]
+pub fn clear_g2_cofactor<P, F, ConstraintF, H>(
+    p: &ProjectiveVar<P::G2Config, F, ConstraintF, H>,
+) -> Result<ProjectiveVar<P::G2Config, F, ConstraintF, H>, SynthesisError>
+where
+    P: Bls12Config,
+    F: FieldVar<Fp2<P::Fp2Config>, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P::G2Config>,
+    for<'a> &'a F: FieldOpsBounds<'a, Fp2<P::Fp2Config>, F>,
+{
+    let x_p = mul_by_x::<P, _, _, _>(p)?;
+    let psi_p = psi::<P, _, _, _>(p)?;
+    let psi2_p2 = psi::<P, _, _, _>(&psi::<P, _, _, _>(&p.double()?)?)?;
+    // [x^2] p + [x] ψ(p)
+    let x_tmp = mul_by_x::<P, _, _, _>(&(x_p.clone() + &psi_p))?;
+    Ok(psi2_p2 + x_tmp - x_p - psi_p - p)
+}
+
+/// The [`ClearCofactorConfig`] of [`G1Var`], which uses [`clear_g1_cofactor`].
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Copy(bound = ""), Debug(bound = ""))]
+pub struct G1ClearCofactor<P>(PhantomData<P>);
+
+impl<P: Bls12Config> ClearCofactorConfig<P::G1Config> for G1ClearCofactor<P> {
+    fn clear_cofactor<F, ConstraintF>(
+        p: &ProjectiveVar<P::G1Config, F, ConstraintF, Self>,
+    ) -> Result<ProjectiveVar<P::G1Config, F, ConstraintF, Self>, SynthesisError>
+    where
+        F: FieldVar<P::Fp, ConstraintF>,
+        ConstraintF: PrimeField,
+        for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    {
+        clear_g1_cofactor::<P, _, _, _>(p)
+    }
+}
+
+/// The [`ClearCofactorConfig`] of [`G2Var`], which uses [`clear_g2_cofactor`].
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Copy(bound = ""), Debug(bound = ""))]
+pub struct G2ClearCofactor<P>(PhantomData<P>);
+
+impl<P: Bls12Config> ClearCofactorConfig<P::G2Config> for G2ClearCofactor<P> {
+    fn clear_cofactor<F, ConstraintF>(
+        p: &ProjectiveVar<P::G2Config, F, ConstraintF, Self>,
+    ) -> Result<ProjectiveVar<P::G2Config, F, ConstraintF, Self>, SynthesisError>
+    where
+        F: FieldVar<Fp2<P::Fp2Config>, ConstraintF>,
+        ConstraintF: PrimeField,
+        for<'a> &'a F: FieldOpsBounds<'a, Fp2<P::Fp2Config>, F>,
+    {
+        clear_g2_cofactor::<P, _, _, _>(p)
+    }
+}
+
+/// The map to G1 of the hash-to-curve suites of RFC 9380 for BLS12 curves,
+/// i.e., [`WBMapGadget`] followed by [`clear_g1_cofactor`].
+pub struct G1MapToCurveGadget<P>(PhantomData<P>);
+
+impl<P: Bls12Config> MapToCurveGadget<GroupProjective<P::G1Config>, P::Fp> for G1MapToCurveGadget<P>
+where
+    P::G1Config: WBConfig,
+{
+    type CurveVar = G1Var<P>;
+    type BaseFieldVar = FpVar<P::Fp>;
+
+    fn map_to_curve(u: &FpVar<P::Fp>) -> Result<G1Var<P>, SynthesisError> {
+        let p = WBMapGadget::<P::G1Config, FpVar<P::Fp>>::map_to_curve(u)?;
+        Ok(G1Var::<P>::new(p.x, p.y, p.z))
+    }
+}
+
+/// The map to G2 of the hash-to-curve suites of RFC 9380 for BLS12 curves,
+/// i.e., [`WBMapGadget`] followed by [`clear_g2_cofactor`].
+pub struct G2MapToCurveGadget<P>(PhantomData<P>);
+
+impl<P: Bls12Config> MapToCurveGadget<GroupProjective<P::G2Config>, P::Fp> for G2MapToCurveGadget<P>
+where
+    P::G2Config: WBConfig,
+{
+    type CurveVar = G2Var<P>;
+    type BaseFieldVar = Fp2G<P>;
+
+    fn map_to_curve(u: &Fp2G<P>) -> Result<G2Var<P>, SynthesisError> {
+        let p = WBMapGadget::<P::G2Config, Fp2G<P>>::map_to_curve(u)?;
+        Ok(G2Var::<P>::new(p.x, p.y, p.z))
+    }
+}
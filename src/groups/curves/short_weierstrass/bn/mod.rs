@@ -0,0 +1,363 @@
+use ark_ec::{
+    bn::{BnConfig, G1Prepared, G2Prepared, TwistType},
+    short_weierstrass::Affine as GroupAffine,
+};
+use ark_ff::{Field, One, PrimeField};
+use ark_relations::r1cs::{Namespace, SynthesisError};
+
+use crate::{
+    fields::{fp::FpVar, fp2::Fp2VarWithBase, FieldOpsBounds, FieldVar},
+    groups::curves::short_weierstrass::*,
+    Vec,
+};
+use core::fmt::Debug;
+
+/// Represents a projective point in G1.
+///
+/// The coordinates are variables of type `F` over the constraint field
+/// `ConstraintF`. By default, these are `FpVar`s over `P::Fp`; to work over
+/// another constraint field, `F` can be a `NonNativeFieldVar`.
+pub type G1Var<P, F = BaseFieldVar<P>, ConstraintF = <P as BnConfig>::Fp> =
+    ProjectiveVar<<P as BnConfig>::G1Config, F, ConstraintF>;
+
+/// Represents an affine point on G1. Should be used only for comparison and
+/// when a canonical representation of a point is required, and not for
+/// arithmetic.
+pub type G1AffineVar<P, F = BaseFieldVar<P>, ConstraintF = <P as BnConfig>::Fp> =
+    AffineVar<<P as BnConfig>::G1Config, F, ConstraintF>;
+
+/// Represents a projective point in G2.
+pub type G2Var<P, F = BaseFieldVar<P>, ConstraintF = <P as BnConfig>::Fp> =
+    ProjectiveVar<<P as BnConfig>::G2Config, Fp2G<P, F, ConstraintF>, ConstraintF>;
+/// Represents an affine point on G2. Should be used only for comparison and
+/// when a canonical representation of a point is required, and not for
+/// arithmetic.
+pub type G2AffineVar<P, F = BaseFieldVar<P>, ConstraintF = <P as BnConfig>::Fp> =
+    AffineVar<<P as BnConfig>::G2Config, Fp2G<P, F, ConstraintF>, ConstraintF>;
+
+type BaseFieldVar<P> = FpVar<<P as BnConfig>::Fp>;
+
+/// Represents the cached precomputation that can be performed on a G1 element
+/// which enables speeding up pairing computation.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "F: Clone"), Debug(bound = "F: Debug"))]
+pub struct G1PreparedVar<
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as BnConfig>::Fp,
+>(pub AffineVar<P::G1Config, F, ConstraintF>)
+where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>;
+
+impl<P, F, ConstraintF> G1PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    /// Returns the value assigned to `self` in the underlying constraint
+    /// system.
+    pub fn value(&self) -> Result<G1Prepared<P>, SynthesisError> {
+        let x = self.0.x.value()?;
+        let y = self.0.y.value()?;
+        let infinity = self.0.infinity.value()?;
+        let g = if infinity {
+            GroupAffine::identity()
+        } else {
+            GroupAffine::new_unchecked(x, y)
+        };
+        Ok(g.into())
+    }
+
+    /// Constructs `Self` from a `G1Var`.
+    pub fn from_group_var(q: &G1Var<P, F, ConstraintF>) -> Result<Self, SynthesisError> {
+        let g = q.to_affine()?;
+        Ok(Self(g))
+    }
+}
+
+impl<P, F, ConstraintF> AllocVar<G1Prepared<P>, ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    fn new_variable<T: Borrow<G1Prepared<P>>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let g1_prep = f().map(|b| b.borrow().0);
+
+        let x = F::new_variable(ark_relations::ns!(cs, "x"), || g1_prep.map(|g| g.x), mode)?;
+        let y = F::new_variable(ark_relations::ns!(cs, "y"), || g1_prep.map(|g| g.y), mode)?;
+        let infinity = Boolean::new_variable(
+            ark_relations::ns!(cs, "inf"),
+            || g1_prep.map(|g| g.infinity),
+            mode,
+        )?;
+        let g = AffineVar::new(x, y, infinity);
+        Ok(Self(g))
+    }
+}
+
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    #[inline]
+    #[tracing::instrument(target = "r1cs")]
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
+        let mut bytes = self.0.x.to_bytes()?;
+        let y_bytes = self.0.y.to_bytes()?;
+        let inf_bytes = self.0.infinity.to_bytes()?;
+        bytes.extend_from_slice(&y_bytes);
+        bytes.extend_from_slice(&inf_bytes);
+        Ok(bytes)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
+        let mut bytes = self.0.x.to_non_unique_bytes()?;
+        let y_bytes = self.0.y.to_non_unique_bytes()?;
+        let inf_bytes = self.0.infinity.to_non_unique_bytes()?;
+        bytes.extend_from_slice(&y_bytes);
+        bytes.extend_from_slice(&inf_bytes);
+        Ok(bytes)
+    }
+}
+
+type Fp2G<P, F = BaseFieldVar<P>, ConstraintF = <P as BnConfig>::Fp> =
+    Fp2VarWithBase<<P as BnConfig>::Fp2Config, F, ConstraintF>;
+type LCoeff<P, F, ConstraintF> = (Fp2G<P, F, ConstraintF>, Fp2G<P, F, ConstraintF>);
+/// Represents the cached precomputation that can be performed on a G2 element
+/// which enables speeding up pairing computation.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "F: Clone"), Debug(bound = "F: Debug"))]
+pub struct G2PreparedVar<
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as BnConfig>::Fp,
+> where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
+    #[doc(hidden)]
+    pub ell_coeffs: Vec<LCoeff<P, F, ConstraintF>>,
+}
+
+impl<P, F, ConstraintF> AllocVar<G2Prepared<P>, ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    #[tracing::instrument(target = "r1cs", skip(cs, f, mode))]
+    fn new_variable<T: Borrow<G2Prepared<P>>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+        let g2_prep = f().map(|b| {
+            let projective_coeffs = &b.borrow().ell_coeffs;
+            match P::TWIST_TYPE {
+                TwistType::M => {
+                    let mut z_s = projective_coeffs
+                        .iter()
+                        .map(|(_, _, z)| *z)
+                        .collect::<Vec<_>>();
+                    ark_ff::fields::batch_inversion(&mut z_s);
+                    projective_coeffs
+                        .iter()
+                        .zip(z_s)
+                        .map(|((x, y, _), z_inv)| (*x * &z_inv, *y * &z_inv))
+                        .collect::<Vec<_>>()
+                },
+                TwistType::D => {
+                    let mut z_s = projective_coeffs
+                        .iter()
+                        .map(|(z, ..)| *z)
+                        .collect::<Vec<_>>();
+                    ark_ff::fields::batch_inversion(&mut z_s);
+                    projective_coeffs
+                        .iter()
+                        .zip(z_s)
+                        .map(|((_, x, y), z_inv)| (*x * &z_inv, *y * &z_inv))
+                        .collect::<Vec<_>>()
+                },
+            }
+        });
+
+        let l = Vec::new_variable(
+            ark_relations::ns!(cs, "l"),
+            || {
+                g2_prep
+                    .clone()
+                    .map(|c| c.iter().map(|(l, _)| *l).collect::<Vec<_>>())
+            },
+            mode,
+        )?;
+        let r = Vec::new_variable(
+            ark_relations::ns!(cs, "r"),
+            || g2_prep.map(|c| c.iter().map(|(_, r)| *r).collect::<Vec<_>>()),
+            mode,
+        )?;
+        let ell_coeffs = l.into_iter().zip(r).collect();
+        Ok(Self { ell_coeffs })
+    }
+}
+
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    #[inline]
+    #[tracing::instrument(target = "r1cs")]
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
+        let mut bytes = Vec::new();
+        for coeffs in &self.ell_coeffs {
+            bytes.extend_from_slice(&coeffs.0.to_bytes()?);
+            bytes.extend_from_slice(&coeffs.1.to_bytes()?);
+        }
+        Ok(bytes)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
+        let mut bytes = Vec::new();
+        for coeffs in &self.ell_coeffs {
+            bytes.extend_from_slice(&coeffs.0.to_non_unique_bytes()?);
+            bytes.extend_from_slice(&coeffs.1.to_non_unique_bytes()?);
+        }
+        Ok(bytes)
+    }
+}
+
+impl<P, F, ConstraintF> G2PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    /// Constructs `Self` from a `G2Var`.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn from_group_var(q: &G2Var<P, F, ConstraintF>) -> Result<Self, SynthesisError> {
+        let q = q.to_affine()?;
+        let two_inv = P::Fp::one().double().inverse().unwrap();
+        // Enforce that `q` is not the point at infinity.
+        q.infinity.enforce_not_equal(&Boolean::Constant(true))?;
+        let mut ell_coeffs = vec![];
+        let mut r = q.clone();
+        let neg_q = Self::negate(&q)?;
+
+        // The NAF of `6x + 2`.
+        for bit in P::ATE_LOOP_COUNT.iter().rev().skip(1) {
+            ell_coeffs.push(Self::double(&mut r, &two_inv)?);
+
+            match bit {
+                1 => ell_coeffs.push(Self::add(&mut r, &q)?),
+                -1 => ell_coeffs.push(Self::add(&mut r, &neg_q)?),
+                _ => continue,
+            }
+        }
+
+        // The two final additions of `π(q)` and `-π^2(q)`, where `π` is the
+        // Frobenius endomorphism.
+        let q1 = Self::mul_by_char(&q)?;
+        let q2 = Self::negate(&Self::mul_by_char(&q1)?)?;
+        if P::X_IS_NEGATIVE {
+            r = Self::negate(&r)?;
+        }
+        ell_coeffs.push(Self::add(&mut r, &q1)?);
+        ell_coeffs.push(Self::add(&mut r, &q2)?);
+
+        Ok(Self { ell_coeffs })
+    }
+
+    /// Computes `-q`.
+    fn negate(
+        q: &G2AffineVar<P, F, ConstraintF>,
+    ) -> Result<G2AffineVar<P, F, ConstraintF>, SynthesisError> {
+        Ok(G2AffineVar::<P, F, ConstraintF>::new(
+            q.x.clone(),
+            q.y.negate()?,
+            q.infinity.clone(),
+        ))
+    }
+
+    /// Applies the Frobenius endomorphism `π` of the curve (untwisted) to `q`,
+    /// which costs no constraints.
+    fn mul_by_char(
+        q: &G2AffineVar<P, F, ConstraintF>,
+    ) -> Result<G2AffineVar<P, F, ConstraintF>, SynthesisError> {
+        Ok(G2AffineVar::<P, F, ConstraintF>::new(
+            q.x.frobenius_map(1)? * P::TWIST_MUL_BY_Q_X,
+            q.y.frobenius_map(1)? * P::TWIST_MUL_BY_Q_Y,
+            q.infinity.clone(),
+        ))
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn double(
+        r: &mut G2AffineVar<P, F, ConstraintF>,
+        two_inv: &P::Fp,
+    ) -> Result<LCoeff<P, F, ConstraintF>, SynthesisError> {
+        let a = r.y.inverse()?;
+        let mut b = r.x.square()?;
+        let b_tmp = b.clone();
+        b.mul_assign_by_base_field_constant(*two_inv);
+        b += &b_tmp;
+
+        let c = &a * &b;
+        let d = r.x.double()?;
+        let x3 = c.square()? - &d;
+        let e = &c * &r.x - &r.y;
+        let c_x3 = &c * &x3;
+        let y3 = &e - &c_x3;
+        let mut f = c;
+        f.negate_in_place()?;
+        r.x = x3;
+        r.y = y3;
+        match P::TWIST_TYPE {
+            TwistType::M => Ok((e, f)),
+            TwistType::D => Ok((f, e)),
+        }
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn add(
+        r: &mut G2AffineVar<P, F, ConstraintF>,
+        q: &G2AffineVar<P, F, ConstraintF>,
+    ) -> Result<LCoeff<P, F, ConstraintF>, SynthesisError> {
+        let a = (&q.x - &r.x).inverse()?;
+        let b = &q.y - &r.y;
+        let c = &a * &b;
+        let d = &r.x + &q.x;
+        let x3 = c.square()? - &d;
+
+        let e = (&r.x - &x3) * &c;
+        let y3 = e - &r.y;
+        let g = &c * &r.x - &r.y;
+        let mut f = c;
+        f.negate_in_place()?;
+        r.x = x3;
+        r.y = y3;
+        match P::TWIST_TYPE {
+            TwistType::M => Ok((g, f)),
+            TwistType::D => Ok((f, g)),
+        }
+    }
+}
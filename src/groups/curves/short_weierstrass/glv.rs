@@ -0,0 +1,602 @@
+use super::{non_zero_affine::NonZeroAffineVar, ProjectiveVar};
+use crate::{fields::nonnative::BigUintVar, prelude::*, Vec};
+use ark_ec::short_weierstrass::{Affine as SWAffine, SWCurveConfig as SWModelParameters};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_relations::r1cs::SynthesisError;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed};
+
+/// Parameters of a short Weierstrass curve with an efficiently computable
+/// endomorphism `φ(x, y) = (β * x, y)`, which acts on the prime-order subgroup
+/// as multiplication by the scalar `λ`. These are used to speed up scalar
+/// multiplication via the method of [\[GLV01\]](<https://www.iacr.org/archive/crypto2001/21390189.pdf>).
+///
+/// Implementing this trait does not change [`CurveVar::scalar_mul_le`] or the
+/// other scalar multiplication methods of `ProjectiveVar<P, F>`: callers must
+/// opt in explicitly by calling [`ProjectiveVar::scalar_mul_le_glv`], which
+/// has the additional precondition that the point lies in the prime-order
+/// subgroup.
+///
+/// This crate does not implement this trait for any curve, as that would
+/// require depending on the curve crates; the implementations belong next to
+/// the curve configurations. The tests of this module contain implementations
+/// for the `G1` configurations of BLS12-377, BLS12-381 and BN254, and for the
+/// configurations of Pallas and secp256k1, which can serve as a reference.
+pub trait GLVConfig: SWModelParameters {
+    /// The cube root of unity `β` in the base field that defines the
+    /// endomorphism.
+    const ENDO_COEFF: Self::BaseField;
+
+    /// The cube root of unity `λ` in the scalar field such that
+    /// `φ(P) = λ * P` for every `P` in the prime-order subgroup.
+    const LAMBDA: Self::ScalarField;
+
+    /// A reduced basis `[(n11, n12), (n21, n22)]` of the lattice of pairs
+    /// `(a, b)` with `a + λ * b = 0 mod r`, used for decomposing scalars.
+    /// Each entry is given as a `(is_positive, absolute value)` pair.
+    const SCALAR_DECOMP_COEFFS: [(bool, <Self::ScalarField as PrimeField>::BigInt); 4];
+
+    /// Applies the endomorphism `φ` to `p`.
+    fn endomorphism_affine(p: &SWAffine<Self>) -> SWAffine<Self> {
+        let mut result = *p;
+        result.x *= Self::ENDO_COEFF;
+        result
+    }
+
+    /// Decomposes `k` into a pair of short signed integers `(k1, k2)` such
+    /// that `k = k1 + λ * k2 mod r`.
+    fn scalar_decomposition(k: &Self::ScalarField) -> (BigInt, BigInt) {
+        let [n11, n12, n21, n22] = decomposition_coeffs::<Self>();
+        let k = BigInt::from_biguint(Sign::Plus, (*k).into());
+
+        // Write `(k, 0) = c1 * (n11, n12) + c2 * (n21, n22)` over the rationals,
+        // and round `c1` and `c2` to the closest integers.
+        let det = &n11 * &n22 - &n12 * &n21;
+        let round_div = |num: BigInt| -> BigInt {
+            let (num, den) = if det.is_negative() {
+                (-num, -&det)
+            } else {
+                (num, det.clone())
+            };
+            (num * 2u8 + &den).div_floor(&(den * 2u8))
+        };
+        let c1 = round_div(&k * &n22);
+        let c2 = round_div(-&k * &n12);
+
+        let k1 = k - &c1 * &n11 - &c2 * &n21;
+        let k2 = -c1 * &n12 - c2 * &n22;
+        (k1, k2)
+    }
+}
+
+/// Returns the entries of `P::SCALAR_DECOMP_COEFFS` as signed integers.
+fn decomposition_coeffs<P: GLVConfig>() -> [BigInt; 4] {
+    P::SCALAR_DECOMP_COEFFS.map(|(is_positive, n)| {
+        let sign = if is_positive { Sign::Plus } else { Sign::Minus };
+        BigInt::from_biguint(sign, n.into())
+    })
+}
+
+impl<P, F> ProjectiveVar<P, F>
+where
+    P: GLVConfig,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    /// Computes `bits * self`, where `bits` is a little-endian `Boolean`
+    /// representation of a scalar, using the GLV endomorphism.
+    ///
+    /// The scalar `k` is decomposed out of circuit into `k1 + λ * k2 mod r`,
+    /// where `k1` and `k2` have roughly half the bit length of `r`. The
+    /// decomposition is enforced with emulated integer arithmetic, and
+    /// `k1 * self + k2 * φ(self)` is then computed by a joint double-and-add
+    /// over the shorter scalars. This needs half as many doublings as
+    /// [`CurveVar::scalar_mul_le`]; for a 255-bit scalar on BLS12-381 G1, the
+    /// total number of constraints is reduced by more than a third.
+    ///
+    /// `self` must be in the prime-order subgroup, as otherwise `φ(self)` is
+    /// not equal to `λ * self`. The affine additions in the main loop are
+    /// incomplete; for such `self`, the exceptional cases either cannot occur
+    /// or are ruled out by constraints, so that the result is sound, and an
+    /// honest prover only runs into them with negligible probability.
+    ///
+    /// Because of the former precondition, which [`CurveVar::scalar_mul_le`]
+    /// does not have, and because `CurveVar` is implemented for
+    /// `ProjectiveVar<P, F>` for every `P`, whether or not `P: GLVConfig`,
+    /// `scalar_mul_le` does not dispatch to this method. Callers that know
+    /// that `self` lies in the prime-order subgroup should call it directly.
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    pub fn scalar_mul_le_glv<'a>(
+        &self,
+        bits: impl Iterator<Item = &'a Boolean<<P::BaseField as Field>::BasePrimeField>>,
+    ) -> Result<Self, SynthesisError> {
+        let bits = bits.cloned().collect::<Vec<_>>();
+        if bits.is_empty() {
+            return Ok(Self::zero());
+        }
+        if self.is_constant() && bits.is_constant() {
+            return self.scalar_mul_le(bits.iter());
+        }
+        if self.is_constant() && self.value()?.is_zero() {
+            return Ok(self.clone());
+        }
+        let cs = self.cs().or(bits.cs());
+
+        // Each `ki` is bounded by half the sum of the absolute values of the
+        // corresponding column of the lattice basis. We represent `ki` by the
+        // `(num_bits + 1)`-bit integer `ui = ki + 2^num_bits - 1`.
+        let [n11, n12, n21, n22] = decomposition_coeffs::<P>();
+        let num_bits = ark_std::cmp::max(
+            (n11.abs() + n21.abs()).bits(),
+            (n12.abs() + n22.abs()).bits(),
+        ) as usize;
+        let offset = (BigInt::one() << num_bits) - 1u8;
+
+        // The double-and-add below outputs
+        // `(2^(num_bits + 1) + k1) * self + (2^(num_bits + 1) + k2) * φ(self)`,
+        // and so we decompose `k - 2^(num_bits + 1) * (1 + λ)`. That is, we
+        // enforce `u1 + λ * u2 + c = k + q * r` over the integers, where `c` is
+        // congruent to `(2^num_bits + 1) * (1 + λ)` modulo `r`, and is large
+        // enough for `q` to be non-negative.
+        let modulus: BigUint = P::ScalarField::MODULUS.into();
+        let lambda: BigUint = P::LAMBDA.into();
+        let max_scalar = (BigUint::one() << bits.len()) - 1u8;
+        let c = {
+            let shift = ((BigUint::one() << num_bits) + 1u8) * (&lambda + 1u8) % &modulus;
+            shift + (&max_scalar / &modulus + 1u8) * &modulus
+        };
+        let max_u = (BigUint::one() << (num_bits + 1)) - 1u8;
+        let max_q = (&max_u + &lambda * &max_u + &c) / &modulus;
+
+        let witness = || -> Result<(BigUint, BigUint, BigUint), SynthesisError> {
+            let mut k = BigUint::zero();
+            for (i, bit) in bits.iter().enumerate() {
+                if bit.value()? {
+                    k.set_bit(i as u64, true);
+                }
+            }
+            let shift = P::ScalarField::from(2u8).pow([num_bits as u64 + 1])
+                * (P::LAMBDA + P::ScalarField::one());
+            let (k1, k2) = P::scalar_decomposition(&(P::ScalarField::from(k.clone()) - shift));
+            let u1 = (k1 + &offset).to_biguint().unwrap();
+            let u2 = (k2 + &offset).to_biguint().unwrap();
+            let q = (&u1 + &lambda * &u2 + &c - k) / &modulus;
+            Ok((u1, u2, q))
+        };
+        let witness = witness();
+        let alloc_bits = |value: Result<&BigUint, SynthesisError>, num_bits: usize| {
+            (0..num_bits)
+                .map(|i| {
+                    Boolean::new_witness(ark_relations::ns!(cs, "bit"), || {
+                        value.map(|v| v.bit(i as u64))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let u1_bits = alloc_bits(witness.as_ref().map(|w| &w.0).map_err(|e| *e), num_bits + 1)?;
+        let u2_bits = alloc_bits(witness.as_ref().map(|w| &w.1).map_err(|e| *e), num_bits + 1)?;
+
+        let constraint_modulus: BigUint =
+            <<P::BaseField as Field>::BasePrimeField as PrimeField>::MODULUS.into();
+        let max_left = &max_u + &lambda * &max_u + &c;
+        // `q` is only range-checked to `max_q.bits()` bits, so a malicious
+        // prover can pick any `q` below `2^max_q.bits()`, not just `q <= max_q`.
+        let max_q_bits = (BigUint::one() << max_q.bits()) - 1u8;
+        let max_right = &max_scalar + &max_q_bits * &modulus;
+        if max_left < constraint_modulus && max_right < constraint_modulus {
+            // Both sides are smaller than the modulus of the constraint field, so
+            // the equation can be checked natively, with a single constraint.
+            let q_bits = alloc_bits(
+                witness.as_ref().map(|w| &w.2).map_err(|e| *e),
+                max_q.bits() as usize,
+            )?;
+            let constant = |v: &BigUint| <P::BaseField as Field>::BasePrimeField::from(v.clone());
+            let left = Boolean::le_bits_to_fp_var(&u1_bits)?
+                + Boolean::le_bits_to_fp_var(&u2_bits)? * constant(&lambda)
+                + constant(&c);
+            let right = Boolean::le_bits_to_fp_var(&bits)?
+                + Boolean::le_bits_to_fp_var(&q_bits)? * constant(&modulus);
+            left.enforce_equal(&right)?;
+        } else {
+            let q = BigUintVar::new_witness_with_num_bits(
+                ark_relations::ns!(cs, "quotient"),
+                || witness.as_ref().map(|w| w.2.clone()).map_err(|e| *e),
+                max_q.bits() as usize,
+            )?;
+            let left = BigUintVar::from_bits_le(&u1_bits)?
+                .add(&BigUintVar::constant(&lambda).mul(&BigUintVar::from_bits_le(&u2_bits)?)?)?
+                .add(&BigUintVar::constant(&c))?;
+            let right =
+                BigUintVar::from_bits_le(&bits)?.add(&q.mul(&BigUintVar::constant(&modulus))?)?;
+            left.enforce_equal(&right)?;
+        }
+
+        // We first handle the non-zero case, and then later will conditionally
+        // select zero if `self` was zero. To keep the constraint system
+        // satisfiable in that case, we replace `self` by the generator.
+        let self_affine = self.to_affine()?;
+        let infinity = self_affine.infinity;
+        let generator = P::GENERATOR;
+        let base = NonZeroAffineVar::new(
+            infinity.select(&F::constant(generator.x), &self_affine.x)?,
+            infinity.select(&F::constant(generator.y), &self_affine.y)?,
+        );
+        // `φ` fixes exactly the points with `x = 0`, which are not in the
+        // prime-order subgroup. Ruling them out ensures that `base != ±φ(base)`.
+        base.x.enforce_not_equal(&F::zero())?;
+        let endo_base = NonZeroAffineVar::new(&base.x * P::ENDO_COEFF, base.y.clone());
+
+        // `ui = 1 + 2 * Σ_j 2^j * ui[j + 1]`, and so `ki = ui - 2^num_bits + 1`
+        // equals `ui[0] + Σ_j 2^j * (2 * ui[j + 1] - 1)`. The latter sum has
+        // digits in `{-1, 1}`, so each step of the double-and-add adds one of
+        // `±base ± φ(base)`, which are all non-zero.
+        let sum = base.add_unchecked(&endo_base)?;
+        let difference = base.add_unchecked(&NonZeroAffineVar::new(
+            endo_base.x.clone(),
+            endo_base.y.negate()?,
+        ))?;
+        let lookup = |b1: &Boolean<_>, b2: &Boolean<_>| -> Result<_, SynthesisError> {
+            let same_sign = b1.xor(b2)?.not();
+            let point = same_sign.select(&sum, &difference)?;
+            let y = b1.select(&point.y, &point.y.negate()?)?;
+            Ok(NonZeroAffineVar::new(point.x, y))
+        };
+
+        // Computes the standard big-endian double-and-add algorithm, jointly for
+        // both scalars, starting from `2 * (base + φ(base))`.
+        //
+        // The additions below are incomplete. Before each step, the accumulator
+        // is `a * base + b * φ(base)` for integers `2 <= a, b <= max`. As
+        // `λ^2 + λ + 1 = 0 mod r`, every pair `(a', b') != (0, 0)` with
+        // `a' + λ * b' = 0 mod r` has `a'^2 - a' * b' + b'^2 >= r`, and so, if
+        // `a', b' > 0`, then `max(a', b')^2 >= r`. Hence, the exceptional cases
+        // `accumulator = ±addend` and `accumulator + addend = -accumulator` cannot
+        // occur while `(2 * max + 1)^2 < r`. For the last few steps, which exceed
+        // this bound, we rule out the former explicitly, while the latter makes
+        // the constraint system unsatisfiable. An honest prover only runs into
+        // these with negligible probability.
+        let mut accumulator = sum.double()?;
+        let mut max = BigUint::from(2u8);
+        for i in (1..=num_bits).rev() {
+            let addend = lookup(&u1_bits[i], &u2_bits[i])?;
+            let bound = &max * 2u8 + 1u8;
+            if &bound * &bound >= modulus {
+                accumulator.x.enforce_not_equal(&addend.x)?;
+            }
+            accumulator = accumulator.double_and_add_unchecked(&addend)?;
+            max = bound;
+        }
+
+        // Finally, add the low bits `u1[0] * base + u2[0] * φ(base)`. By the same
+        // argument, the accumulator can only equal `±base` or `±φ(base)` for
+        // coefficients that exceed `sqrt(r)`, which we rule out explicitly.
+        for (bit, point) in [(&u1_bits[0], &base), (&u2_bits[0], &endo_base)] {
+            accumulator.x.enforce_not_equal(&point.x)?;
+            let sum = accumulator.add_unchecked(point)?;
+            accumulator = bit.select(&sum, &accumulator)?;
+        }
+
+        infinity.select(&Self::zero(), &accumulator.into_projective())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GLVConfig;
+    use crate::{
+        alloc::AllocVar,
+        boolean::Boolean,
+        fields::fp::FpVar,
+        groups::{curves::short_weierstrass::ProjectiveVar, CurveVar},
+        R1CSVar,
+    };
+    use ark_ec::{
+        models::short_weierstrass::{Projective as ShortWeierstrassProjective, SWCurveConfig},
+        CurveGroup,
+    };
+    use ark_ff::{BigInt, BigInteger, MontFp, PrimeField, UniformRand, Zero};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::vec::Vec;
+    use ark_test_curves::bls12_381::{g1::Config as G1Config, Fq, Fr, G1Projective};
+    use num_bigint::{BigInt as SignedBigInt, BigUint};
+
+    impl GLVConfig for G1Config {
+        const ENDO_COEFF: Fq = MontFp!("4002409555221667392624310435006688643935503118305586438271171395842971157480381377015405980053539358417135540939436");
+
+        const LAMBDA: Fr = MontFp!("228988810152649578064853576960394133503");
+
+        const SCALAR_DECOMP_COEFFS: [(bool, <Fr as PrimeField>::BigInt); 4] = [
+            (true, BigInt!("1")),
+            (true, BigInt!("228988810152649578064853576960394133504")),
+            (true, BigInt!("228988810152649578064853576960394133503")),
+            (false, BigInt!("1")),
+        ];
+    }
+
+    type G1Var = ProjectiveVar<G1Config, FpVar<Fq>>;
+
+    #[test]
+    fn test_scalar_decomposition() {
+        let mut rng = ark_std::test_rng();
+        let generator = G1Config::GENERATOR;
+        assert_eq!(
+            G1Config::endomorphism_affine(&generator),
+            (generator * G1Config::LAMBDA).into_affine()
+        );
+
+        let lambda = SignedBigInt::from(BigUint::from(G1Config::LAMBDA));
+        let modulus = SignedBigInt::from(BigUint::from(Fr::MODULUS));
+        for _ in 0..20 {
+            let k = Fr::rand(&mut rng);
+            let (k1, k2) = G1Config::scalar_decomposition(&k);
+            assert!(k1.bits() <= 128 && k2.bits() <= 128);
+            let k = SignedBigInt::from(BigUint::from(k));
+            assert!(((k1 + &lambda * k2 - k) % &modulus).is_zero());
+        }
+    }
+
+    #[test]
+    fn test_scalar_mul_le_glv() {
+        let mut rng = ark_std::test_rng();
+        for point in [G1Projective::rand(&mut rng), G1Projective::zero()] {
+            for num_bits in [8, 255, 256, 300] {
+                let mut bits = Fr::rand(&mut rng).into_bigint().to_bits_le();
+                bits.resize(num_bits, true);
+                let scalar = Fr::from(
+                    bits.iter()
+                        .rev()
+                        .fold(BigUint::zero(), |acc, b| (acc << 1) + *b as u8),
+                );
+                let expected = point * scalar;
+
+                let cs = ConstraintSystem::<Fq>::new_ref();
+                let point_var = G1Var::new_witness(cs.clone(), || Ok(point)).unwrap();
+                let bits = bits
+                    .iter()
+                    .map(|b| Boolean::new_witness(cs.clone(), || Ok(*b)).unwrap())
+                    .collect::<Vec<_>>();
+
+                let num_constraints = cs.num_constraints();
+                let result = point_var.scalar_mul_le_glv(bits.iter()).unwrap();
+                let glv_cost = cs.num_constraints() - num_constraints;
+                assert_eq!(result.value().unwrap(), expected);
+
+                let result = G1Var::constant(point)
+                    .scalar_mul_le_glv(bits.iter())
+                    .unwrap();
+                assert_eq!(result.value().unwrap(), expected);
+                assert!(cs.is_satisfied().unwrap());
+
+                if !point.is_zero() && num_bits >= 255 {
+                    let num_constraints = cs.num_constraints();
+                    let result = point_var.scalar_mul_le(bits.iter()).unwrap();
+                    let double_and_add_cost = cs.num_constraints() - num_constraints;
+                    assert_eq!(result.value().unwrap(), expected);
+                    // For 255 bits, this is 1452 rather than 2334 constraints.
+                    assert!(8 * glv_cost < 5 * double_and_add_cost);
+                }
+            }
+        }
+    }
+
+    impl GLVConfig for ark_bls12_377::g1::Config {
+        const ENDO_COEFF: ark_bls12_377::Fq = ark_ff::MontFp!("80949648264912719408558363140637477264845294720710499478137287262712535938301461879813459410945");
+
+        const LAMBDA: ark_bls12_377::Fr = ark_ff::MontFp!("91893752504881257701523279626832445440");
+
+        const SCALAR_DECOMP_COEFFS: [(bool, <ark_bls12_377::Fr as PrimeField>::BigInt); 4] = [
+            (true, ark_ff::BigInt!("1")),
+            (
+                true,
+                ark_ff::BigInt!("91893752504881257701523279626832445441"),
+            ),
+            (
+                true,
+                ark_ff::BigInt!("91893752504881257701523279626832445440"),
+            ),
+            (false, ark_ff::BigInt!("1")),
+        ];
+    }
+
+    impl GLVConfig for ark_bls12_381::g1::Config {
+        const ENDO_COEFF: ark_bls12_381::Fq = ark_ff::MontFp!("4002409555221667392624310435006688643935503118305586438271171395842971157480381377015405980053539358417135540939436");
+
+        const LAMBDA: ark_bls12_381::Fr =
+            ark_ff::MontFp!("228988810152649578064853576960394133503");
+
+        const SCALAR_DECOMP_COEFFS: [(bool, <ark_bls12_381::Fr as PrimeField>::BigInt); 4] = [
+            (true, ark_ff::BigInt!("1")),
+            (
+                true,
+                ark_ff::BigInt!("228988810152649578064853576960394133504"),
+            ),
+            (
+                true,
+                ark_ff::BigInt!("228988810152649578064853576960394133503"),
+            ),
+            (false, ark_ff::BigInt!("1")),
+        ];
+    }
+
+    impl GLVConfig for ark_bn254::g1::Config {
+        const ENDO_COEFF: ark_bn254::Fq =
+            ark_ff::MontFp!("2203960485148121921418603742825762020974279258880205651966");
+
+        const LAMBDA: ark_bn254::Fr =
+            ark_ff::MontFp!("4407920970296243842393367215006156084916469457145843978461");
+
+        const SCALAR_DECOMP_COEFFS: [(bool, <ark_bn254::Fr as PrimeField>::BigInt); 4] = [
+            (true, ark_ff::BigInt!("9931322734385697763")),
+            (
+                false,
+                ark_ff::BigInt!("147946756881789319000765030803803410728"),
+            ),
+            (
+                true,
+                ark_ff::BigInt!("147946756881789319010696353538189108491"),
+            ),
+            (true, ark_ff::BigInt!("9931322734385697763")),
+        ];
+    }
+
+    impl GLVConfig for ark_pallas::PallasConfig {
+        const ENDO_COEFF: ark_pallas::Fq = ark_ff::MontFp!(
+            "8503465768106391777493614032514048814691664078728891710322960303815233784505"
+        );
+
+        const LAMBDA: ark_pallas::Fr = ark_ff::MontFp!(
+            "2942865608506852014473558576493638302197734138389222805617480874486368177743"
+        );
+
+        const SCALAR_DECOMP_COEFFS: [(bool, <ark_pallas::Fr as PrimeField>::BigInt); 4] = [
+            (
+                true,
+                ark_ff::BigInt!("98231058071186745657228807397848383489"),
+            ),
+            (
+                false,
+                ark_ff::BigInt!("98231058071100081932162823354453065728"),
+            ),
+            (
+                true,
+                ark_ff::BigInt!("98231058071100081932162823354453065728"),
+            ),
+            (
+                true,
+                ark_ff::BigInt!("196462116142286827589391630752301449217"),
+            ),
+        ];
+    }
+
+    impl GLVConfig for ark_secp256k1::Config {
+        const ENDO_COEFF: ark_secp256k1::Fq = ark_ff::MontFp!(
+            "60197513588986302554485582024885075108884032450952339817679072026166228089408"
+        );
+
+        const LAMBDA: ark_secp256k1::Fr = ark_ff::MontFp!(
+            "78074008874160198520644763525212887401909906723592317393988542598630163514318"
+        );
+
+        const SCALAR_DECOMP_COEFFS: [(bool, <ark_secp256k1::Fr as PrimeField>::BigInt); 4] = [
+            (
+                true,
+                ark_ff::BigInt!("303414439467246543595250775667605759171"),
+            ),
+            (
+                false,
+                ark_ff::BigInt!("64502973549206556628585045361533709077"),
+            ),
+            (
+                true,
+                ark_ff::BigInt!("64502973549206556628585045361533709077"),
+            ),
+            (
+                true,
+                ark_ff::BigInt!("367917413016453100223835821029139468248"),
+            ),
+        ];
+    }
+
+    /// Checks the GLV parameters of `P` and `scalar_mul_le_glv` against the native
+    /// computation, and returns the costs of `scalar_mul_le_glv` and of
+    /// `scalar_mul_le` for a full-length scalar.
+    fn check_glv<P>() -> (usize, usize)
+    where
+        P: GLVConfig,
+        P::BaseField: PrimeField,
+    {
+        let mut rng = ark_std::test_rng();
+        let generator = P::GENERATOR;
+        assert_eq!(
+            P::endomorphism_affine(&generator),
+            (generator * P::LAMBDA).into_affine()
+        );
+
+        let lambda = SignedBigInt::from(Into::<BigUint>::into(P::LAMBDA));
+        let modulus = SignedBigInt::from(Into::<BigUint>::into(P::ScalarField::MODULUS));
+        let half_bits = (P::ScalarField::MODULUS_BIT_SIZE as u64 + 1) / 2 + 1;
+        for _ in 0..20 {
+            let k = P::ScalarField::rand(&mut rng);
+            let (k1, k2) = P::scalar_decomposition(&k);
+            assert!(k1.bits() <= half_bits && k2.bits() <= half_bits);
+            let k = SignedBigInt::from(Into::<BigUint>::into(k));
+            assert!(((k1 + &lambda * k2 - k) % &modulus).is_zero());
+        }
+
+        let mut costs = (0, 0);
+        for point in [
+            ShortWeierstrassProjective::<P>::rand(&mut rng),
+            ShortWeierstrassProjective::<P>::zero(),
+        ] {
+            let scalar = P::ScalarField::rand(&mut rng);
+            let expected = point * scalar;
+
+            let cs = ConstraintSystem::<P::BaseField>::new_ref();
+            let point_var =
+                ProjectiveVar::<P, FpVar<P::BaseField>>::new_witness(cs.clone(), || Ok(point))
+                    .unwrap();
+            let bits = scalar
+                .into_bigint()
+                .to_bits_le()
+                .into_iter()
+                .take(P::ScalarField::MODULUS_BIT_SIZE as usize)
+                .map(|b| Boolean::new_witness(cs.clone(), || Ok(b)).unwrap())
+                .collect::<Vec<_>>();
+
+            let num_constraints = cs.num_constraints();
+            let result = point_var.scalar_mul_le_glv(bits.iter()).unwrap();
+            let glv_cost = cs.num_constraints() - num_constraints;
+            assert_eq!(result.value().unwrap(), expected);
+
+            let num_constraints = cs.num_constraints();
+            let result = point_var.scalar_mul_le(bits.iter()).unwrap();
+            let double_and_add_cost = cs.num_constraints() - num_constraints;
+            assert_eq!(result.value().unwrap(), expected);
+            assert!(cs.is_satisfied().unwrap());
+
+            if !point.is_zero() {
+                costs = (glv_cost, double_and_add_cost);
+            }
+        }
+        costs
+    }
+
+    #[test]
+    fn bls12_377_glv_test() {
+        let (glv, double_and_add) = check_glv::<ark_bls12_377::g1::Config>();
+        // This is 1441 rather than 2316 constraints.
+        assert!(8 * glv < 5 * double_and_add);
+    }
+
+    #[test]
+    fn bls12_381_glv_test() {
+        let (glv, double_and_add) = check_glv::<ark_bls12_381::g1::Config>();
+        // This is 1452 rather than 2334 constraints.
+        assert!(8 * glv < 5 * double_and_add);
+    }
+
+    #[test]
+    fn bn254_glv_test() {
+        let (glv, double_and_add) = check_glv::<ark_bn254::g1::Config>();
+        // The scalar field is barely smaller than the base field, so the
+        // decomposition is checked with emulated arithmetic, which reduces the
+        // savings: this is 1768 rather than 2325 constraints.
+        assert!(5 * glv < 4 * double_and_add);
+    }
+
+    #[test]
+    fn pallas_glv_test() {
+        let (glv, double_and_add) = check_glv::<ark_pallas::PallasConfig>();
+        // As for BN254, but the scalar field is larger than the base field: this
+        // is 1838 rather than 2334 constraints.
+        assert!(5 * glv < 4 * double_and_add);
+    }
+
+    #[test]
+    fn secp256k1_glv_test() {
+        let (glv, double_and_add) = check_glv::<ark_secp256k1::Config>();
+        // As for BN254: this is 1856 rather than 2343 constraints.
+        assert!(5 * glv < 4 * double_and_add);
+    }
+}
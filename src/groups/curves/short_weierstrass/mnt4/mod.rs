@@ -2,11 +2,11 @@ use ark_ec::mnt4::{
     g2::{AteAdditionCoefficients, AteDoubleCoefficients},
     G1Prepared, G2Prepared, MNT4Config,
 };
-use ark_ff::Field;
-use ark_relations::r1cs::{Namespace, SynthesisError};
+use ark_ff::{Field, PrimeField};
+use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
 
 use crate::{
-    fields::{fp::FpVar, fp2::Fp2Var, FieldVar},
+    fields::{fp::FpVar, fp2::Fp2VarWithBase, FieldOpsBounds, FieldVar},
     groups::curves::short_weierstrass::ProjectiveVar,
     pairing::mnt4::PairingVar,
     prelude::*,
@@ -15,30 +15,50 @@ use crate::{
 use core::borrow::Borrow;
 
 /// Represents a projective point in G1.
-pub type G1Var<P> = ProjectiveVar<<P as MNT4Config>::G1Config, FpVar<<P as MNT4Config>::Fp>>;
+///
+/// The coordinates are variables of type `F` over the constraint field
+/// `ConstraintF`. By default, these are `FpVar`s over `P::Fp`; to work over
+/// another constraint field, `F` can be a `NonNativeFieldVar`.
+pub type G1Var<P, F = BaseFieldVar<P>, ConstraintF = <P as MNT4Config>::Fp> =
+    ProjectiveVar<<P as MNT4Config>::G1Config, F, ConstraintF>;
 
 /// Represents a projective point in G2.
-pub type G2Var<P> = ProjectiveVar<<P as MNT4Config>::G2Config, Fp2G<P>>;
+pub type G2Var<P, F = BaseFieldVar<P>, ConstraintF = <P as MNT4Config>::Fp> =
+    ProjectiveVar<<P as MNT4Config>::G2Config, Fp2G<P, F, ConstraintF>, ConstraintF>;
+
+type BaseFieldVar<P> = FpVar<<P as MNT4Config>::Fp>;
 
 /// Represents the cached precomputation that can be performed on a G1 element
 /// which enables speeding up pairing computation.
 #[derive(Derivative)]
 #[derivative(Clone(bound = "P: MNT4Config"), Debug(bound = "P: MNT4Config"))]
-pub struct G1PreparedVar<P: MNT4Config> {
+pub struct G1PreparedVar<
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as MNT4Config>::Fp,
+> where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
     #[doc(hidden)]
-    pub x: FpVar<P::Fp>,
+    pub x: F,
     #[doc(hidden)]
-    pub y: FpVar<P::Fp>,
+    pub y: F,
     #[doc(hidden)]
-    pub x_twist: Fp2Var<P::Fp2Config>,
+    pub x_twist: Fp2G<P, F, ConstraintF>,
     #[doc(hidden)]
-    pub y_twist: Fp2Var<P::Fp2Config>,
+    pub y_twist: Fp2G<P, F, ConstraintF>,
 }
 
-impl<P: MNT4Config> AllocVar<G1Prepared<P>, P::Fp> for G1PreparedVar<P> {
+impl<P, F, ConstraintF> AllocVar<G1Prepared<P>, ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[tracing::instrument(target = "r1cs", skip(cs, f))]
     fn new_variable<T: Borrow<G1Prepared<P>>>(
-        cs: impl Into<Namespace<P::Fp>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -47,14 +67,14 @@ impl<P: MNT4Config> AllocVar<G1Prepared<P>, P::Fp> for G1PreparedVar<P> {
 
         let g1_prep = f().map(|b| *b.borrow());
 
-        let x = FpVar::new_variable(ark_relations::ns!(cs, "x"), || g1_prep.map(|g| g.x), mode)?;
-        let y = FpVar::new_variable(ark_relations::ns!(cs, "y"), || g1_prep.map(|g| g.y), mode)?;
-        let x_twist = Fp2Var::new_variable(
+        let x = F::new_variable(ark_relations::ns!(cs, "x"), || g1_prep.map(|g| g.x), mode)?;
+        let y = F::new_variable(ark_relations::ns!(cs, "y"), || g1_prep.map(|g| g.y), mode)?;
+        let x_twist = Fp2VarWithBase::new_variable(
             ark_relations::ns!(cs, "x_twist"),
             || g1_prep.map(|g| g.x_twist),
             mode,
         )?;
-        let y_twist = Fp2Var::new_variable(
+        let y_twist = Fp2VarWithBase::new_variable(
             ark_relations::ns!(cs, "y_twist"),
             || g1_prep.map(|g| g.y_twist),
             mode,
@@ -68,7 +88,13 @@ impl<P: MNT4Config> AllocVar<G1Prepared<P>, P::Fp> for G1PreparedVar<P> {
     }
 }
 
-impl<P: MNT4Config> G1PreparedVar<P> {
+impl<P, F, ConstraintF> G1PreparedVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     /// Returns the value assigned to `self` in the underlying constraint
     /// system.
     pub fn value(&self) -> Result<G1Prepared<P>, SynthesisError> {
@@ -88,10 +114,10 @@ impl<P: MNT4Config> G1PreparedVar<P> {
 
     /// Constructs `Self` from a `G1Var`.
     #[tracing::instrument(target = "r1cs")]
-    pub fn from_group_var(q: &G1Var<P>) -> Result<Self, SynthesisError> {
+    pub fn from_group_var(q: &G1Var<P, F, ConstraintF>) -> Result<Self, SynthesisError> {
         let q = q.to_affine()?;
-        let x_twist = Fp2Var::new(&q.x * P::TWIST.c0, &q.x * P::TWIST.c1);
-        let y_twist = Fp2Var::new(&q.y * P::TWIST.c0, &q.y * P::TWIST.c1);
+        let x_twist = Fp2VarWithBase::new(&q.x * P::TWIST.c0, &q.x * P::TWIST.c1);
+        let y_twist = Fp2VarWithBase::new(&q.y * P::TWIST.c0, &q.y * P::TWIST.c1);
         Ok(G1PreparedVar {
             x: q.x,
             y: q.y,
@@ -101,10 +127,16 @@ impl<P: MNT4Config> G1PreparedVar<P> {
     }
 }
 
-impl<P: MNT4Config> ToBytesGadget<P::Fp> for G1PreparedVar<P> {
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[inline]
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut x = self.x.to_bytes()?;
         let mut y = self.y.to_bytes()?;
         let mut x_twist = self.x_twist.to_bytes()?;
@@ -117,7 +149,7 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for G1PreparedVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut x = self.x.to_non_unique_bytes()?;
         let mut y = self.y.to_non_unique_bytes()?;
         let mut x_twist = self.x_twist.to_non_unique_bytes()?;
@@ -130,31 +162,44 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for G1PreparedVar<P> {
     }
 }
 
-type Fp2G<P> = Fp2Var<<P as MNT4Config>::Fp2Config>;
+type Fp2G<P, F = BaseFieldVar<P>, ConstraintF = <P as MNT4Config>::Fp> =
+    Fp2VarWithBase<<P as MNT4Config>::Fp2Config, F, ConstraintF>;
 
 /// Represents the cached precomputation that can be performed on a G2 element
 /// which enables speeding up pairing computation.
 #[derive(Derivative)]
 #[derivative(Clone(bound = "P: MNT4Config"), Debug(bound = "P: MNT4Config"))]
-pub struct G2PreparedVar<P: MNT4Config> {
+pub struct G2PreparedVar<
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as MNT4Config>::Fp,
+> where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
     #[doc(hidden)]
-    pub x: Fp2Var<P::Fp2Config>,
+    pub x: Fp2G<P, F, ConstraintF>,
     #[doc(hidden)]
-    pub y: Fp2Var<P::Fp2Config>,
+    pub y: Fp2G<P, F, ConstraintF>,
     #[doc(hidden)]
-    pub x_over_twist: Fp2Var<P::Fp2Config>,
+    pub x_over_twist: Fp2G<P, F, ConstraintF>,
     #[doc(hidden)]
-    pub y_over_twist: Fp2Var<P::Fp2Config>,
+    pub y_over_twist: Fp2G<P, F, ConstraintF>,
     #[doc(hidden)]
-    pub double_coefficients: Vec<AteDoubleCoefficientsVar<P>>,
+    pub double_coefficients: Vec<AteDoubleCoefficientsVar<P, F, ConstraintF>>,
     #[doc(hidden)]
-    pub addition_coefficients: Vec<AteAdditionCoefficientsVar<P>>,
+    pub addition_coefficients: Vec<AteAdditionCoefficientsVar<P, F, ConstraintF>>,
 }
 
-impl<P: MNT4Config> AllocVar<G2Prepared<P>, P::Fp> for G2PreparedVar<P> {
+impl<P, F, ConstraintF> AllocVar<G2Prepared<P>, ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[tracing::instrument(target = "r1cs", skip(cs, f))]
     fn new_variable<T: Borrow<G2Prepared<P>>>(
-        cs: impl Into<Namespace<P::Fp>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -164,14 +209,16 @@ impl<P: MNT4Config> AllocVar<G2Prepared<P>, P::Fp> for G2PreparedVar<P> {
         let g2_prep = f().map(|b| b.borrow().clone());
         let g2 = g2_prep.as_ref().map_err(|e| *e);
 
-        let x = Fp2Var::new_variable(ark_relations::ns!(cs, "x"), || g2.map(|g| g.x), mode)?;
-        let y = Fp2Var::new_variable(ark_relations::ns!(cs, "y"), || g2.map(|g| g.y), mode)?;
-        let x_over_twist = Fp2Var::new_variable(
+        let x =
+            Fp2VarWithBase::new_variable(ark_relations::ns!(cs, "x"), || g2.map(|g| g.x), mode)?;
+        let y =
+            Fp2VarWithBase::new_variable(ark_relations::ns!(cs, "y"), || g2.map(|g| g.y), mode)?;
+        let x_over_twist = Fp2VarWithBase::new_variable(
             ark_relations::ns!(cs, "x_over_twist"),
             || g2.map(|g| g.x_over_twist),
             mode,
         )?;
-        let y_over_twist = Fp2Var::new_variable(
+        let y_over_twist = Fp2VarWithBase::new_variable(
             ark_relations::ns!(cs, "y_over_twist"),
             || g2.map(|g| g.y_over_twist),
             mode,
@@ -197,10 +244,16 @@ impl<P: MNT4Config> AllocVar<G2Prepared<P>, P::Fp> for G2PreparedVar<P> {
     }
 }
 
-impl<P: MNT4Config> ToBytesGadget<P::Fp> for G2PreparedVar<P> {
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[inline]
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut x = self.x.to_bytes()?;
         let mut y = self.y.to_bytes()?;
         let mut x_over_twist = self.x_over_twist.to_bytes()?;
@@ -220,7 +273,7 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for G2PreparedVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut x = self.x.to_non_unique_bytes()?;
         let mut y = self.y.to_non_unique_bytes()?;
         let mut x_over_twist = self.x_over_twist.to_non_unique_bytes()?;
@@ -240,7 +293,21 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for G2PreparedVar<P> {
     }
 }
 
-impl<P: MNT4Config> G2PreparedVar<P> {
+impl<P, F, ConstraintF> G2PreparedVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    /// Constructs a constant `Self` from `q`, whose Miller loop coefficients
+    /// are precomputed natively. Unlike [`Self::from_group_var`], this requires
+    /// no constraints, and the Miller loop needs none to evaluate its lines.
+    pub fn constant(q: G2Prepared<P>) -> Self {
+        // Allocating a constant does not fail.
+        Self::new_constant(ConstraintSystemRef::None, q).unwrap()
+    }
+
     /// Returns the value assigned to `self` in the underlying constraint
     /// system.
     pub fn value(&self) -> Result<G2Prepared<P>, SynthesisError> {
@@ -270,7 +337,7 @@ impl<P: MNT4Config> G2PreparedVar<P> {
 
     /// Constructs `Self` from a `G2Var`.
     #[tracing::instrument(target = "r1cs")]
-    pub fn from_group_var(q: &G2Var<P>) -> Result<Self, SynthesisError> {
+    pub fn from_group_var(q: &G2Var<P, F, ConstraintF>) -> Result<Self, SynthesisError> {
         let twist_inv = P::TWIST.inverse().unwrap();
         let q = q.to_affine()?;
 
@@ -286,12 +353,13 @@ impl<P: MNT4Config> G2PreparedVar<P> {
         let mut r = G2ProjectiveExtendedVar {
             x: q.x.clone(),
             y: q.y.clone(),
-            z: Fp2G::<P>::one(),
-            t: Fp2G::<P>::one(),
+            z: Fp2G::<P, F, ConstraintF>::one(),
+            t: Fp2G::<P, F, ConstraintF>::one(),
         };
 
         for bit in P::ATE_LOOP_COUNT.iter().skip(1) {
-            let (r2, coeff) = PairingVar::<P>::doubling_step_for_flipped_miller_loop(&r)?;
+            let (r2, coeff) =
+                PairingVar::<P, F, ConstraintF>::doubling_step_for_flipped_miller_loop(&r)?;
             g2p.double_coefficients.push(coeff);
             r = r2;
 
@@ -300,13 +368,13 @@ impl<P: MNT4Config> G2PreparedVar<P> {
             match bit {
                 1 => {
                     (r_temp, add_coeff) =
-                        PairingVar::<P>::mixed_addition_step_for_flipped_miller_loop(
+                        PairingVar::<P, F, ConstraintF>::mixed_addition_step_for_flipped_miller_loop(
                             &q.x, &q.y, &r,
                         )?;
                 },
                 -1 => {
                     (r_temp, add_coeff) =
-                        PairingVar::<P>::mixed_addition_step_for_flipped_miller_loop(
+                        PairingVar::<P, F, ConstraintF>::mixed_addition_step_for_flipped_miller_loop(
                             &q.x,
                             &q.y.negate()?,
                             &r,
@@ -326,11 +394,12 @@ impl<P: MNT4Config> G2PreparedVar<P> {
             let minus_r_affine_x = &r.x * &rz2_inv;
             let minus_r_affine_y = r.y.negate()? * &rz3_inv;
 
-            let add_result = PairingVar::<P>::mixed_addition_step_for_flipped_miller_loop(
-                &minus_r_affine_x,
-                &minus_r_affine_y,
-                &r,
-            )?;
+            let add_result =
+                PairingVar::<P, F, ConstraintF>::mixed_addition_step_for_flipped_miller_loop(
+                    &minus_r_affine_x,
+                    &minus_r_affine_y,
+                    &r,
+                )?;
             g2p.addition_coefficients.push(add_result.1);
         }
 
@@ -341,17 +410,30 @@ impl<P: MNT4Config> G2PreparedVar<P> {
 #[doc(hidden)]
 #[derive(Derivative)]
 #[derivative(Clone(bound = "P: MNT4Config"), Debug(bound = "P: MNT4Config"))]
-pub struct AteDoubleCoefficientsVar<P: MNT4Config> {
-    pub c_h: Fp2Var<P::Fp2Config>,
-    pub c_4c: Fp2Var<P::Fp2Config>,
-    pub c_j: Fp2Var<P::Fp2Config>,
-    pub c_l: Fp2Var<P::Fp2Config>,
+pub struct AteDoubleCoefficientsVar<
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as MNT4Config>::Fp,
+> where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
+    pub c_h: Fp2G<P, F, ConstraintF>,
+    pub c_4c: Fp2G<P, F, ConstraintF>,
+    pub c_j: Fp2G<P, F, ConstraintF>,
+    pub c_l: Fp2G<P, F, ConstraintF>,
 }
 
-impl<P: MNT4Config> AllocVar<AteDoubleCoefficients<P>, P::Fp> for AteDoubleCoefficientsVar<P> {
+impl<P, F, ConstraintF> AllocVar<AteDoubleCoefficients<P>, ConstraintF>
+    for AteDoubleCoefficientsVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[tracing::instrument(target = "r1cs", skip(cs, f))]
     fn new_variable<T: Borrow<AteDoubleCoefficients<P>>>(
-        cs: impl Into<Namespace<P::Fp>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -361,11 +443,17 @@ impl<P: MNT4Config> AllocVar<AteDoubleCoefficients<P>, P::Fp> for AteDoubleCoeff
         let c_prep = f().map(|c| c.borrow().clone());
         let c = c_prep.as_ref().map_err(|e| *e);
 
-        let c_h = Fp2Var::new_variable(ark_relations::ns!(cs, "c_h"), || c.map(|c| c.c_h), mode)?;
-        let c_4c =
-            Fp2Var::new_variable(ark_relations::ns!(cs, "c_4c"), || c.map(|c| c.c_4c), mode)?;
-        let c_j = Fp2Var::new_variable(ark_relations::ns!(cs, "c_j"), || c.map(|c| c.c_j), mode)?;
-        let c_l = Fp2Var::new_variable(ark_relations::ns!(cs, "c_l"), || c.map(|c| c.c_l), mode)?;
+        let c_h =
+            Fp2VarWithBase::new_variable(ark_relations::ns!(cs, "c_h"), || c.map(|c| c.c_h), mode)?;
+        let c_4c = Fp2VarWithBase::new_variable(
+            ark_relations::ns!(cs, "c_4c"),
+            || c.map(|c| c.c_4c),
+            mode,
+        )?;
+        let c_j =
+            Fp2VarWithBase::new_variable(ark_relations::ns!(cs, "c_j"), || c.map(|c| c.c_j), mode)?;
+        let c_l =
+            Fp2VarWithBase::new_variable(ark_relations::ns!(cs, "c_l"), || c.map(|c| c.c_l), mode)?;
         Ok(Self {
             c_h,
             c_4c,
@@ -375,10 +463,16 @@ impl<P: MNT4Config> AllocVar<AteDoubleCoefficients<P>, P::Fp> for AteDoubleCoeff
     }
 }
 
-impl<P: MNT4Config> ToBytesGadget<P::Fp> for AteDoubleCoefficientsVar<P> {
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for AteDoubleCoefficientsVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[inline]
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut c_h = self.c_h.to_bytes()?;
         let mut c_4c = self.c_4c.to_bytes()?;
         let mut c_j = self.c_j.to_bytes()?;
@@ -391,7 +485,7 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for AteDoubleCoefficientsVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut c_h = self.c_h.to_non_unique_bytes()?;
         let mut c_4c = self.c_4c.to_non_unique_bytes()?;
         let mut c_j = self.c_j.to_non_unique_bytes()?;
@@ -404,7 +498,13 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for AteDoubleCoefficientsVar<P> {
     }
 }
 
-impl<P: MNT4Config> AteDoubleCoefficientsVar<P> {
+impl<P, F, ConstraintF> AteDoubleCoefficientsVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     /// Returns the value assigned to `self` in the underlying constraint
     /// system.
     pub fn value(&self) -> Result<AteDoubleCoefficients<P>, SynthesisError> {
@@ -426,15 +526,28 @@ impl<P: MNT4Config> AteDoubleCoefficientsVar<P> {
 #[doc(hidden)]
 #[derive(Derivative)]
 #[derivative(Clone(bound = "P: MNT4Config"), Debug(bound = "P: MNT4Config"))]
-pub struct AteAdditionCoefficientsVar<P: MNT4Config> {
-    pub c_l1: Fp2Var<P::Fp2Config>,
-    pub c_rz: Fp2Var<P::Fp2Config>,
+pub struct AteAdditionCoefficientsVar<
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as MNT4Config>::Fp,
+> where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
+    pub c_l1: Fp2G<P, F, ConstraintF>,
+    pub c_rz: Fp2G<P, F, ConstraintF>,
 }
 
-impl<P: MNT4Config> AllocVar<AteAdditionCoefficients<P>, P::Fp> for AteAdditionCoefficientsVar<P> {
+impl<P, F, ConstraintF> AllocVar<AteAdditionCoefficients<P>, ConstraintF>
+    for AteAdditionCoefficientsVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[tracing::instrument(target = "r1cs", skip(cs, f))]
     fn new_variable<T: Borrow<AteAdditionCoefficients<P>>>(
-        cs: impl Into<Namespace<P::Fp>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -444,18 +557,30 @@ impl<P: MNT4Config> AllocVar<AteAdditionCoefficients<P>, P::Fp> for AteAdditionC
         let c_prep = f().map(|c| c.borrow().clone());
         let c = c_prep.as_ref().map_err(|e| *e);
 
-        let c_l1 =
-            Fp2Var::new_variable(ark_relations::ns!(cs, "c_l1"), || c.map(|c| c.c_l1), mode)?;
-        let c_rz =
-            Fp2Var::new_variable(ark_relations::ns!(cs, "c_rz"), || c.map(|c| c.c_rz), mode)?;
+        let c_l1 = Fp2VarWithBase::new_variable(
+            ark_relations::ns!(cs, "c_l1"),
+            || c.map(|c| c.c_l1),
+            mode,
+        )?;
+        let c_rz = Fp2VarWithBase::new_variable(
+            ark_relations::ns!(cs, "c_rz"),
+            || c.map(|c| c.c_rz),
+            mode,
+        )?;
         Ok(Self { c_l1, c_rz })
     }
 }
 
-impl<P: MNT4Config> ToBytesGadget<P::Fp> for AteAdditionCoefficientsVar<P> {
+impl<P, F, ConstraintF> ToBytesGadget<ConstraintF> for AteAdditionCoefficientsVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[inline]
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut c_l1 = self.c_l1.to_bytes()?;
         let mut c_rz = self.c_rz.to_bytes()?;
 
@@ -464,7 +589,7 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for AteAdditionCoefficientsVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<P::Fp>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let mut c_l1 = self.c_l1.to_non_unique_bytes()?;
         let mut c_rz = self.c_rz.to_non_unique_bytes()?;
 
@@ -473,7 +598,13 @@ impl<P: MNT4Config> ToBytesGadget<P::Fp> for AteAdditionCoefficientsVar<P> {
     }
 }
 
-impl<P: MNT4Config> AteAdditionCoefficientsVar<P> {
+impl<P, F, ConstraintF> AteAdditionCoefficientsVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     /// Returns the value assigned to `self` in the underlying constraint
     /// system.
     pub fn value(&self) -> Result<AteAdditionCoefficients<P>, SynthesisError> {
@@ -483,9 +614,15 @@ impl<P: MNT4Config> AteAdditionCoefficientsVar<P> {
 }
 
 #[doc(hidden)]
-pub struct G2ProjectiveExtendedVar<P: MNT4Config> {
-    pub x: Fp2Var<P::Fp2Config>,
-    pub y: Fp2Var<P::Fp2Config>,
-    pub z: Fp2Var<P::Fp2Config>,
-    pub t: Fp2Var<P::Fp2Config>,
+pub struct G2ProjectiveExtendedVar<
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF> = BaseFieldVar<P>,
+    ConstraintF: PrimeField = <P as MNT4Config>::Fp,
+> where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+{
+    pub x: Fp2G<P, F, ConstraintF>,
+    pub y: Fp2G<P, F, ConstraintF>,
+    pub z: Fp2G<P, F, ConstraintF>,
+    pub t: Fp2G<P, F, ConstraintF>,
 }
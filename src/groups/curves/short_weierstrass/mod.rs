@@ -2,19 +2,38 @@ use ark_ec::{
     short_weierstrass::{
         Affine as SWAffine, Projective as SWProjective, SWCurveConfig as SWModelParameters,
     },
-    AffineRepr, CurveGroup,
+    AffineRepr, CurveConfig, CurveGroup,
 };
 use ark_ff::{BigInteger, BitIteratorBE, Field, One, PrimeField, Zero};
 use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
-use ark_std::{borrow::Borrow, marker::PhantomData, ops::Mul};
+use ark_std::{borrow::Borrow, fmt::Debug, marker::PhantomData, ops::Mul};
 use non_zero_affine::NonZeroAffineVar;
 
-use crate::{fields::fp::FpVar, prelude::*, ToConstraintFieldGadget, Vec};
+use crate::{
+    fields::fp::FpVar,
+    groups::{
+        compression::{
+            field_from_bytes_with_flags, field_to_bytes_with_flags, sw_compressed_layout,
+            SWCompressedLayout,
+        },
+        mul_by_cofactor, power_of_two_multiples, FixedBaseTable, PointCompressionGadget,
+    },
+    prelude::*,
+    ToConstraintFieldGadget, Vec,
+};
 
 /// This module provides a generic implementation of G1 and G2 for
 /// the [\[BLS12]\](<https://eprint.iacr.org/2002/088.pdf>) family of bilinear groups.
 pub mod bls12;
 
+/// This module provides a generic implementation of G1 and G2 for
+/// the [\[BN]\](<https://eprint.iacr.org/2005/133.pdf>) family of bilinear groups.
+pub mod bn;
+
+/// This module provides a generic implementation of G1 and G2 for
+/// the [\[BW6]\](<https://eprint.iacr.org/2020/351.pdf>) family of bilinear groups.
+pub mod bw6;
+
 /// This module provides a generic implementation of G1 and G2 for
 /// the [\[MNT4]\](<https://citeseerx.ist.psu.edu/viewdoc/download?doi=10.1.1.20.8113&rep=rep1&type=pdf>)
 ///  family of bilinear groups.
@@ -24,6 +43,10 @@ pub mod mnt4;
 ///  family of bilinear groups.
 pub mod mnt6;
 
+/// This module provides GLV endomorphism-accelerated scalar multiplication
+/// for [ProjectiveVar] on curves that implement [glv::GLVConfig].
+pub mod glv;
+
 /// This module provides a generic implementation of elliptic curve operations
 /// for points on short-weierstrass curves in affine coordinates that **are
 /// not** equal to zero.
@@ -36,12 +59,17 @@ pub mod non_zero_affine;
 /// An implementation of arithmetic for Short Weierstrass curves that relies on
 /// the complete formulae derived in the paper of
 /// [[Renes, Costello, Batina 2015]](<https://eprint.iacr.org/2015/1060>).
+///
+/// The parameter `H` selects how [`CurveVar::clear_cofactor`] maps a point
+/// into the prime-order subgroup; see [`ClearCofactorConfig`].
 #[derive(Derivative)]
 #[derivative(Debug, Clone)]
 #[must_use]
 pub struct ProjectiveVar<
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField = <<P as CurveConfig>::BaseField as Field>::BasePrimeField,
+    H: ClearCofactorConfig<P> = MulByCofactor,
 > where
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
@@ -52,16 +80,44 @@ pub struct ProjectiveVar<
     /// The z-coordinate.
     pub z: F,
     #[derivative(Debug = "ignore")]
-    _params: PhantomData<P>,
+    _params: PhantomData<(P, ConstraintF, H)>,
 }
 
+/// Selects how [`CurveVar::clear_cofactor`] maps a [`ProjectiveVar`] into the
+/// prime-order subgroup of the curve `P`.
+///
+/// The default method multiplies by the cofactor of `P`. Curves with a cheaper
+/// effective cofactor override it, and use the overriding type as the last
+/// parameter of [`ProjectiveVar`], as is done by `G1Var` and `G2Var` of
+/// [`bls12`].
+pub trait ClearCofactorConfig<P: SWModelParameters>: 'static + Sized + Clone + Debug {
+    /// Maps `p` into the prime-order subgroup.
+    fn clear_cofactor<F, ConstraintF>(
+        p: &ProjectiveVar<P, F, ConstraintF, Self>,
+    ) -> Result<ProjectiveVar<P, F, ConstraintF, Self>, SynthesisError>
+    where
+        F: FieldVar<P::BaseField, ConstraintF>,
+        ConstraintF: PrimeField,
+        for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+    {
+        mul_by_cofactor(p)
+    }
+}
+
+/// The default [`ClearCofactorConfig`], which multiplies by the cofactor.
+#[derive(Clone, Copy, Debug)]
+pub struct MulByCofactor;
+
+impl<P: SWModelParameters> ClearCofactorConfig<P> for MulByCofactor {}
+
 /// An affine representation of a curve point.
 #[derive(Derivative)]
 #[derivative(Debug, Clone)]
 #[must_use]
 pub struct AffineVar<
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField = <<P as CurveConfig>::BaseField as Field>::BasePrimeField,
 > where
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
@@ -70,18 +126,19 @@ pub struct AffineVar<
     /// The y-coordinate.
     pub y: F,
     /// Is `self` the point at infinity.
-    pub infinity: Boolean<<P::BaseField as Field>::BasePrimeField>,
+    pub infinity: Boolean<ConstraintF>,
     #[derivative(Debug = "ignore")]
-    _params: PhantomData<P>,
+    _params: PhantomData<(P, ConstraintF)>,
 }
 
-impl<P, F> AffineVar<P, F>
+impl<P, F, ConstraintF> AffineVar<P, F, ConstraintF>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
-    fn new(x: F, y: F, infinity: Boolean<<P::BaseField as Field>::BasePrimeField>) -> Self {
+    fn new(x: F, y: F, infinity: Boolean<ConstraintF>) -> Self {
         Self {
             x,
             y,
@@ -95,22 +152,21 @@ where
     pub fn value(&self) -> Result<SWAffine<P>, SynthesisError> {
         Ok(match self.infinity.value()? {
             true => SWAffine::identity(),
-            false => SWAffine::new(self.x.value()?, self.y.value()?),
+            false => SWAffine::new_unchecked(self.x.value()?, self.y.value()?),
         })
     }
 }
 
-impl<P, F> ToConstraintFieldGadget<<P::BaseField as Field>::BasePrimeField> for AffineVar<P, F>
+impl<P, F, ConstraintF> ToConstraintFieldGadget<ConstraintF> for AffineVar<P, F, ConstraintF>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
-    F: ToConstraintFieldGadget<<P::BaseField as Field>::BasePrimeField>,
+    F: ToConstraintFieldGadget<ConstraintF>,
 {
-    fn to_constraint_field(
-        &self,
-    ) -> Result<Vec<FpVar<<P::BaseField as Field>::BasePrimeField>>, SynthesisError> {
-        let mut res = Vec::<FpVar<<P::BaseField as Field>::BasePrimeField>>::new();
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<ConstraintF>>, SynthesisError> {
+        let mut res = Vec::<FpVar<ConstraintF>>::new();
 
         res.extend_from_slice(&self.x.to_constraint_field()?);
         res.extend_from_slice(&self.y.to_constraint_field()?);
@@ -120,22 +176,24 @@ where
     }
 }
 
-impl<P, F> R1CSVar<<P::BaseField as Field>::BasePrimeField> for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> R1CSVar<ConstraintF> for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     type Value = SWProjective<P>;
 
-    fn cs(&self) -> ConstraintSystemRef<<P::BaseField as Field>::BasePrimeField> {
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF> {
         self.x.cs().or(self.y.cs()).or(self.z.cs())
     }
 
     fn value(&self) -> Result<Self::Value, SynthesisError> {
         let (x, y, z) = (self.x.value()?, self.y.value()?, self.z.value()?);
         let result = if let Some(z_inv) = z.inverse() {
-            SWAffine::new(x * &z_inv, y * &z_inv)
+            SWAffine::new_unchecked(x * &z_inv, y * &z_inv)
         } else {
             SWAffine::identity()
         };
@@ -143,9 +201,12 @@ where
     }
 }
 
-impl<P: SWModelParameters, F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>>
-    ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> ProjectiveVar<P, F, ConstraintF, H>
 where
+    P: SWModelParameters,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     /// Constructs `Self` from an `(x, y, z)` coordinate triple.
@@ -160,7 +221,7 @@ where
 
     /// Convert this point into affine form.
     #[tracing::instrument(target = "r1cs")]
-    pub fn to_affine(&self) -> Result<AffineVar<P, F>, SynthesisError> {
+    pub fn to_affine(&self) -> Result<AffineVar<P, F, ConstraintF>, SynthesisError> {
         if self.is_constant() {
             let point = self.value()?.into_affine();
             let x = F::new_constant(ConstraintSystemRef::None, point.x)?;
@@ -194,12 +255,40 @@ where
         }
     }
 
+    /// Enforces that `self` is in the prime-order subgroup, by checking that
+    /// `self = cofactor * q` for a witness point `q`.
+    #[tracing::instrument(target = "r1cs")]
+    fn enforce_multiple_of_cofactor(&self) -> Result<(), SynthesisError> {
+        if P::COFACTOR[0] == 1 && P::COFACTOR[1..].iter().all(|l| *l == 0) {
+            return Ok(());
+        }
+        let cs = self.cs();
+        let mode = if cs.is_none() {
+            AllocationMode::Constant
+        } else {
+            AllocationMode::Witness
+        };
+        let q = Self::new_variable_omit_prime_order_check(
+            ark_relations::ns!(cs, "self / cofactor"),
+            || Ok(self.value()?.into_affine().mul_by_cofactor_inv().into()),
+            mode,
+        )?;
+        let mut result = Self::zero();
+        for b in BitIteratorBE::without_leading_zeros(P::COFACTOR) {
+            result.double_in_place()?;
+            if b {
+                result += &q;
+            }
+        }
+        result.enforce_equal(self)
+    }
+
     /// Allocates a new variable without performing an on-curve check, which is
     /// useful if the variable is known to be on the curve (eg., if the point
     /// is a constant or is a public input).
     #[tracing::instrument(target = "r1cs", skip(cs, f))]
     pub fn new_variable_omit_on_curve_check(
-        cs: impl Into<Namespace<<P::BaseField as Field>::BasePrimeField>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<SWProjective<P>, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -236,7 +325,10 @@ where
     /// Mixed addition, which is useful when `other = (x2, y2)` is known to have
     /// z = 1.
     #[tracing::instrument(target = "r1cs", skip(self, other))]
-    pub(crate) fn add_mixed(&self, other: &NonZeroAffineVar<P, F>) -> Result<Self, SynthesisError> {
+    pub(crate) fn add_mixed(
+        &self,
+        other: &NonZeroAffineVar<P, F, ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
         // Complete mixed addition formula from Renes-Costello-Batina 2015
         // Algorithm 2
         // (https://eprint.iacr.org/2015/1060).
@@ -255,18 +347,18 @@ where
         let xz_pairs = (x2 * z1) + x1; // 8, 9
         let yz_pairs = (y2 * z1) + y1; // 10, 11
 
-        let axz = mul_by_coeff_a::<P, F>(&xz_pairs); // 12
+        let axz = mul_by_coeff_a::<P, F, ConstraintF>(&xz_pairs); // 12
 
         let bz3_part = &axz + z1 * three_b; // 13, 14
 
         let yy_m_bz3 = &yy - &bz3_part; // 15
         let yy_p_bz3 = &yy + &bz3_part; // 16
 
-        let azz = mul_by_coeff_a::<P, F>(z1); // 20
+        let azz = mul_by_coeff_a::<P, F, ConstraintF>(z1); // 20
         let xx3_p_azz = xx.double().unwrap() + &xx + &azz; // 18, 19, 22
 
         let bxz3 = &xz_pairs * three_b; // 21
-        let b3_xz_pairs = mul_by_coeff_a::<P, F>(&(&xx - &azz)) + &bxz3; // 23, 24, 25
+        let b3_xz_pairs = mul_by_coeff_a::<P, F, ConstraintF>(&(&xx - &azz)) + &bxz3; // 23, 24, 25
 
         let x = (&yy_m_bz3 * &xy_pairs) - &yz_pairs * &b3_xz_pairs; // 28,29, 30
         let y = (&yy_p_bz3 * &yy_m_bz3) + &xx3_p_azz * b3_xz_pairs; // 17, 26, 27
@@ -276,20 +368,18 @@ where
     }
 
     /// Computes a scalar multiplication with a little-endian scalar of size
-    /// `P::ScalarField::MODULUS_BITS`.
-    #[tracing::instrument(
-        target = "r1cs",
-        skip(self, mul_result, multiple_of_power_of_two, bits)
-    )]
+    /// `P::ScalarField::MODULUS_BITS`, where `multiples_of_power_of_two[i]`
+    /// is `2^i` times the (non-zero) base.
+    #[tracing::instrument(target = "r1cs", skip(mul_result, multiples_of_power_of_two, bits))]
     fn fixed_scalar_mul_le(
-        &self,
         mul_result: &mut Self,
-        multiple_of_power_of_two: &mut NonZeroAffineVar<P, F>,
-        bits: &[&Boolean<<P::BaseField as Field>::BasePrimeField>],
+        multiples_of_power_of_two: &[NonZeroAffineVar<P, F, ConstraintF>],
+        bits: &[&Boolean<ConstraintF>],
     ) -> Result<(), SynthesisError> {
         let scalar_modulus_bits = <P::ScalarField as PrimeField>::MODULUS_BIT_SIZE as usize;
 
         assert!(scalar_modulus_bits >= bits.len());
+        assert_eq!(multiples_of_power_of_two.len(), bits.len());
         let split_len = ark_std::cmp::min(scalar_modulus_bits - 2, bits.len());
         let (affine_bits, proj_bits) = bits.split_at(split_len);
         // Computes the standard little-endian double-and-add algorithm
@@ -313,34 +403,28 @@ where
         // Unlike normal double-and-add, here we start off with a non-zero
         // `accumulator`, because `NonZeroAffineVar::add_unchecked` doesn't
         // support addition with `zero`. In more detail, we initialize
-        // `accumulator` to be the initial value of `multiple_of_power_of_two`.
+        // `accumulator` to be the first multiple, i.e., the base itself.
         // This ensures that all unchecked additions of `accumulator` with later
-        // values of `multiple_of_power_of_two` are safe. However, to do this
-        // correctly, we need to perform two steps:
-        // * We must skip the LSB, and instead proceed assuming that it was 1. Later, we
-        //   will conditionally subtract the initial value of `accumulator`: if LSB ==
-        //   0: subtract initial_acc_value; else, subtract 0.
-        // * Because we are assuming the first bit, we must double
-        //   `multiple_of_power_of_two`.
-
-        let mut accumulator = multiple_of_power_of_two.clone();
-        let initial_acc_value = accumulator.into_projective();
+        // multiples are safe. However, to do this correctly, we must skip the LSB,
+        // and instead proceed assuming that it was 1. Later, we will conditionally
+        // subtract the initial value of `accumulator`: if LSB == 0: subtract
+        // initial_acc_value; else, subtract 0.
+        let (affine_multiples, proj_multiples) = multiples_of_power_of_two.split_at(split_len);
 
-        // The powers start at 2 (instead of 1) because we're skipping the first bit.
-        multiple_of_power_of_two.double_in_place()?;
+        let mut accumulator = affine_multiples[0].clone();
+        let initial_acc_value = accumulator.into_projective();
 
         // As mentioned, we will skip the LSB, and will later handle it via a
         // conditional subtraction.
-        for bit in affine_bits.iter().skip(1) {
+        for (bit, multiple) in affine_bits.iter().zip(affine_multiples).skip(1) {
             if bit.is_constant() {
                 if *bit == &Boolean::TRUE {
-                    accumulator = accumulator.add_unchecked(&multiple_of_power_of_two)?;
+                    accumulator = accumulator.add_unchecked(multiple)?;
                 }
             } else {
-                let temp = accumulator.add_unchecked(&multiple_of_power_of_two)?;
+                let temp = accumulator.add_unchecked(multiple)?;
                 accumulator = bit.select(&temp, &accumulator)?;
             }
-            multiple_of_power_of_two.double_in_place()?;
         }
         // Perform conditional subtraction:
 
@@ -353,26 +437,95 @@ where
         *mul_result += result - subtrahend;
 
         // Now, let's finish off the rest of the bits using our complete formulae
-        for bit in proj_bits {
+        for (bit, multiple) in proj_bits.iter().zip(proj_multiples) {
             if bit.is_constant() {
                 if *bit == &Boolean::TRUE {
-                    *mul_result += &multiple_of_power_of_two.into_projective();
+                    *mul_result += &multiple.into_projective();
                 }
             } else {
-                let temp = &*mul_result + &multiple_of_power_of_two.into_projective();
+                let temp = &*mul_result + &multiple.into_projective();
                 *mul_result = bit.select(&temp, &mul_result)?;
             }
-            multiple_of_power_of_two.double_in_place()?;
         }
         Ok(())
     }
 }
 
-impl<P, F> CurveVar<SWProjective<P>, <P::BaseField as Field>::BasePrimeField>
-    for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> ProjectiveVar<P, F, ConstraintF, H>
+where
+    P: SWModelParameters,
+    F: FieldVar<P::BaseField, ConstraintF>
+        + TwoBitLookupGadget<ConstraintF, TableConstant = P::BaseField>
+        + ThreeBitCondNegLookupGadget<ConstraintF, TableConstant = P::BaseField>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    /// Computes `bits * table.base()`, where `bits` is a little-endian
+    /// `Boolean` representation of a scalar, using 3-bit signed-digit windows
+    /// over the precomputed `table`.
+    ///
+    /// Each window costs a lookup into constant tables and a single incomplete
+    /// affine addition, which is cheaper than the conditional addition per bit
+    /// performed by [`CurveVar::scalar_mul_le`] on constant points.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `bits` has more than `table.max_num_bits()`
+    /// elements.
+    #[tracing::instrument(target = "r1cs", skip(table, bits))]
+    pub fn fixed_base_scalar_mul_le<'a>(
+        table: &FixedBaseTable<SWProjective<P>>,
+        bits: impl Iterator<Item = &'a Boolean<ConstraintF>>,
+    ) -> Result<Self, SynthesisError> {
+        let bits = bits.cloned().collect::<Vec<_>>();
+        assert!(
+            bits.len() <= table.max_num_bits(),
+            "the scalar is too large for the table"
+        );
+        if bits.is_empty() || table.base().is_zero() {
+            return Ok(Self::zero());
+        }
+        let digits = crate::groups::three_bit_signed_digits(&bits)?;
+
+        // Within a chunk of windows starting at `i0`, the partial sums are of the
+        // form `8^i0 * s * base`, where `s = Σᵢ 8ⁱ * d[i0 + i]` is odd, and
+        // `|s| < 8^len` after `len` windows. The next addend is
+        // `8^i0 * 8^len * d * base`, where `d` is odd. As long as `8^len < p / 8`,
+        // neither `s` nor `s ± 8^len * d` is `0 mod p`, and so the incomplete
+        // affine formulae are safe. We combine the chunks with complete formulae.
+        let scalar_modulus_bits = <P::ScalarField as PrimeField>::MODULUS_BIT_SIZE as usize;
+        let windows_per_chunk = (scalar_modulus_bits - 1) / 3;
+
+        let mut result = Self::zero();
+        for (chunk_index, chunk) in digits.chunks(windows_per_chunk).enumerate() {
+            let mut accumulator: Option<NonZeroAffineVar<P, F, ConstraintF>> = None;
+            for (i, (digit_bits, j0_and_j1)) in chunk.iter().enumerate() {
+                let multiples = table.window(chunk_index * windows_per_chunk + i);
+                let xs = multiples.iter().map(|p| p.x).collect::<Vec<_>>();
+                let ys = multiples.iter().map(|p| p.y).collect::<Vec<_>>();
+                let x = F::two_bit_lookup(&digit_bits[..2], &xs)?;
+                let y = F::three_bit_cond_neg_lookup(digit_bits, j0_and_j1, &ys)?;
+                let multiple = NonZeroAffineVar::new(x, y);
+                accumulator = Some(match accumulator {
+                    Some(accumulator) => accumulator.add_unchecked(&multiple)?,
+                    None => multiple,
+                });
+            }
+            result += accumulator.unwrap().into_projective();
+        }
+        // If `bits[0]` is 0, we have to subtract the base to undo the recoding.
+        bits[0].select(&result, &(result.clone() - table.base()))
+    }
+}
+
+impl<P, F, ConstraintF, H> CurveVar<SWProjective<P>, ConstraintF>
+    for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     fn constant(g: SWProjective<P>) -> Self {
@@ -384,13 +537,13 @@ where
         Self::new(F::zero(), F::one(), F::zero())
     }
 
-    fn is_zero(&self) -> Result<Boolean<<P::BaseField as Field>::BasePrimeField>, SynthesisError> {
+    fn is_zero(&self) -> Result<Boolean<ConstraintF>, SynthesisError> {
         self.z.is_zero()
     }
 
     #[tracing::instrument(target = "r1cs", skip(cs, f))]
     fn new_variable_omit_prime_order_check(
-        cs: impl Into<Namespace<<P::BaseField as Field>::BasePrimeField>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<SWProjective<P>, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -448,6 +601,11 @@ where
         // Ok(())
     }
 
+    #[tracing::instrument(target = "r1cs")]
+    fn clear_cofactor(&self) -> Result<Self, SynthesisError> {
+        H::clear_cofactor(self)
+    }
+
     #[inline]
     #[tracing::instrument(target = "r1cs")]
     fn double_in_place(&mut self) -> Result<(), SynthesisError> {
@@ -467,7 +625,7 @@ where
         let xy2 = (&self.x * &self.y).double()?; // 4, 5
         let xz2 = (&self.x * &self.z).double()?; // 6, 7
 
-        let axz2 = mul_by_coeff_a::<P, F>(&xz2); // 8
+        let axz2 = mul_by_coeff_a::<P, F, ConstraintF>(&xz2); // 8
 
         let bzz3_part = &axz2 + &zz * three_b; // 9, 10
         let yy_m_bzz3 = &yy - &bzz3_part; // 11
@@ -476,8 +634,8 @@ where
         let x_frag = yy_m_bzz3 * &xy2; // 14
 
         let bxz3 = xz2 * three_b; // 15
-        let azz = mul_by_coeff_a::<P, F>(&zz); // 16
-        let b3_xz_pairs = mul_by_coeff_a::<P, F>(&(&xx - &azz)) + &bxz3; // 15, 16, 17, 18, 19
+        let azz = mul_by_coeff_a::<P, F, ConstraintF>(&zz); // 16
+        let b3_xz_pairs = mul_by_coeff_a::<P, F, ConstraintF>(&(&xx - &azz)) + &bxz3; // 15, 16, 17, 18, 19
         let xx3_p_azz = (xx.double()? + &xx + &azz) * &b3_xz_pairs; // 23, 24, 25
 
         let y = y_frag + &xx3_p_azz; // 26, 27
@@ -500,18 +658,29 @@ where
     #[tracing::instrument(target = "r1cs", skip(bits))]
     fn scalar_mul_le<'a>(
         &self,
-        bits: impl Iterator<Item = &'a Boolean<<P::BaseField as Field>::BasePrimeField>>,
+        bits: impl Iterator<Item = &'a Boolean<ConstraintF>>,
     ) -> Result<Self, SynthesisError> {
+        // If `self` is a constant, its power-of-two multiples can be computed
+        // outside the circuit.
         if self.is_constant() {
-            if self.value().unwrap().is_zero() {
-                return Ok(self.clone());
-            }
+            // The value should exist because `self` is a constant.
+            let bits = bits.collect::<Vec<_>>();
+            let multiples = power_of_two_multiples(self.value()?, bits.len());
+            let mut res = Self::zero();
+            res.precomputed_base_scalar_mul_le(bits.into_iter().zip(&multiples))?;
+            return Ok(res);
         }
         let self_affine = self.to_affine()?;
         let (x, y, infinity) = (self_affine.x, self_affine.y, self_affine.infinity);
         // We first handle the non-zero case, and then later
         // will conditionally select zero if `self` was zero.
-        let non_zero_self = NonZeroAffineVar::new(x, y);
+        // To keep the constraint system satisfiable in that case,
+        // we replace `self` by the generator.
+        let generator = P::GENERATOR;
+        let non_zero_self = NonZeroAffineVar::new(
+            infinity.select(&F::constant(generator.x), &x)?,
+            infinity.select(&F::constant(generator.y), &y)?,
+        );
 
         let mut bits = bits.collect::<Vec<_>>();
         if bits.len() == 0 {
@@ -534,7 +703,12 @@ where
         let mut power_of_two_times_self = non_zero_self;
         // We chunk up `bits` into `p`-sized chunks.
         for bits in bits.chunks(scalar_modulus_bits as usize) {
-            self.fixed_scalar_mul_le(&mut mul_result, &mut power_of_two_times_self, bits)?;
+            let mut multiples = Vec::with_capacity(bits.len());
+            for _ in bits {
+                multiples.push(power_of_two_times_self.clone());
+                power_of_two_times_self.double_in_place()?;
+            }
+            Self::fixed_scalar_mul_le(&mut mul_result, &multiples, bits)?;
         }
 
         // The foregoing algorithm relies on incomplete addition, and so does not
@@ -552,39 +726,117 @@ where
     ) -> Result<(), SynthesisError>
     where
         I: Iterator<Item = (B, &'a SWProjective<P>)>,
-        B: Borrow<Boolean<<P::BaseField as Field>::BasePrimeField>>,
+        B: Borrow<Boolean<ConstraintF>>,
     {
-        // We just ignore the provided bases and use the faster scalar multiplication.
         let (bits, bases): (Vec<_>, Vec<_>) = scalar_bits_with_bases
             .map(|(b, c)| (b.borrow().clone(), *c))
             .unzip();
-        let base = bases[0];
-        *self += Self::constant(base).scalar_mul_le(bits.iter())?;
+        // The bases are power-of-two multiples of the first one, so they are all
+        // zero if the first one is.
+        if bases.is_empty() || bases[0].is_zero() {
+            return Ok(());
+        }
+        // Since the bases are constants, we can use the faster scalar
+        // multiplication of `Self::scalar_mul_le` without computing the
+        // doublings in the circuit.
+        let bits = bits.iter().collect::<Vec<_>>();
+        let bases = SWProjective::normalize_batch(&bases)
+            .into_iter()
+            .map(|base| NonZeroAffineVar::new(F::constant(base.x), F::constant(base.y)))
+            .collect::<Vec<_>>();
+        let scalar_modulus_bits = <P::ScalarField as PrimeField>::MODULUS_BIT_SIZE as usize;
+        let mut mul_result = Self::zero();
+        for (bits, bases) in bits
+            .chunks(scalar_modulus_bits)
+            .zip(bases.chunks(scalar_modulus_bits))
+        {
+            Self::fixed_scalar_mul_le(&mut mul_result, bases, bits)?;
+        }
+        *self += mul_result;
         Ok(())
     }
 }
 
-impl<P, F> ToConstraintFieldGadget<<P::BaseField as Field>::BasePrimeField> for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> PointCompressionGadget<SWProjective<P>, ConstraintF>
+    for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
-    F: ToConstraintFieldGadget<<P::BaseField as Field>::BasePrimeField>,
 {
-    fn to_constraint_field(
-        &self,
-    ) -> Result<Vec<FpVar<<P::BaseField as Field>::BasePrimeField>>, SynthesisError> {
-        self.to_affine()?.to_constraint_field()
+    #[tracing::instrument(target = "r1cs")]
+    fn to_compressed_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
+        // The `x`-coordinate of the point at infinity is zero.
+        let self_affine = self.to_affine()?;
+        let infinity = self_affine.infinity;
+        let y_is_negative = self_affine.y.is_positive()?.not().and(&infinity.not())?;
+        match sw_compressed_layout::<P>() {
+            SWCompressedLayout::Arkworks => {
+                field_to_bytes_with_flags(&self_affine.x, &[infinity, y_is_negative])
+            },
+            SWCompressedLayout::Zcash => {
+                // `y_is_negative` is the "lexicographically largest" flag.
+                let flags = [y_is_negative, infinity, Boolean::TRUE];
+                let mut bytes = field_to_bytes_with_flags(&self_affine.x, &flags)?;
+                bytes.reverse();
+                Ok(bytes)
+            },
+        }
+    }
+
+    #[tracing::instrument(target = "r1cs", skip(bytes))]
+    fn from_compressed_bytes(bytes: &[UInt8<ConstraintF>]) -> Result<Self, SynthesisError> {
+        let (x, infinity, y_is_negative) = match sw_compressed_layout::<P>() {
+            SWCompressedLayout::Arkworks => {
+                let (x, flags) = field_from_bytes_with_flags::<P::BaseField, _, F>(bytes, 2)?;
+                (x, flags[0].clone(), flags[1].clone())
+            },
+            SWCompressedLayout::Zcash => {
+                let bytes = bytes.iter().rev().cloned().collect::<Vec<_>>();
+                let (x, flags) = field_from_bytes_with_flags::<P::BaseField, _, F>(&bytes, 3)?;
+                // The compression flag must be set.
+                flags[2].enforce_equal(&Boolean::TRUE)?;
+                (x, flags[1].clone(), flags[0].clone())
+            },
+        };
+        infinity
+            .and(&y_is_negative)?
+            .enforce_equal(&Boolean::FALSE)?;
+        x.conditional_enforce_equal(&F::zero(), &infinity)?;
+
+        // Recover `y` from `y^2 = x^3 + ax + b`, which we replace by zero for the point
+        // at infinity.
+        let y_squared = x.square()? * &x + mul_by_coeff_a::<P, F, ConstraintF>(&x) + P::COEFF_B;
+        let y_squared = infinity.select(&F::zero(), &y_squared)?;
+        let y = y_squared.sqrt_with_sign(&y_is_negative.not())?;
+        let point = Self::new(x, infinity.select(&F::one(), &y)?, F::from(infinity.not()));
+        point.enforce_multiple_of_cofactor()?;
+        Ok(point)
     }
 }
 
-fn mul_by_coeff_a<
+impl<P, F, ConstraintF, H> ToConstraintFieldGadget<ConstraintF>
+    for ProjectiveVar<P, F, ConstraintF, H>
+where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
->(
-    f: &F,
-) -> F
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+    F: ToConstraintFieldGadget<ConstraintF>,
+{
+    fn to_constraint_field(&self) -> Result<Vec<FpVar<ConstraintF>>, SynthesisError> {
+        self.to_affine()?.to_constraint_field()
+    }
+}
+
+fn mul_by_coeff_a<P, F, ConstraintF>(f: &F) -> F
 where
+    P: SWModelParameters,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     if !P::COEFF_A.is_zero() {
@@ -595,13 +847,13 @@ where
 }
 
 impl_bounded_ops!(
-    ProjectiveVar<P, F>,
+    ProjectiveVar<P, F, ConstraintF, H>,
     SWProjective<P>,
     Add,
     add,
     AddAssign,
     add_assign,
-    |mut this: &'a ProjectiveVar<P, F>, mut other: &'a ProjectiveVar<P, F>| {
+    |mut this: &'a ProjectiveVar<P, F, ConstraintF, H>, mut other: &'a ProjectiveVar<P, F, ConstraintF, H>| {
         // Implement complete addition for Short Weierstrass curves, following
         // the complete addition formula from Renes-Costello-Batina 2015
         // (https://eprint.iacr.org/2015/1060).
@@ -644,18 +896,18 @@ impl_bounded_ops!(
             let xz_pairs = ((x1 + z1) * &(x2 + z2)) - (&xx + &zz); // 9, 10, 11, 12, 13
             let yz_pairs = ((y1 + z1) * &(y2 + z2)) - (&yy + &zz); // 14, 15, 16, 17, 18
 
-            let axz = mul_by_coeff_a::<P, F>(&xz_pairs); // 19
+            let axz = mul_by_coeff_a::<P, F, ConstraintF>(&xz_pairs); // 19
 
             let bzz3_part = &axz + &zz * three_b; // 20, 21
 
             let yy_m_bzz3 = &yy - &bzz3_part; // 22
             let yy_p_bzz3 = &yy + &bzz3_part; // 23
 
-            let azz = mul_by_coeff_a::<P, F>(&zz);
+            let azz = mul_by_coeff_a::<P, F, ConstraintF>(&zz);
             let xx3_p_azz = xx.double().unwrap() + &xx + &azz; // 25, 26, 27, 29
 
             let bxz3 = &xz_pairs * three_b; // 28
-            let b3_xz_pairs = mul_by_coeff_a::<P, F>(&(&xx - &azz)) + &bxz3; // 30, 31, 32
+            let b3_xz_pairs = mul_by_coeff_a::<P, F, ConstraintF>(&(&xx - &azz)) + &bxz3; // 30, 31, 32
 
             let x = (&yy_m_bzz3 * &xy_pairs) - &yz_pairs * &b3_xz_pairs; // 35, 39, 40
             let y = (&yy_p_bzz3 * &yy_m_bzz3) + &xx3_p_azz * b3_xz_pairs; // 24, 36, 37, 38
@@ -665,52 +917,62 @@ impl_bounded_ops!(
         }
 
     },
-    |this: &'a ProjectiveVar<P, F>, other: SWProjective<P>| {
+    |this: &'a ProjectiveVar<P, F, ConstraintF, H>, other: SWProjective<P>| {
         this + ProjectiveVar::constant(other)
     },
-    (F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>, P: SWModelParameters),
+    (F: FieldVar<P::BaseField, ConstraintF>, P: SWModelParameters, ConstraintF: PrimeField, H: ClearCofactorConfig<P>),
     for <'b> &'b F: FieldOpsBounds<'b, P::BaseField, F>,
 );
 
 impl_bounded_ops!(
-    ProjectiveVar<P, F>,
+    ProjectiveVar<P, F, ConstraintF, H>,
     SWProjective<P>,
     Sub,
     sub,
     SubAssign,
     sub_assign,
-    |this: &'a ProjectiveVar<P, F>, other: &'a ProjectiveVar<P, F>| this + other.negate().unwrap(),
-    |this: &'a ProjectiveVar<P, F>, other: SWProjective<P>| this - ProjectiveVar::constant(other),
-    (F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>, P: SWModelParameters),
+    |this: &'a ProjectiveVar<P, F, ConstraintF, H>, other: &'a ProjectiveVar<P, F, ConstraintF, H>| this + other.negate().unwrap(),
+    |this: &'a ProjectiveVar<P, F, ConstraintF, H>, other: SWProjective<P>| this - ProjectiveVar::constant(other),
+    (F: FieldVar<P::BaseField, ConstraintF>, P: SWModelParameters, ConstraintF: PrimeField, H: ClearCofactorConfig<P>),
     for <'b> &'b F: FieldOpsBounds<'b, P::BaseField, F>
 );
 
-impl<'a, P, F> GroupOpsBounds<'a, SWProjective<P>, ProjectiveVar<P, F>> for ProjectiveVar<P, F>
+impl<'a, P, F, ConstraintF, H>
+    GroupOpsBounds<'a, SWProjective<P>, ProjectiveVar<P, F, ConstraintF, H>>
+    for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'b> &'b F: FieldOpsBounds<'b, P::BaseField, F>,
 {
 }
 
-impl<'a, P, F> GroupOpsBounds<'a, SWProjective<P>, ProjectiveVar<P, F>> for &'a ProjectiveVar<P, F>
+impl<'a, P, F, ConstraintF, H>
+    GroupOpsBounds<'a, SWProjective<P>, ProjectiveVar<P, F, ConstraintF, H>>
+    for &'a ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'b> &'b F: FieldOpsBounds<'b, P::BaseField, F>,
 {
 }
 
-impl<P, F> CondSelectGadget<<P::BaseField as Field>::BasePrimeField> for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> CondSelectGadget<ConstraintF> for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     #[inline]
     #[tracing::instrument(target = "r1cs")]
     fn conditionally_select(
-        cond: &Boolean<<P::BaseField as Field>::BasePrimeField>,
+        cond: &Boolean<ConstraintF>,
         true_value: &Self,
         false_value: &Self,
     ) -> Result<Self, SynthesisError> {
@@ -722,17 +984,16 @@ where
     }
 }
 
-impl<P, F> EqGadget<<P::BaseField as Field>::BasePrimeField> for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> EqGadget<ConstraintF> for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn is_eq(
-        &self,
-        other: &Self,
-    ) -> Result<Boolean<<P::BaseField as Field>::BasePrimeField>, SynthesisError> {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<ConstraintF>, SynthesisError> {
         let x_equal = (&self.x * &other.z).is_eq(&(&other.x * &self.z))?;
         let y_equal = (&self.y * &other.z).is_eq(&(&other.y * &self.z))?;
         let coordinates_equal = x_equal.and(&y_equal)?;
@@ -745,7 +1006,7 @@ where
     fn conditional_enforce_equal(
         &self,
         other: &Self,
-        condition: &Boolean<<P::BaseField as Field>::BasePrimeField>,
+        condition: &Boolean<ConstraintF>,
     ) -> Result<(), SynthesisError> {
         let x_equal = (&self.x * &other.z).is_eq(&(&other.x * &self.z))?;
         let y_equal = (&self.y * &other.z).is_eq(&(&other.y * &self.z))?;
@@ -762,7 +1023,7 @@ where
     fn conditional_enforce_not_equal(
         &self,
         other: &Self,
-        condition: &Boolean<<P::BaseField as Field>::BasePrimeField>,
+        condition: &Boolean<ConstraintF>,
     ) -> Result<(), SynthesisError> {
         let is_equal = self.is_eq(other)?;
         is_equal
@@ -771,14 +1032,17 @@ where
     }
 }
 
-impl<P, F> AllocVar<SWAffine<P>, <P::BaseField as Field>::BasePrimeField> for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> AllocVar<SWAffine<P>, ConstraintF>
+    for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     fn new_variable<T: Borrow<SWAffine<P>>>(
-        cs: impl Into<Namespace<<P::BaseField as Field>::BasePrimeField>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -790,15 +1054,17 @@ where
     }
 }
 
-impl<P, F> AllocVar<SWProjective<P>, <P::BaseField as Field>::BasePrimeField>
-    for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> AllocVar<SWProjective<P>, ConstraintF>
+    for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     fn new_variable<T: Borrow<SWProjective<P>>>(
-        cs: impl Into<Namespace<<P::BaseField as Field>::BasePrimeField>>,
+        cs: impl Into<Namespace<ConstraintF>>,
         f: impl FnOnce() -> Result<T, SynthesisError>,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
@@ -838,7 +1104,7 @@ where
                 let (mut ge, iter) = if cofactor_weight < modulus_minus_1_weight {
                     let ge = Self::new_variable_omit_prime_order_check(
                         ark_relations::ns!(cs, "Witness without subgroup check with cofactor mul"),
-                        || f().map(|g| g.borrow().into_affine().mul_by_cofactor_inv().into()),
+                        || f().map(|g| g.into_affine().mul_by_cofactor_inv().into()),
                         mode,
                     )?;
                     (
@@ -902,16 +1168,16 @@ fn div2(limbs: &mut [u64]) {
     }
 }
 
-impl<P, F> ToBitsGadget<<P::BaseField as Field>::BasePrimeField> for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> ToBitsGadget<ConstraintF> for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn to_bits_le(
-        &self,
-    ) -> Result<Vec<Boolean<<P::BaseField as Field>::BasePrimeField>>, SynthesisError> {
+    fn to_bits_le(&self) -> Result<Vec<Boolean<ConstraintF>>, SynthesisError> {
         let g = self.to_affine()?;
         let mut bits = g.x.to_bits_le()?;
         let y_bits = g.y.to_bits_le()?;
@@ -921,9 +1187,7 @@ where
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bits_le(
-        &self,
-    ) -> Result<Vec<Boolean<<P::BaseField as Field>::BasePrimeField>>, SynthesisError> {
+    fn to_non_unique_bits_le(&self) -> Result<Vec<Boolean<ConstraintF>>, SynthesisError> {
         let g = self.to_affine()?;
         let mut bits = g.x.to_non_unique_bits_le()?;
         let y_bits = g.y.to_non_unique_bits_le()?;
@@ -933,16 +1197,16 @@ where
     }
 }
 
-impl<P, F> ToBytesGadget<<P::BaseField as Field>::BasePrimeField> for ProjectiveVar<P, F>
+impl<P, F, ConstraintF, H> ToBytesGadget<ConstraintF> for ProjectiveVar<P, F, ConstraintF, H>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
+    H: ClearCofactorConfig<P>,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn to_bytes(
-        &self,
-    ) -> Result<Vec<UInt8<<P::BaseField as Field>::BasePrimeField>>, SynthesisError> {
+    fn to_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let g = self.to_affine()?;
         let mut bytes = g.x.to_bytes()?;
         let y_bytes = g.y.to_bytes()?;
@@ -953,9 +1217,7 @@ where
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn to_non_unique_bytes(
-        &self,
-    ) -> Result<Vec<UInt8<<P::BaseField as Field>::BasePrimeField>>, SynthesisError> {
+    fn to_non_unique_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError> {
         let g = self.to_affine()?;
         let mut bytes = g.x.to_non_unique_bytes()?;
         let y_bytes = g.y.to_non_unique_bytes()?;
@@ -9,7 +9,8 @@ use ark_std::ops::Add;
 #[must_use]
 pub struct NonZeroAffineVar<
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField = <<P as CurveConfig>::BaseField as Field>::BasePrimeField,
 > where
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
@@ -18,13 +19,14 @@ pub struct NonZeroAffineVar<
     /// The y-coordinate.
     pub y: F,
     #[derivative(Debug = "ignore")]
-    _params: PhantomData<P>,
+    _params: PhantomData<(P, ConstraintF)>,
 }
 
-impl<P, F> NonZeroAffineVar<P, F>
+impl<P, F, ConstraintF> NonZeroAffineVar<P, F, ConstraintF>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     pub fn new(x: F, y: F) -> Self {
@@ -37,7 +39,9 @@ where
 
     /// Converts self into a non-zero projective point.
     #[tracing::instrument(target = "r1cs", skip(self))]
-    pub fn into_projective(&self) -> ProjectiveVar<P, F> {
+    pub fn into_projective<H: ClearCofactorConfig<P>>(
+        &self,
+    ) -> ProjectiveVar<P, F, ConstraintF, H> {
         ProjectiveVar::new(self.x.clone(), self.y.clone(), F::one())
     }
 
@@ -130,6 +134,118 @@ where
         }
     }
 
+    /// Computes `self + other`, where `other` may equal `±self`, in which case
+    /// the output is `self.double()` or the point at infinity, respectively.
+    /// Points of order two, for which `self == -self`, are also supported.
+    ///
+    /// The exceptional cases are detected with `is_eq` on the coordinates,
+    /// and the slope of the tangent is selected in place of the slope of the
+    /// chord when `self == other`. For `FpVar`, this requires 15 constraints,
+    /// compared to the 3 required by `add_unchecked`, which is still cheaper
+    /// than an addition of projective points.
+    #[tracing::instrument(target = "r1cs", skip(self, other))]
+    pub fn add_checked(
+        &self,
+        other: &Self,
+    ) -> Result<AffineVar<P, F, ConstraintF>, SynthesisError> {
+        if [self, other].is_constant() {
+            let result = self.value()?.add(other.value()?).into_affine();
+            return Ok(AffineVar::new(
+                F::constant(result.x),
+                F::constant(result.y),
+                Boolean::constant(result.infinity),
+            ));
+        }
+        let (x1, y1) = (&self.x, &self.y);
+        let x2 = &other.x;
+        let x_equal = x1.is_eq(x2)?;
+        let is_negation = x_equal.and(&(y1 + &other.y).is_zero()?)?;
+        let lambda = self.checked_slope(other, &x_equal, &is_negation)?;
+        let x3 = lambda.square()? - x1 - x2;
+        let y3 = lambda * &(x1 - &x3) - y1;
+        Ok(AffineVar::new(
+            is_negation.select(&F::zero(), &x3)?,
+            is_negation.select(&F::one(), &y3)?,
+            is_negation,
+        ))
+    }
+
+    /// Computes `(self + other) + self`, where `other` may equal `±self` or
+    /// `-self.double()`. In the last case, the output is the point at
+    /// infinity.
+    ///
+    /// This extends the formulae of [`Self::double_and_add_unchecked`] with
+    /// the exceptional cases of [`Self::add_checked`], as well as a check
+    /// of whether `self + other = -self`. For `FpVar`, this requires 24
+    /// constraints, compared to the 5 required by
+    /// `double_and_add_unchecked`.
+    #[tracing::instrument(target = "r1cs", skip(self, other))]
+    pub fn double_and_add_checked(
+        &self,
+        other: &Self,
+    ) -> Result<AffineVar<P, F, ConstraintF>, SynthesisError> {
+        if [self, other].is_constant() {
+            let result = (self.value()?.add(other.value()?) + self.value()?).into_affine();
+            return Ok(AffineVar::new(
+                F::constant(result.x),
+                F::constant(result.y),
+                Boolean::constant(result.infinity),
+            ));
+        }
+        let (x1, y1) = (&self.x, &self.y);
+        let x2 = &other.x;
+
+        // Calculate the x-coordinate of self + other, which is meaningless if
+        // `other == -self`.
+        let x_equal = x1.is_eq(x2)?;
+        let is_negation = x_equal.and(&(y1 + &other.y).is_zero()?)?;
+        let lambda_1 = self.checked_slope(other, &x_equal, &is_negation)?;
+        let x3 = lambda_1.square()? - x1 - x2;
+
+        // If `self + other = ±self`, then it must equal `-self` (as `other` is
+        // non-zero), and the output is the point at infinity.
+        let x3_equals_x1 = x3.is_eq(x1)?;
+        let is_zero = x3_equals_x1.and(&is_negation.not())?;
+        let denominator = x3_equals_x1.select(&F::one(), &(&x3 - x1))?;
+        let lambda_2 =
+            (lambda_1 + y1.double()?.mul_by_inverse_unchecked(&denominator)?).negate()?;
+        let x4 = lambda_2.square()? - x1 - &x3;
+        let y4 = lambda_2 * &(x1 - &x4) - y1;
+
+        // If `other == -self`, then the output is `self`.
+        let x4 = is_negation.select(x1, &x4)?;
+        let y4 = is_negation.select(y1, &y4)?;
+        Ok(AffineVar::new(
+            is_zero.select(&F::zero(), &x4)?,
+            is_zero.select(&F::one(), &y4)?,
+            is_zero,
+        ))
+    }
+
+    /// Outputs the slope of the line through `self` and `other`, or of the
+    /// tangent at `self` if `x_equal` is true. If `other == -self` (which
+    /// includes the case `self == other` for points of order two), as
+    /// indicated by `is_negation`, the slope is meaningless.
+    fn checked_slope(
+        &self,
+        other: &Self,
+        x_equal: &Boolean<ConstraintF>,
+        is_negation: &Boolean<ConstraintF>,
+    ) -> Result<F, SynthesisError> {
+        let (x1, y1) = (&self.x, &self.y);
+        let (x2, y2) = (&other.x, &other.y);
+        let x1_sqr = x1.square()?;
+        let numerator = x_equal.select(&(x1_sqr.double()? + &x1_sqr + P::COEFF_A), &(y2 - y1))?;
+        // When `x_equal` is true, `y1 + y2` is `2 * y1` unless `other == -self`,
+        // in which case it is zero and we add 1 instead.
+        let denominator = x_equal.select(&(y1 + y2), &(x2 - x1))? + F::from(is_negation.clone());
+        // It's okay to use `unchecked` here, because `denominator` is non-zero:
+        // both points lie on the curve, so `x1 == x2` implies that
+        // `other == ±self`. If `other == -self`, the denominator is 1, and
+        // otherwise `y1 == y2 != 0`.
+        numerator.mul_by_inverse_unchecked(&denominator)
+    }
+
     /// Doubles `self` in place.
     #[tracing::instrument(target = "r1cs", skip(self))]
     pub fn double_in_place(&mut self) -> Result<(), SynthesisError> {
@@ -138,33 +254,35 @@ where
     }
 }
 
-impl<P, F> R1CSVar<<P::BaseField as Field>::BasePrimeField> for NonZeroAffineVar<P, F>
+impl<P, F, ConstraintF> R1CSVar<ConstraintF> for NonZeroAffineVar<P, F, ConstraintF>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     type Value = SWAffine<P>;
 
-    fn cs(&self) -> ConstraintSystemRef<<P::BaseField as Field>::BasePrimeField> {
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF> {
         self.x.cs().or(self.y.cs())
     }
 
     fn value(&self) -> Result<SWAffine<P>, SynthesisError> {
-        Ok(SWAffine::new(self.x.value()?, self.y.value()?))
+        Ok(SWAffine::new_unchecked(self.x.value()?, self.y.value()?))
     }
 }
 
-impl<P, F> CondSelectGadget<<P::BaseField as Field>::BasePrimeField> for NonZeroAffineVar<P, F>
+impl<P, F, ConstraintF> CondSelectGadget<ConstraintF> for NonZeroAffineVar<P, F, ConstraintF>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     #[inline]
     #[tracing::instrument(target = "r1cs")]
     fn conditionally_select(
-        cond: &Boolean<<P::BaseField as Field>::BasePrimeField>,
+        cond: &Boolean<ConstraintF>,
         true_value: &Self,
         false_value: &Self,
     ) -> Result<Self, SynthesisError> {
@@ -175,17 +293,15 @@ where
     }
 }
 
-impl<P, F> EqGadget<<P::BaseField as Field>::BasePrimeField> for NonZeroAffineVar<P, F>
+impl<P, F, ConstraintF> EqGadget<ConstraintF> for NonZeroAffineVar<P, F, ConstraintF>
 where
     P: SWModelParameters,
-    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    F: FieldVar<P::BaseField, ConstraintF>,
+    ConstraintF: PrimeField,
     for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
 {
     #[tracing::instrument(target = "r1cs")]
-    fn is_eq(
-        &self,
-        other: &Self,
-    ) -> Result<Boolean<<P::BaseField as Field>::BasePrimeField>, SynthesisError> {
+    fn is_eq(&self, other: &Self) -> Result<Boolean<ConstraintF>, SynthesisError> {
         let x_equal = self.x.is_eq(&other.x)?;
         let y_equal = self.y.is_eq(&other.y)?;
         x_equal.and(&y_equal)
@@ -196,7 +312,7 @@ where
     fn conditional_enforce_equal(
         &self,
         other: &Self,
-        condition: &Boolean<<P::BaseField as Field>::BasePrimeField>,
+        condition: &Boolean<ConstraintF>,
     ) -> Result<(), SynthesisError> {
         let x_equal = self.x.is_eq(&other.x)?;
         let y_equal = self.y.is_eq(&other.y)?;
@@ -218,7 +334,7 @@ where
     fn conditional_enforce_not_equal(
         &self,
         other: &Self,
-        condition: &Boolean<<P::BaseField as Field>::BasePrimeField>,
+        condition: &Boolean<ConstraintF>,
     ) -> Result<(), SynthesisError> {
         let is_equal = self.is_eq(other)?;
         is_equal
@@ -241,8 +357,8 @@ mod test_non_zero_affine {
     };
     use ark_ec::{models::short_weierstrass::SWCurveConfig, CurveGroup};
     use ark_relations::r1cs::ConstraintSystem;
-    use ark_std::{vec::Vec, One};
-    use ark_test_curves::bls12_381::{g1::Config as G1Config, Fq};
+    use ark_std::{vec::Vec, One, Zero};
+    use ark_test_curves::bls12_381::{g1::Config as G1Config, Fq, G1Affine};
 
     #[test]
     fn correctness_test_1() {
@@ -390,4 +506,82 @@ mod test_non_zero_affine {
 
         assert!(cs.is_satisfied().unwrap());
     }
+
+    #[test]
+    fn checked_addition_test() {
+        let g = G1Config::GENERATOR;
+        let cases = [
+            (g, (g + g).into_affine()),
+            (g, g),
+            (g, -g),
+            (g, (-(g + g)).into_affine()),
+        ];
+        for (a, b) in cases {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let alloc = |p: G1Affine| {
+                NonZeroAffineVar::<G1Config, FpVar<Fq>>::new(
+                    FpVar::new_witness(cs.clone(), || Ok(p.x)).unwrap(),
+                    FpVar::new_witness(cs.clone(), || Ok(p.y)).unwrap(),
+                )
+            };
+            let (a_var, b_var) = (alloc(a), alloc(b));
+
+            let num_constraints = cs.num_constraints();
+            let sum = a_var.add_checked(&b_var).unwrap();
+            assert_eq!(cs.num_constraints() - num_constraints, 15);
+            assert_eq!(sum.value().unwrap(), (a + b).into_affine());
+
+            let num_constraints = cs.num_constraints();
+            let sum = a_var.double_and_add_checked(&b_var).unwrap();
+            assert_eq!(cs.num_constraints() - num_constraints, 24);
+            assert_eq!(sum.value().unwrap(), (a + b + a).into_affine());
+
+            // Constants are handled natively.
+            let a_const = NonZeroAffineVar::<G1Config, FpVar<Fq>>::new(
+                FpVar::Constant(a.x),
+                FpVar::Constant(a.y),
+            );
+            let sum = a_const.add_checked(&b_var).unwrap();
+            assert_eq!(sum.value().unwrap(), (a + b).into_affine());
+
+            let b_const = NonZeroAffineVar::<G1Config, FpVar<Fq>>::new(
+                FpVar::Constant(b.x),
+                FpVar::Constant(b.y),
+            );
+            let num_constraints = cs.num_constraints();
+            let sum = a_const.double_and_add_checked(&b_const).unwrap();
+            assert_eq!(cs.num_constraints(), num_constraints);
+            assert!(sum.x.is_constant() && sum.y.is_constant());
+            assert_eq!(sum.value().unwrap(), (a + b + a).into_affine());
+
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn checked_addition_order_two_test() {
+        use ark_bls12_377::{g1::Config, Fq, G1Affine};
+
+        // `(-1, 0)` lies on `y^2 = x^3 + 1`, and has order two.
+        let t = G1Affine::new_unchecked(-Fq::one(), Fq::zero());
+        let g = Config::GENERATOR;
+        let cases = [(t, t), (g, t), (t, g)];
+        for (a, b) in cases {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let alloc = |p: G1Affine| {
+                NonZeroAffineVar::<Config, FpVar<Fq>>::new(
+                    FpVar::new_witness(cs.clone(), || Ok(p.x)).unwrap(),
+                    FpVar::new_witness(cs.clone(), || Ok(p.y)).unwrap(),
+                )
+            };
+            let (a_var, b_var) = (alloc(a), alloc(b));
+
+            let sum = a_var.add_checked(&b_var).unwrap();
+            assert_eq!(sum.value().unwrap(), (a + b).into_affine());
+            let sum = a_var.double_and_add_checked(&b_var).unwrap();
+            assert_eq!(sum.value().unwrap(), (a + b + a).into_affine());
+
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
 }
@@ -0,0 +1,206 @@
+use ark_ec::short_weierstrass::SWCurveConfig;
+
+use super::*;
+use crate::groups::curves::short_weierstrass::ProjectiveVar as SWProjectiveVar;
+
+/// Outputs the coefficients `(A, B)` of the Montgomery curve
+/// `B * v^2 = u^3 + A * u^2 + u` that is birationally equivalent to `P` via
+/// `u = (1 + y) / (1 - y)` and `v = u / x`, as in
+/// [`MontgomeryAffineVar::from_edwards_to_coords`].
+fn montgomery_coefficients<P: TEModelParameters>() -> (P::BaseField, P::BaseField) {
+    let (a, d) = (P::COEFF_A, P::COEFF_D);
+    let a_minus_d_inv = (a - d).inverse().unwrap();
+    (
+        (a + d).double() * a_minus_d_inv,
+        a_minus_d_inv.double().double(),
+    )
+}
+
+/// Outputs constants `(α, β_u, β_v)` such that `(u, v) ↦ (β_u * u + α, β_v * v)`
+/// is an isomorphism from the Montgomery curve of [`montgomery_coefficients`]
+/// to the short Weierstrass form of `P`, which maps the image of the twisted
+/// Edwards generator to the short Weierstrass generator.
+///
+/// # Panics
+///
+/// This function panics if no such isomorphism exists.
+fn weierstrass_constants<P>() -> (P::BaseField, P::BaseField, P::BaseField)
+where
+    P: TEModelParameters + SWCurveConfig,
+{
+    let one = P::BaseField::one();
+    let (a, b) = montgomery_coefficients::<P>();
+    let b_inv = b.inverse().unwrap();
+    let three_inv = P::BaseField::from(3u8).inverse().unwrap();
+
+    // `(u, v) ↦ (u / B + A / (3 * B), v / B)` maps the Montgomery curve to
+    // `y^2 = x^3 + a_0 * x + b_0`.
+    let a_0 = (P::BaseField::from(3u8) - a.square()) * three_inv * b_inv.square();
+    let b_0 = (a.square() * a.double() - a * P::BaseField::from(9u8))
+        * three_inv.square()
+        * three_inv
+        * b_inv.square()
+        * b_inv;
+    let te_generator = <P as TEModelParameters>::GENERATOR;
+    let u = (one + te_generator.y) * (one - te_generator.y).inverse().unwrap();
+    let v = u * te_generator.x.inverse().unwrap();
+    let (x_0, y_0) = ((u + a * three_inv) * b_inv, v * b_inv);
+
+    // The isomorphism to `y^2 = x^3 + s^4 * a_0 * x + s^6 * b_0` is
+    // `(x, y) ↦ (s^2 * x, s^3 * y)`.
+    let sw_generator = <P as SWCurveConfig>::GENERATOR;
+    let s = (sw_generator.y * x_0) * (y_0 * sw_generator.x).inverse().unwrap();
+    let (s_2, s_3) = (s.square(), s.square() * s);
+    assert!(
+        s_2 * x_0 == sw_generator.x
+            && s_3 * y_0 == sw_generator.y
+            && s_2.square() * a_0 == <P as SWCurveConfig>::COEFF_A
+            && s_3.square() * b_0 == <P as SWCurveConfig>::COEFF_B,
+        "the twisted Edwards and short Weierstrass forms are not isomorphic"
+    );
+    (s_2 * a * three_inv * b_inv, s_2 * b_inv, s_3 * b_inv)
+}
+
+/// Outputs the Montgomery point corresponding to `p` (as in
+/// [`MontgomeryAffineVar::from_edwards_to_coords`]), which is meaningless if
+/// `p` is the identity.
+fn edwards_to_montgomery<P, F>(
+    p: &AffineVar<P, F>,
+) -> Result<MontgomeryAffineVar<P, F>, SynthesisError>
+where
+    P: TEModelParameters,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    // Compute v = (1 + y) / ((1 - y) * x) and u = v * x. The denominator is zero
+    // only for the identity (0, 1) and the point of order two (0, -1), the
+    // latter of which is mapped to (0, 0).
+    let one = P::BaseField::one();
+    let denominator = (p.y.negate()? + one) * &p.x;
+    let denominator_is_zero = denominator.is_zero()?;
+    let v = (&p.y + one)
+        .mul_by_inverse_unchecked(&denominator_is_zero.select(&F::one(), &denominator)?)?;
+    let u = &v * &p.x;
+    Ok(MontgomeryAffineVar::new(u, v))
+}
+
+impl<P, F> MontgomeryAffineVar<P, F>
+where
+    P: TEModelParameters,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    /// Converts a Twisted Edwards curve point variable into the corresponding
+    /// affine Montgomery curve point, as in [`Self::from_edwards_to_coords`].
+    /// This maps `(0, -1)` to `(0, 0)`.
+    ///
+    /// The constraint system is unsatisfiable if `p` is the identity, since
+    /// `Self` cannot represent the point at infinity.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn from_edwards(p: &AffineVar<P, F>) -> Result<Self, SynthesisError> {
+        p.y.is_eq(&F::one())?.enforce_equal(&Boolean::FALSE)?;
+        edwards_to_montgomery(p)
+    }
+}
+
+impl<P, F> MontgomeryAffineVar<P, F>
+where
+    P: TEModelParameters + SWCurveConfig,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    /// Converts `self` into a point on the short Weierstrass form of `P`. As
+    /// this map is linear, it costs no constraints.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the short Weierstrass form of `P` is not
+    /// isomorphic to its twisted Edwards form via a map that sends the twisted
+    /// Edwards generator to the short Weierstrass generator.
+    #[tracing::instrument(target = "r1cs")]
+    pub fn into_short_weierstrass(&self) -> SWProjectiveVar<P, F> {
+        let (alpha, beta_u, beta_v) = weierstrass_constants::<P>();
+        SWProjectiveVar::new(&self.x * beta_u + alpha, &self.y * beta_v, F::one())
+    }
+
+    /// Converts a point on the short Weierstrass form of `P` into the
+    /// corresponding affine Montgomery curve point, the inverse of
+    /// [`Self::into_short_weierstrass`].
+    ///
+    /// The constraint system is unsatisfiable if `p` is the identity, since
+    /// `Self` cannot represent the point at infinity.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`Self::into_short_weierstrass`].
+    #[tracing::instrument(target = "r1cs")]
+    pub fn from_short_weierstrass(p: &SWProjectiveVar<P, F>) -> Result<Self, SynthesisError> {
+        let p = p.to_affine()?;
+        p.infinity.enforce_equal(&Boolean::FALSE)?;
+        let (alpha, beta_u, beta_v) = weierstrass_constants::<P>();
+        Ok(Self::new(
+            (p.x - alpha) * beta_u.inverse().unwrap(),
+            p.y * beta_v.inverse().unwrap(),
+        ))
+    }
+}
+
+impl<P, F> AffineVar<P, F>
+where
+    P: TEModelParameters + SWCurveConfig,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    /// Converts `self` into a point on the short Weierstrass form of `P`, via
+    /// the Montgomery form (see
+    /// [`MontgomeryAffineVar::into_short_weierstrass`]). The identity is
+    /// mapped to the identity.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`MontgomeryAffineVar::into_short_weierstrass`].
+    #[tracing::instrument(target = "r1cs")]
+    pub fn into_short_weierstrass(&self) -> Result<SWProjectiveVar<P, F>, SynthesisError> {
+        let is_identity = self.y.is_eq(&F::one())?;
+        let p = edwards_to_montgomery(self)?.into_short_weierstrass();
+        Ok(SWProjectiveVar::new(
+            is_identity.select(&F::zero(), &p.x)?,
+            is_identity.select(&F::one(), &p.y)?,
+            F::from(is_identity.not()),
+        ))
+    }
+
+    /// Converts a point on the short Weierstrass form of `P` into a twisted
+    /// Edwards point, the inverse of [`Self::into_short_weierstrass`]. The
+    /// identity is mapped to the identity.
+    ///
+    /// The constraint system is unsatisfiable if `p` has no affine image on
+    /// the twisted Edwards curve, which can only happen if the curve is not
+    /// complete.
+    ///
+    /// # Panics
+    ///
+    /// This method panics under the same conditions as
+    /// [`MontgomeryAffineVar::into_short_weierstrass`].
+    #[tracing::instrument(target = "r1cs")]
+    pub fn from_short_weierstrass(p: &SWProjectiveVar<P, F>) -> Result<Self, SynthesisError> {
+        let p = p.to_affine()?;
+        // Replace the identity by a point with an affine image, so that the
+        // conversion below is satisfiable.
+        let generator = <P as SWCurveConfig>::GENERATOR;
+        let x = p.infinity.select(&F::constant(generator.x), &p.x)?;
+        let y = p.infinity.select(&F::constant(generator.y), &p.y)?;
+        let (alpha, beta_u, beta_v) = weierstrass_constants::<P>();
+        let q = MontgomeryAffineVar::<P, F>::new(
+            (x - alpha) * beta_u.inverse().unwrap(),
+            y * beta_v.inverse().unwrap(),
+        )
+        .into_edwards()?;
+        Ok(Self::new(
+            p.infinity.select(&F::zero(), &q.x)?,
+            p.infinity.select(&F::one(), &q.y)?,
+        ))
+    }
+}
@@ -8,7 +8,14 @@ use ark_ec::{
 use ark_ff::{BigInteger, BitIteratorBE, Field, One, PrimeField, Zero};
 use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
 
-use crate::{prelude::*, ToConstraintFieldGadget, Vec};
+use crate::{
+    groups::{
+        compression::{field_from_bytes_with_flags, field_to_bytes_with_flags},
+        FixedBaseTable, PointCompressionGadget,
+    },
+    prelude::*,
+    ToConstraintFieldGadget, Vec,
+};
 
 use crate::fields::fp::FpVar;
 use ark_std::{borrow::Borrow, marker::PhantomData, ops::Mul};
@@ -36,6 +43,8 @@ pub struct MontgomeryAffineVar<
     _params: PhantomData<P>,
 }
 
+mod birational;
+
 mod montgomery_affine_impl {
     use super::*;
     use ark_ec::twisted_edwards::MontgomeryAffine as GroupAffine;
@@ -111,6 +120,11 @@ mod montgomery_affine_impl {
         }
 
         /// Converts `self` into a Twisted Edwards curve point variable.
+        ///
+        /// This is the inverse of [`Self::from_edwards`], and maps `(0, 0)` to
+        /// `(0, -1)`. The constraint system is unsatisfiable if `self` has no
+        /// affine image on the Twisted Edwards curve, which can only happen if
+        /// the curve is not complete.
         #[tracing::instrument(target = "r1cs")]
         pub fn into_edwards(&self) -> Result<AffineVar<P, F>, SynthesisError> {
             let cs = self.cs();
@@ -121,21 +135,13 @@ mod montgomery_affine_impl {
                 AllocationMode::Witness
             };
 
-            // Compute u = x / y
-            let u = F::new_variable(
-                ark_relations::ns!(cs, "u"),
-                || {
-                    let y_inv = self
-                        .y
-                        .value()?
-                        .inverse()
-                        .ok_or(SynthesisError::DivisionByZero)?;
-                    Ok(self.x.value()? * &y_inv)
-                },
-                mode,
-            )?;
-
-            u.mul_equals(&self.y, &self.x)?;
+            // Compute u = x / y. If y = 0, then x must be zero as well (i.e., `self`
+            // is the point of order two (0, 0)), in which case u = 0.
+            let y_is_zero = self.y.is_zero()?;
+            self.x.conditional_enforce_equal(&F::zero(), &y_is_zero)?;
+            let u = self
+                .x
+                .mul_by_inverse_unchecked(&y_is_zero.select(&F::one(), &self.y)?)?;
 
             let v = F::new_variable(
                 ark_relations::ns!(cs, "v"),
@@ -337,7 +343,6 @@ where
                 .iter()
                 .zip(segment_powers.borrow())
             {
-                let base_power = base_power.borrow();
                 let mut acc_power = *base_power;
                 let mut coords = vec![];
                 for _ in 0..4 {
@@ -384,6 +389,66 @@ where
         }
         Ok(ed_result.unwrap())
     }
+
+    /// Computes `bits * table.base()`, where `bits` is a little-endian
+    /// `Boolean` representation of a scalar, using 3-bit signed-digit windows
+    /// over the precomputed `table`.
+    ///
+    /// Like [`Self::precomputed_base_3_bit_signed_digit_scalar_mul`], this
+    /// performs the additions on the corresponding Montgomery curve, so that
+    /// each window costs a lookup into constant tables and a single
+    /// incomplete addition. This is cheaper than the lookup and complete
+    /// addition per 2 bits performed by [`CurveVar::scalar_mul_le`] on
+    /// constant points.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `bits` has more than `table.max_num_bits()`
+    /// elements.
+    #[tracing::instrument(target = "r1cs", skip(table, bits))]
+    pub fn fixed_base_scalar_mul_le<'a>(
+        table: &FixedBaseTable<TEProjective<P>>,
+        bits: impl Iterator<Item = &'a Boolean<<P::BaseField as Field>::BasePrimeField>>,
+    ) -> Result<Self, SynthesisError> {
+        let bits = bits.cloned().collect::<Vec<_>>();
+        assert!(
+            bits.len() <= table.max_num_bits(),
+            "the scalar is too large for the table"
+        );
+        if bits.is_empty() || table.base().is_zero() {
+            return Ok(Self::zero());
+        }
+        let digits = crate::groups::three_bit_signed_digits(&bits)?;
+
+        // The incomplete Montgomery formulae are safe within chunks of windows
+        // for the same reason as in `ProjectiveVar::fixed_base_scalar_mul_le`.
+        let scalar_modulus_bits = <P::ScalarField as PrimeField>::MODULUS_BIT_SIZE as usize;
+        let windows_per_chunk = (scalar_modulus_bits - 1) / 3;
+
+        let mut result = Self::zero();
+        for (chunk_index, chunk) in digits.chunks(windows_per_chunk).enumerate() {
+            let mut accumulator: Option<MontgomeryAffineVar<P, F>> = None;
+            for (i, (digit_bits, j0_and_j1)) in chunk.iter().enumerate() {
+                let multiples = table.window(chunk_index * windows_per_chunk + i);
+                let coords = multiples
+                    .iter()
+                    .map(MontgomeryAffineVar::<P, F>::from_edwards_to_coords)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let xs = coords.iter().map(|p| p.0).collect::<Vec<_>>();
+                let ys = coords.iter().map(|p| p.1).collect::<Vec<_>>();
+                let x = F::two_bit_lookup(&digit_bits[..2], &xs)?;
+                let y = F::three_bit_cond_neg_lookup(digit_bits, j0_and_j1, &ys)?;
+                let multiple = MontgomeryAffineVar::new(x, y);
+                accumulator = Some(match accumulator {
+                    Some(accumulator) => accumulator + &multiple,
+                    None => multiple,
+                });
+            }
+            result += accumulator.unwrap().into_edwards()?;
+        }
+        // If `bits[0]` is 0, we have to subtract the base to undo the recoding.
+        bits[0].select(&result, &(result.clone() - table.base()))
+    }
 }
 
 impl<P, F> R1CSVar<<P::BaseField as Field>::BasePrimeField> for AffineVar<P, F>
@@ -401,7 +466,7 @@ where
     #[inline]
     fn value(&self) -> Result<TEProjective<P>, SynthesisError> {
         let (x, y) = (self.x.value()?, self.y.value()?);
-        let result = TEAffine::new(x, y);
+        let result = TEAffine::new_unchecked(x, y);
         Ok(result.into())
     }
 }
@@ -560,6 +625,62 @@ where
     }
 }
 
+impl<P, F> PointCompressionGadget<TEProjective<P>, <P::BaseField as Field>::BasePrimeField>
+    for AffineVar<P, F>
+where
+    P: TEModelParameters,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>
+        + TwoBitLookupGadget<<P::BaseField as Field>::BasePrimeField, TableConstant = P::BaseField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    #[tracing::instrument(target = "r1cs")]
+    fn to_compressed_bytes(
+        &self,
+    ) -> Result<Vec<UInt8<<P::BaseField as Field>::BasePrimeField>>, SynthesisError> {
+        let x_is_negative = self.x.is_positive()?.not();
+        field_to_bytes_with_flags(&self.y, &[x_is_negative])
+    }
+
+    #[tracing::instrument(target = "r1cs", skip(bytes))]
+    fn from_compressed_bytes(
+        bytes: &[UInt8<<P::BaseField as Field>::BasePrimeField>],
+    ) -> Result<Self, SynthesisError> {
+        let (y, flags) = field_from_bytes_with_flags::<P::BaseField, _, F>(bytes, 1)?;
+        let x_is_negative = &flags[0];
+
+        // Recover `x` from `x^2 = (1 - y^2) / (a - d * y^2)`.
+        let y_squared = y.square()?;
+        let numerator = F::one() - &y_squared;
+        let denominator = (&y_squared * P::COEFF_D).negate()? + P::COEFF_A;
+        let x_squared = numerator.mul_by_inverse(&denominator)?;
+        let x = x_squared.sqrt_with_sign(&x_is_negative.not())?;
+        let point = Self::new(x, y);
+
+        // Enforce that `point` is in the prime-order subgroup, by checking that
+        // `point = cofactor * q` for a witness point `q`.
+        let cs = point.cs();
+        let mode = if cs.is_none() {
+            AllocationMode::Constant
+        } else {
+            AllocationMode::Witness
+        };
+        let q = Self::new_variable_omit_prime_order_check(
+            ark_relations::ns!(cs, "point / cofactor"),
+            || Ok(point.value()?.into_affine().mul_by_cofactor_inv().into()),
+            mode,
+        )?;
+        let mut result = Self::zero();
+        for b in BitIteratorBE::without_leading_zeros(P::COFACTOR) {
+            result.double_in_place()?;
+            if b {
+                result += &q;
+            }
+        }
+        result.enforce_equal(&point)?;
+        Ok(point)
+    }
+}
+
 impl<P, F> AllocVar<TEProjective<P>, <P::BaseField as Field>::BasePrimeField> for AffineVar<P, F>
 where
     P: TEModelParameters,
@@ -609,7 +730,7 @@ where
                 let (mut ge, iter) = if cofactor_weight < modulus_minus_1_weight {
                     let ge = Self::new_variable_omit_prime_order_check(
                         ark_relations::ns!(cs, "Witness without subgroup check with cofactor mul"),
-                        || f().map(|g| g.borrow().into_affine().mul_by_cofactor_inv().into()),
+                        || f().map(|g| g.into_affine().mul_by_cofactor_inv().into()),
                         mode,
                     )?;
                     (
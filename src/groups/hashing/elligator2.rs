@@ -0,0 +1,103 @@
+use ark_ec::twisted_edwards::{
+    MontCurveConfig, Projective as TEProjective, TECurveConfig as TEModelParameters,
+};
+use ark_ff::{Field, One};
+use ark_relations::r1cs::SynthesisError;
+use ark_std::marker::PhantomData;
+
+use super::{sgn0_native, sqrt_or_sqrt_of_non_square, with_sgn0, MapToCurveGadget};
+use crate::{
+    fields::{FieldOpsBounds, FieldVar},
+    groups::curves::twisted_edwards::AffineVar,
+    select::TwoBitLookupGadget,
+};
+
+/// The Elligator 2 map for twisted Edwards curves, as specified in
+/// [RFC 9380, Section 6.8.2](https://www.rfc-editor.org/rfc/rfc9380.html#section-6.8.2).
+///
+/// This maps to the Montgomery curve `K * t^2 = s^3 + J * s^2 + s` that is
+/// birationally equivalent to `P`, followed by the rational map to `P`.
+/// Here, `J` is `P::MontCurveConfig::COEFF_A`, which must be non-zero. If
+/// `B = P::MontCurveConfig::COEFF_B` is a square, then `K = 1` and the
+/// rational map is scaled by the square root `c` of `B` with
+/// `sgn0(c) = 0`, as RFC 9380 does for edwards25519. Otherwise, `K = B`.
+///
+/// The non-square `Z` is the first non-square in the sequence
+/// `1, -1, 2, -2, ...`, which matches `find_z_ell2` of RFC 9380 for prime
+/// fields.
+pub struct Elligator2MapGadget<P, F>(PhantomData<(P, F)>);
+
+/// Returns the first non-square in the sequence `1, -1, 2, -2, ...`.
+fn find_z<F: Field>() -> F {
+    let mut ctr = F::one();
+    loop {
+        for candidate in [ctr, -ctr] {
+            if candidate.legendre().is_qnr() {
+                return candidate;
+            }
+        }
+        ctr += F::one();
+    }
+}
+
+impl<P, F> MapToCurveGadget<TEProjective<P>, <P::BaseField as Field>::BasePrimeField>
+    for Elligator2MapGadget<P, F>
+where
+    P: TEModelParameters,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>
+        + TwoBitLookupGadget<<P::BaseField as Field>::BasePrimeField, TableConstant = P::BaseField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    type CurveVar = AffineVar<P, F>;
+    type BaseFieldVar = F;
+
+    #[tracing::instrument(target = "r1cs")]
+    fn map_to_curve(u: &F) -> Result<AffineVar<P, F>, SynthesisError> {
+        let one = P::BaseField::one();
+        let j = P::MontCurveConfig::COEFF_A;
+        let b = P::MontCurveConfig::COEFF_B;
+        let (k, c) = match b.sqrt() {
+            Some(c) if sgn0_native(&c) => (one, -c),
+            Some(c) => (one, c),
+            None => (b, one),
+        };
+        let z = find_z::<P::BaseField>();
+        let j_over_k = j * k.inverse().unwrap();
+        let k_squared_inv = k.square().inverse().unwrap();
+
+        // `x1 = -(J / K) / (1 + Z * u^2)`, or `x1 = -(J / K)` if the denominator
+        // is zero.
+        let tv = u.square()? * z;
+        let den = tv + one;
+        let den_is_zero = den.is_zero()?;
+        let x1 = F::constant(-j_over_k)
+            .mul_by_inverse_unchecked(&den_is_zero.select(&F::one(), &den)?)?;
+        let gx1 = ((x1.clone() + j_over_k) * &x1 + k_squared_inv) * &x1;
+
+        // If the denominator is non-zero, then `x2 = -x1 - J / K` satisfies
+        // `g(x2) = Z * u^2 * g(x1)`. So if `g(x1)` is not a square, then
+        // `u * sqrt(Z * g(x1))` is a square root of `g(x2)`. Otherwise, `x2 = 0`
+        // and `g(x2) = 0`.
+        let x2 = x1.negate()? - j_over_k;
+        let (r, gx1_is_square) = sqrt_or_sqrt_of_non_square(&gx1, z)?;
+        let x = gx1_is_square.select(&x1, &x2)?;
+        let y2 = den_is_zero.select(&F::zero(), &(u * &r))?;
+        let y = gx1_is_square.select(&r, &y2)?;
+        let y = with_sgn0(&y, &gx1_is_square)?;
+        let s = x * k;
+        let t = y * k;
+
+        // Apply the rational map `(c * s / t, (s - 1) / (s + 1))`, which sends
+        // points with `t * (s + 1) = 0` to the identity.
+        let s_plus_one = s.clone() + one;
+        let is_identity = (&t * &s_plus_one).is_zero()?;
+        let t = is_identity.select(&F::one(), &t)?;
+        let s_plus_one = is_identity.select(&F::one(), &s_plus_one)?;
+        let x = (s.clone() * c).mul_by_inverse_unchecked(&t)?;
+        let y = (s - one).mul_by_inverse_unchecked(&s_plus_one)?;
+        Ok(AffineVar::new(
+            is_identity.select(&F::zero(), &x)?,
+            is_identity.select(&F::one(), &y)?,
+        ))
+    }
+}
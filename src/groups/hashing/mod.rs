@@ -0,0 +1,144 @@
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use ark_relations::r1cs::SynthesisError;
+
+use crate::{
+    fields::field_hashers::{hash_to_field, DigestGadget},
+    prelude::*,
+};
+
+/// This module contains the simplified SWU map for short Weierstrass curves,
+/// and its composition with an isogeny for curves such as BLS12-381.
+pub mod swu;
+
+/// This module contains the Elligator 2 map for twisted Edwards curves.
+pub mod elligator2;
+
+pub use elligator2::Elligator2MapGadget;
+pub use swu::{SWUMapGadget, WBMapGadget};
+
+/// Trait for gadgets that map base field elements to points on the curve
+/// `C`. That is, this is the R1CS equivalent of
+/// `ark_ec::hashing::map_to_curve_hasher::MapToCurve`.
+pub trait MapToCurveGadget<C: CurveGroup, ConstraintF: PrimeField> {
+    /// The type of variables representing points on `C`.
+    type CurveVar: CurveVar<C, ConstraintF>;
+    /// The type of variables representing elements of the base field of `C`.
+    type BaseFieldVar: FieldVar<C::BaseField, ConstraintF>;
+
+    /// Maps `u` to a point on the curve, as specified by `map_to_curve` in
+    /// RFC 9380. The output need not be in the prime-order subgroup.
+    fn map_to_curve(u: &Self::BaseFieldVar) -> Result<Self::CurveVar, SynthesisError>;
+
+    /// Maps `point` into the prime-order subgroup, as specified by
+    /// `clear_cofactor` in RFC 9380.
+    ///
+    /// By default, this calls [`CurveVar::clear_cofactor`]. If `h_eff` of the
+    /// hash-to-curve suite differs from the effective cofactor used there,
+    /// this method should be overridden.
+    #[tracing::instrument(target = "r1cs")]
+    fn clear_cofactor(point: &Self::CurveVar) -> Result<Self::CurveVar, SynthesisError> {
+        point.clear_cofactor()
+    }
+}
+
+/// Implements `hash_to_curve` from [RFC 9380, Section 3](https://www.rfc-editor.org/rfc/rfc9380.html#section-3),
+/// which hashes `msg` to a point in the prime-order subgroup of `C`, using
+/// [`hash_to_field`] with the hash function `H`, the map `M`, and the
+/// domain separation tag `dst`.
+#[tracing::instrument(target = "r1cs", skip(msg))]
+pub fn hash_to_curve<C, ConstraintF, M, H>(
+    msg: &[UInt8<ConstraintF>],
+    dst: &[u8],
+) -> Result<M::CurveVar, SynthesisError>
+where
+    C: CurveGroup,
+    ConstraintF: PrimeField,
+    M: MapToCurveGadget<C, ConstraintF>,
+    H: DigestGadget<ConstraintF>,
+{
+    let u = hash_to_field::<C::BaseField, ConstraintF, M::BaseFieldVar, H>(msg, dst, 2)?;
+    let q0 = M::map_to_curve(&u[0])?;
+    let q1 = M::map_to_curve(&u[1])?;
+    M::clear_cofactor(&(q0 + q1))
+}
+
+/// Outputs `sgn0(f)` as specified in [RFC 9380, Section 4.1](https://www.rfc-editor.org/rfc/rfc9380.html#section-4.1),
+/// i.e., the parity of the first non-zero component of `f`.
+fn sgn0<F, ConstraintF, FV>(f: &FV) -> Result<Boolean<ConstraintF>, SynthesisError>
+where
+    F: Field,
+    ConstraintF: PrimeField,
+    FV: FieldVar<F, ConstraintF>,
+{
+    let bits = f.to_bits_le()?;
+    let num_components = F::extension_degree() as usize;
+    let mut result = Boolean::FALSE;
+    for component in bits.chunks(bits.len() / num_components).rev() {
+        let is_zero = Boolean::kary_or(component)?.not();
+        result = is_zero.select(&result, &component[0])?;
+    }
+    Ok(result)
+}
+
+/// Negates `y` if necessary, such that `sgn0(y)` is equal to `sign`. (If
+/// `y` is zero, then it is returned unchanged.)
+fn with_sgn0<F, ConstraintF, FV>(y: &FV, sign: &Boolean<ConstraintF>) -> Result<FV, SynthesisError>
+where
+    F: Field,
+    ConstraintF: PrimeField,
+    FV: FieldVar<F, ConstraintF>,
+{
+    sgn0(y)?.is_eq(sign)?.select(y, &y.negate()?)
+}
+
+/// Allocates a square root `r` of either `g` (if `g` is a square) or of
+/// `z * g` (otherwise), where `z` is a non-square, and outputs `r` along
+/// with a `Boolean` representing whether `g` is a square.
+///
+/// These checks ensure that the `Boolean` is correct:
+/// * If it is true, then `r^2 = g`, so `g` is a square.
+/// * If it is false, then `r^2 = z * g` and `r != 0`, so `g` is not a square.
+fn sqrt_or_sqrt_of_non_square<F, ConstraintF, FV>(
+    g: &FV,
+    z: F,
+) -> Result<(FV, Boolean<ConstraintF>), SynthesisError>
+where
+    F: Field,
+    ConstraintF: PrimeField,
+    FV: FieldVar<F, ConstraintF>,
+{
+    let cs = g.cs();
+    let mode = if cs.is_none() {
+        AllocationMode::Constant
+    } else {
+        AllocationMode::Witness
+    };
+    let is_square = Boolean::new_variable(
+        ark_relations::ns!(cs, "is square"),
+        || Ok(!g.value()?.legendre().is_qnr()),
+        mode,
+    )?;
+    let r = FV::new_variable(
+        ark_relations::ns!(cs, "sqrt"),
+        || {
+            let g = g.value()?;
+            let root = g.sqrt().or_else(|| (z * g).sqrt());
+            root.ok_or(SynthesisError::Unsatisfiable)
+        },
+        mode,
+    )?;
+    let r_squared = is_square.select(g, &(g.clone() * z))?;
+    r.square_equals(&r_squared)?;
+    is_square
+        .or(&r.is_zero()?.not())?
+        .enforce_equal(&Boolean::TRUE)?;
+    Ok((r, is_square))
+}
+
+/// The native analogue of [`sgn0`].
+fn sgn0_native<F: Field>(f: &F) -> bool {
+    f.to_base_prime_field_elements()
+        .find(|c| !c.is_zero())
+        .is_some_and(|c| c.into_bigint().is_odd())
+}
@@ -0,0 +1,137 @@
+use ark_ec::{
+    hashing::curve_maps::{swu::SWUConfig, wb::WBConfig},
+    short_weierstrass::Projective as SWProjective,
+};
+use ark_ff::{Field, One};
+use ark_relations::r1cs::SynthesisError;
+use ark_std::marker::PhantomData;
+
+use super::{sgn0, sqrt_or_sqrt_of_non_square, with_sgn0, MapToCurveGadget};
+use crate::{
+    fields::{FieldOpsBounds, FieldVar},
+    groups::curves::short_weierstrass::ProjectiveVar,
+    Vec,
+};
+
+/// The simplified SWU map for short Weierstrass curves `y^2 = x^3 + a*x + b`
+/// with `a * b != 0`, as specified in [RFC 9380, Section 6.6.2](https://www.rfc-editor.org/rfc/rfc9380.html#section-6.6.2).
+/// That is, this is the R1CS equivalent of
+/// `ark_ec::hashing::curve_maps::swu::SWUMap`.
+pub struct SWUMapGadget<P, F>(PhantomData<(P, F)>);
+
+/// The simplified SWU map for the curve isogenous to `P`, followed by the
+/// isogeny to `P`, as specified in [RFC 9380, Section 6.6.3](https://www.rfc-editor.org/rfc/rfc9380.html#section-6.6.3).
+/// This is used for curves with `a * b = 0`, such as BLS12-381. That is, this
+/// is the R1CS equivalent of `ark_ec::hashing::curve_maps::wb::WBMap`.
+pub struct WBMapGadget<P, F>(PhantomData<(P, F)>);
+
+/// Outputs the affine coordinates of the image of `u` under the simplified
+/// SWU map for `P`.
+#[tracing::instrument(target = "r1cs")]
+fn map_to_curve_swu<P, F>(u: &F) -> Result<(F, F), SynthesisError>
+where
+    P: SWUConfig,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    let (a, b, z) = (P::COEFF_A, P::COEFF_B, P::ZETA);
+
+    // As in `SWUMap`, we compute `x1 = num_x1 / div`, where `tv = Z * u^2`,
+    // `ta = tv^2 + tv`, `num_x1 = B * (ta + 1)`, and `div = -A * ta` (or
+    // `div = A * Z` if `ta == 0`). Since `A` and `Z` are non-zero, so is `div`.
+    let tv = u.square()? * z;
+    let ta = tv.square()? + &tv;
+    let num_x1 = (ta.clone() + P::BaseField::one()) * b;
+    let div = ta.is_zero()?.select(&F::constant(z), &ta.negate()?)? * a;
+    let x1 = num_x1.mul_by_inverse_unchecked(&div)?;
+    let gx1 = (x1.square()? + a) * &x1 + b;
+
+    // If `ta != 0`, then `x2 = tv * x1` satisfies `g(x2) = tv^3 * g(x1)`. So if
+    // `g(x1)` is not a square, then `tv * u * sqrt(Z * g(x1))` is a square root
+    // of `g(x2)`. (If `ta == 0`, then `g(x1)` is a square by the choice of `Z`.)
+    let x2 = &tv * &x1;
+    let (r, gx1_is_square) = sqrt_or_sqrt_of_non_square(&gx1, z)?;
+    let x = gx1_is_square.select(&x1, &x2)?;
+    let y = gx1_is_square.select(&r, &(&tv * u * &r))?;
+    let y = with_sgn0(&y, &sgn0(u)?)?;
+    Ok((x, y))
+}
+
+impl<P, F> MapToCurveGadget<SWProjective<P>, <P::BaseField as Field>::BasePrimeField>
+    for SWUMapGadget<P, F>
+where
+    P: SWUConfig,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    type CurveVar = ProjectiveVar<P, F>;
+    type BaseFieldVar = F;
+
+    #[tracing::instrument(target = "r1cs")]
+    fn map_to_curve(u: &F) -> Result<ProjectiveVar<P, F>, SynthesisError> {
+        let (x, y) = map_to_curve_swu::<P, F>(u)?;
+        Ok(ProjectiveVar::new(x, y, F::one()))
+    }
+}
+
+impl<P, F> MapToCurveGadget<SWProjective<P>, <P::BaseField as Field>::BasePrimeField>
+    for WBMapGadget<P, F>
+where
+    P: WBConfig,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::BaseField, F>,
+{
+    type CurveVar = ProjectiveVar<P, F>;
+    type BaseFieldVar = F;
+
+    #[tracing::instrument(target = "r1cs")]
+    fn map_to_curve(u: &F) -> Result<ProjectiveVar<P, F>, SynthesisError> {
+        let (x, y) = map_to_curve_swu::<P::IsogenousCurve, F>(u)?;
+
+        // Evaluate the rational maps `x' = x_num(x) / x_den(x)` and
+        // `y' = y * y_num(x) / y_den(x)` of the isogeny.
+        let isogeny = P::ISOGENY_MAP;
+        let degree = [
+            isogeny.x_map_numerator,
+            isogeny.x_map_denominator,
+            isogeny.y_map_numerator,
+            isogeny.y_map_denominator,
+        ]
+        .iter()
+        .map(|coeffs| coeffs.len())
+        .max()
+        .unwrap_or(1);
+        let mut powers = Vec::with_capacity(degree);
+        powers.push(F::one());
+        for i in 1..degree {
+            powers.push(if i == 1 {
+                x.clone()
+            } else {
+                &powers[i - 1] * &x
+            });
+        }
+        let evaluate = |coeffs: &[P::BaseField]| {
+            coeffs
+                .iter()
+                .zip(&powers)
+                .fold(F::zero(), |acc, (c, power)| acc + power.clone() * *c)
+        };
+        let x_num = evaluate(isogeny.x_map_numerator);
+        let x_den = evaluate(isogeny.x_map_denominator);
+        let y_num = evaluate(isogeny.y_map_numerator);
+        let y_den = evaluate(isogeny.y_map_denominator);
+
+        // The denominators vanish exactly on the kernel of the isogeny, which
+        // is mapped to the identity.
+        let is_identity = x_den.is_zero()?;
+        let x_den = is_identity.select(&F::one(), &x_den)?;
+        let y_den = is_identity.select(&F::one(), &y_den)?;
+        let x = x_num.mul_by_inverse_unchecked(&x_den)?;
+        let y = (y * y_num).mul_by_inverse(&y_den)?;
+        Ok(ProjectiveVar::new(
+            is_identity.select(&F::zero(), &x)?,
+            is_identity.select(&F::one(), &y)?,
+            F::from(is_identity.not()),
+        ))
+    }
+}
@@ -1,15 +1,22 @@
-use crate::prelude::*;
+use crate::{prelude::*, Vec};
 use ark_ff::Field;
 use ark_relations::r1cs::{Namespace, SynthesisError};
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 
-use ark_ec::CurveGroup;
+use ark_ec::{CurveConfig, CurveGroup};
+use ark_ff::{BitIteratorBE, PrimeField};
 use core::{borrow::Borrow, fmt::Debug};
 
 /// This module contains implementations of arithmetic for various curve models.
 pub mod curves;
 
-pub use self::curves::short_weierstrass::{bls12, mnt4, mnt6};
+mod compression;
+
+/// This module contains gadgets for hashing to curves, as specified in RFC 9380.
+/// That is, it implements the R1CS equivalent of `ark_ec::hashing`.
+pub mod hashing;
+
+pub use self::curves::short_weierstrass::{bls12, bn, bw6, mnt4, mnt6};
 
 /// A hack used to work around the lack of implied bounds.
 pub trait GroupOpsBounds<'a, F, T: 'a>:
@@ -71,6 +78,29 @@ pub trait CurveVar<C: CurveGroup, ConstraintF: Field>:
     /// Enforce that `self` is in the prime-order subgroup.
     fn enforce_prime_order(&self) -> Result<(), SynthesisError>;
 
+    /// Maps `self` into the prime-order subgroup, by default by multiplying
+    /// it by the cofactor of `C`.
+    ///
+    /// Unlike [`CurveVar::enforce_prime_order`], this does not constrain
+    /// `self`, which makes it suitable for points that need not lie in the
+    /// prime-order subgroup, such as the output of a map to the curve or a
+    /// point from an untrusted source.
+    ///
+    /// Some curves admit a cheaper effective cofactor, such as the one of
+    /// [`crate::groups::bls12::clear_g2_cofactor`], which uses the
+    /// endomorphism `ψ` of the twist of BLS12 curves. For short Weierstrass
+    /// curves, the method is chosen by the
+    /// [`ClearCofactorConfig`](curves::short_weierstrass::ClearCofactorConfig)
+    /// parameter of
+    /// [`ProjectiveVar`](curves::short_weierstrass::ProjectiveVar), and the
+    /// `G1Var` and `G2Var` of [`bls12`] use the effective cofactors. Note that
+    /// the effective cofactor may yield a different point in the prime-order
+    /// subgroup than the cofactor does.
+    #[tracing::instrument(target = "r1cs")]
+    fn clear_cofactor(&self) -> Result<Self, SynthesisError> {
+        mul_by_cofactor(self)
+    }
+
     /// Computes `self + self`.
     #[tracing::instrument(target = "r1cs")]
     fn double(&self) -> Result<Self, SynthesisError> {
@@ -92,8 +122,16 @@ pub trait CurveVar<C: CurveGroup, ConstraintF: Field>:
         &self,
         bits: impl Iterator<Item = &'a Boolean<ConstraintF>>,
     ) -> Result<Self, SynthesisError> {
-        // TODO: in the constant case we should call precomputed_scalar_mul_le,
-        // but rn there's a bug when doing this with TE curves.
+        // If `self` is a constant, its power-of-two multiples can be computed
+        // outside the circuit.
+        if self.is_constant() {
+            // The value should exist because `self` is a constant.
+            let bits = bits.collect::<Vec<_>>();
+            let multiples = power_of_two_multiples(self.value()?, bits.len());
+            let mut res = Self::zero();
+            res.precomputed_base_scalar_mul_le(bits.into_iter().zip(&multiples))?;
+            return Ok(res);
+        }
 
         // Computes the standard little-endian double-and-add algorithm
         // (Algorithm 3.26, Guide to Elliptic Curve Cryptography)
@@ -107,6 +145,224 @@ pub trait CurveVar<C: CurveGroup, ConstraintF: Field>:
         Ok(res)
     }
 
+    /// Computes `bits * self`, where `bits` is a little-endian
+    /// `Boolean` representation of a scalar, using a fixed-window method.
+    ///
+    /// The multiples `0 * self, ..., (2^window_size - 1) * self` are computed
+    /// once, after which every window of `window_size` bits costs
+    /// `window_size` doublings, a lookup into this table, and a single
+    /// addition. Whether this is cheaper than [`CurveVar::scalar_mul_le`]
+    /// depends on the cost of the group operations of the implementation.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `window_size == 0`.
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    fn scalar_mul_le_windowed<'a>(
+        &self,
+        bits: impl Iterator<Item = &'a Boolean<ConstraintF>>,
+        window_size: usize,
+    ) -> Result<Self, SynthesisError> {
+        assert!(window_size > 0, "the window size must be positive");
+        let bits = bits.cloned().collect::<Vec<_>>();
+
+        // table[i] = i * self
+        let mut table = vec![Self::zero(), self.clone()];
+        for i in 2..(1 << window_size) {
+            let multiple = if i % 2 == 0 {
+                table[i / 2].double()?
+            } else {
+                table[i - 1].clone() + self
+            };
+            table.push(multiple);
+        }
+
+        // Computes the fixed-window big-endian double-and-add algorithm
+        // (Algorithm 3.36, Guide to Elliptic Curve Cryptography)
+        let mut result = Self::zero();
+        for window in bits.chunks(window_size).rev() {
+            for _ in 0..window_size {
+                result.double_in_place()?;
+            }
+            let mut position = window.to_vec();
+            position.resize(window_size, Boolean::FALSE);
+            position.reverse();
+            result += Self::conditionally_select_power_of_two_vector(&position, &table)?;
+        }
+        Ok(result)
+    }
+
+    /// Computes `bits * self`, where `bits` is a little-endian
+    /// `Boolean` representation of a scalar, using a fixed-window method with
+    /// signed digits.
+    ///
+    /// The scalar is recoded (at no cost) into digits in
+    /// `{±1, ±3, ..., ±(2^window_size - 1)}`, so that only the odd multiples
+    /// `self, 3 * self, ..., (2^window_size - 1) * self` need to be computed.
+    /// Compared to [`CurveVar::scalar_mul_le_windowed`], this halves the size
+    /// of the table, at the cost of `window_size - 1` XORs and a conditional
+    /// negation per window.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `window_size == 0`.
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    fn scalar_mul_le_signed_digit<'a>(
+        &self,
+        bits: impl Iterator<Item = &'a Boolean<ConstraintF>>,
+        window_size: usize,
+    ) -> Result<Self, SynthesisError> {
+        assert!(window_size > 0, "the window size must be positive");
+        let bits = bits.cloned().collect::<Vec<_>>();
+        if bits.is_empty() {
+            return Ok(Self::zero());
+        }
+
+        // Let `k` be the scalar, padded to `n` bits, where `n` is a multiple of
+        // `window_size`. Then `k' = k | 1 = Σᵢ 2ⁱ * (2 * b[i] - 1)`, where
+        // `b = [k[1], ..., k[n - 1], 1]`. Each window of `b` thus encodes an odd
+        // signed digit, and we correct for the lowest bit of `k` at the end.
+        let num_bits = bits.len().div_ceil(window_size) * window_size;
+        let mut recoded_bits = bits[1..].to_vec();
+        recoded_bits.resize(num_bits - 1, Boolean::FALSE);
+        recoded_bits.push(Boolean::TRUE);
+
+        // table[i] = (2i + 1) * self
+        let double = self.double()?;
+        let mut table = vec![self.clone()];
+        for i in 1..(1 << (window_size - 1)) {
+            table.push(table[i - 1].clone() + &double);
+        }
+
+        let mut result = Self::zero();
+        for window in recoded_bits.chunks(window_size).rev() {
+            for _ in 0..window_size {
+                result.double_in_place()?;
+            }
+            // The digit `Σᵢ 2ⁱ * (2 * window[i] - 1)` is positive iff the top bit is
+            // set. Its absolute value is `2j + 1`, where `j` consists of the
+            // remaining bits, complemented if the digit is negative.
+            let (is_positive, rest) = window.split_last().unwrap();
+            let position = rest
+                .iter()
+                .rev()
+                .map(|b| b.xor(&is_positive.not()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let multiple = Self::conditionally_select_power_of_two_vector(&position, &table)?;
+            result += is_positive.select(&multiple, &multiple.negate()?)?;
+        }
+        bits[0].select(&result, &(result.clone() - self))
+    }
+
+    /// Computes `Σᵢ(scalarᵢ * baseᵢ)`, where `scalarᵢ` is a `Boolean`
+    /// *little-endian* representation of the i-th scalar, and both the bases
+    /// and the scalars may be variables.
+    ///
+    /// This interleaves the signed-digit method of
+    /// [`CurveVar::scalar_mul_le_signed_digit`] across all terms (Straus's
+    /// method), so that the doublings are shared: after a table of odd
+    /// multiples has been computed for every base, each window costs
+    /// `window_size` doublings in total, plus a lookup and an addition per
+    /// term. Since only the complete group operations of `Self` are used,
+    /// bases (and partial sums) equal to the identity are handled correctly.
+    /// As with the other windowed methods, whether this is cheaper than
+    /// separate calls to [`CurveVar::scalar_mul_le`] depends on the cost of
+    /// the group operations of the implementation.
+    ///
+    /// The window size is 1 for scalars shorter than 32 bits, and 3
+    /// otherwise; use [`CurveVar::variable_base_msm_with_window`] to choose
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `bases.len() != scalars.len()`.
+    #[tracing::instrument(target = "r1cs", skip(bases, scalars))]
+    fn variable_base_msm(
+        bases: &[Self],
+        scalars: &[Vec<Boolean<ConstraintF>>],
+    ) -> Result<Self, SynthesisError> {
+        let max_num_bits = scalars.iter().map(Vec::len).max().unwrap_or(0);
+        // Larger windows only pay off once the tables are amortized over enough
+        // windows.
+        let window_size = if max_num_bits < 32 { 1 } else { 3 };
+        Self::variable_base_msm_with_window(bases, scalars, window_size)
+    }
+
+    /// Computes `Σᵢ(scalarᵢ * baseᵢ)` as in [`CurveVar::variable_base_msm`],
+    /// using windows of `window_size` bits, i.e. tables of
+    /// `2^(window_size - 1)` odd multiples of each base.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `bases.len() != scalars.len()`, or if
+    /// `window_size == 0`.
+    #[tracing::instrument(target = "r1cs", skip(bases, scalars))]
+    fn variable_base_msm_with_window(
+        bases: &[Self],
+        scalars: &[Vec<Boolean<ConstraintF>>],
+        window_size: usize,
+    ) -> Result<Self, SynthesisError> {
+        assert_eq!(
+            bases.len(),
+            scalars.len(),
+            "the number of bases and scalars must be equal"
+        );
+        assert!(window_size > 0, "the window size must be positive");
+        let terms = bases
+            .iter()
+            .zip(scalars)
+            .filter(|(_, bits)| !bits.is_empty())
+            .collect::<Vec<_>>();
+        let max_num_bits = terms.iter().map(|(_, bits)| bits.len()).max();
+        let max_num_bits = match max_num_bits {
+            Some(n) => n,
+            None => return Ok(Self::zero()),
+        };
+        let num_bits = max_num_bits.div_ceil(window_size) * window_size;
+
+        // See `scalar_mul_le_signed_digit` for the recoding.
+        let mut recoded_scalars = Vec::with_capacity(terms.len());
+        let mut tables = Vec::with_capacity(terms.len());
+        for (base, bits) in &terms {
+            let mut recoded_bits = bits[1..].to_vec();
+            recoded_bits.resize(num_bits - 1, Boolean::FALSE);
+            recoded_bits.push(Boolean::TRUE);
+            recoded_scalars.push(recoded_bits);
+
+            // table[i] = (2i + 1) * base
+            let mut table = vec![(*base).clone()];
+            if window_size > 1 {
+                let double = base.double()?;
+                for i in 1..(1 << (window_size - 1)) {
+                    table.push(table[i - 1].clone() + &double);
+                }
+            }
+            tables.push(table);
+        }
+
+        let mut result = Self::zero();
+        for i in (0..num_bits / window_size).rev() {
+            for _ in 0..window_size {
+                result.double_in_place()?;
+            }
+            for (recoded_bits, table) in recoded_scalars.iter().zip(&tables) {
+                let window = &recoded_bits[i * window_size..(i + 1) * window_size];
+                let (is_positive, rest) = window.split_last().unwrap();
+                let position = rest
+                    .iter()
+                    .rev()
+                    .map(|b| b.xor(&is_positive.not()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let multiple = Self::conditionally_select_power_of_two_vector(&position, table)?;
+                result += is_positive.select(&multiple, &multiple.negate()?)?;
+            }
+        }
+        for (base, bits) in terms {
+            result = bits[0].select(&result, &(result.clone() - base))?;
+        }
+        Ok(result)
+    }
+
     /// Computes a `I * self` in place, where `I` is a `Boolean` *little-endian*
     /// representation of the scalar.
     ///
@@ -161,3 +417,162 @@ pub trait CurveVar<C: CurveGroup, ConstraintF: Field>:
         Ok(result)
     }
 }
+
+/// Returns `[base, 2 * base, ..., 2^(n - 1) * base]`, computed outside the
+/// circuit.
+pub(crate) fn power_of_two_multiples<C: CurveGroup>(base: C, n: usize) -> Vec<C> {
+    let mut multiple = base;
+    (0..n)
+        .map(|_| {
+            let current = multiple;
+            multiple.double_in_place();
+            current
+        })
+        .collect()
+}
+
+/// Computes `cofactor * p`, where `cofactor` is the cofactor of `C`.
+pub(crate) fn mul_by_cofactor<C, ConstraintF, V>(p: &V) -> Result<V, SynthesisError>
+where
+    C: CurveGroup,
+    ConstraintF: Field,
+    V: CurveVar<C, ConstraintF>,
+{
+    let cofactor = C::Config::COFACTOR;
+    if p.is_constant() {
+        return Ok(V::constant(p.value()?.mul_bigint(cofactor)));
+    }
+    // The leading bit is set, since the cofactor is non-zero.
+    let mut result = p.clone();
+    for b in BitIteratorBE::without_leading_zeros(cofactor).skip(1) {
+        result.double_in_place()?;
+        if b {
+            result += p;
+        }
+    }
+    Ok(result)
+}
+
+/// Point compression for curve variables, compatible with `ark-serialize`.
+pub trait PointCompressionGadget<C: CurveGroup, ConstraintF: Field>:
+    CurveVar<C, ConstraintF>
+{
+    /// Outputs the compressed serialization of `self`, with the same byte
+    /// layout as `ark-serialize`'s `CanonicalSerialize` with `Compress::Yes`
+    /// for the curve model of `C` (including the infinity and sign flags).
+    ///
+    /// Short Weierstrass curves that override the serialization of their
+    /// curve model are supported if they use the format of Zcash, like
+    /// BLS12-381; this method panics for other formats.
+    fn to_compressed_bytes(&self) -> Result<Vec<UInt8<ConstraintF>>, SynthesisError>;
+
+    /// Decompresses a point from `bytes`, the inverse of
+    /// [`PointCompressionGadget::to_compressed_bytes`].
+    ///
+    /// The constraint system will be unsatisfiable if `bytes` is not the
+    /// compressed serialization of a point in the prime-order subgroup. Unlike
+    /// `ark-serialize`, this also rejects encodings of the point at infinity
+    /// with a non-zero `x`-coordinate, so that every point has exactly one
+    /// encoding.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `bytes` does not have the length of a compressed
+    /// point, or if the format of `C` is unsupported.
+    fn from_compressed_bytes(bytes: &[UInt8<ConstraintF>]) -> Result<Self, SynthesisError>;
+}
+
+/// A table of precomputed multiples of a fixed base, for use in fixed-base
+/// scalar multiplication with 3-bit signed-digit windows.
+///
+/// The table can be computed once and reused across many scalar
+/// multiplications. It is consumed by
+/// [`ProjectiveVar::fixed_base_scalar_mul_le`](curves::short_weierstrass::ProjectiveVar::fixed_base_scalar_mul_le)
+/// and [`AffineVar::fixed_base_scalar_mul_le`](curves::twisted_edwards::AffineVar::fixed_base_scalar_mul_le).
+#[derive(Clone, Debug)]
+pub struct FixedBaseTable<C: CurveGroup> {
+    base: C,
+    /// `windows[i][j] = (2j + 1) * 8^i * base`.
+    windows: Vec<Vec<C::Affine>>,
+}
+
+impl<C: CurveGroup> FixedBaseTable<C> {
+    /// Precomputes the multiples of `base` that are required to multiply it
+    /// by scalars of at most `max_num_bits` bits.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `base` is not in the prime-order subgroup.
+    pub fn new(base: C, max_num_bits: usize) -> Self {
+        assert!(
+            base.mul_bigint(C::ScalarField::MODULUS).is_zero(),
+            "the base must be in the prime-order subgroup"
+        );
+        let num_windows = max_num_bits.div_ceil(3);
+        let mut windows = Vec::with_capacity(num_windows);
+        let mut power = base;
+        for _ in 0..num_windows {
+            let double = power.double();
+            let mut multiples = vec![power];
+            for j in 1..4 {
+                multiples.push(multiples[j - 1] + double);
+            }
+            windows.push(C::normalize_batch(&multiples));
+            for _ in 0..3 {
+                power.double_in_place();
+            }
+        }
+        Self { base, windows }
+    }
+
+    /// Returns the base.
+    pub fn base(&self) -> C {
+        self.base
+    }
+
+    /// Returns the maximum number of bits of the scalars that `self`
+    /// supports.
+    pub fn max_num_bits(&self) -> usize {
+        3 * self.windows.len()
+    }
+
+    /// Returns the multiples `(2j + 1) * 8^i * base` for `j = 0, ..., 3`.
+    pub(crate) fn window(&self, i: usize) -> &[C::Affine] {
+        &self.windows[i]
+    }
+}
+
+/// A 3-bit signed digit in the form expected by
+/// [`ThreeBitCondNegLookupGadget`](crate::select::ThreeBitCondNegLookupGadget),
+/// i.e., the bits `[j[0], j[1], is_negative]` and `j[0] & j[1]`.
+pub(crate) type SignedDigitBits<F> = (Vec<Boolean<F>>, Boolean<F>);
+
+/// Recodes the little-endian `bits` of a scalar `k` into 3-bit signed digits
+/// `d[i] ∈ {±1, ±3, ±5, ±7}`, such that `k | 1 = Σᵢ 8ⁱ * d[i]`.
+///
+/// For each digit, this returns the bits `[j[0], j[1], is_negative]` and
+/// `j[0] & j[1]`, where `|d[i]| = 2j + 1`, as expected by
+/// [`ThreeBitCondNegLookupGadget`](crate::select::ThreeBitCondNegLookupGadget).
+/// See [`CurveVar::scalar_mul_le_signed_digit`] for the recoding.
+pub(crate) fn three_bit_signed_digits<F: Field>(
+    bits: &[Boolean<F>],
+) -> Result<Vec<SignedDigitBits<F>>, SynthesisError> {
+    if bits.is_empty() {
+        return Ok(Vec::new());
+    }
+    let num_bits = bits.len().div_ceil(3) * 3;
+    let mut recoded_bits = bits[1..].to_vec();
+    recoded_bits.resize(num_bits - 1, Boolean::FALSE);
+    recoded_bits.push(Boolean::TRUE);
+
+    recoded_bits
+        .chunks(3)
+        .map(|window| {
+            let is_negative = window[2].not();
+            let j0 = window[0].xor(&is_negative)?;
+            let j1 = window[1].xor(&is_negative)?;
+            let j0_and_j1 = j0.and(&j1)?;
+            Ok((vec![j0, j1, is_negative], j0_and_j1))
+        })
+        .collect()
+}
@@ -0,0 +1,263 @@
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_relations::r1cs::{Namespace, SynthesisError};
+use core::{borrow::Borrow, marker::PhantomData};
+
+use crate::{fields::nonnative::NonNativeFieldVar, pairing::PairingVar, prelude::*, Vec};
+
+/// The verifier key of the KZG polynomial commitment scheme.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct VerifierKey<E: Pairing> {
+    /// The generator of `G1` that polynomials are committed with.
+    pub g: E::G1Affine,
+    /// The generator of `G2`.
+    pub h: E::G2Affine,
+    /// `τ * h`, where `τ` is the secret of the setup.
+    pub beta_h: E::G2Affine,
+}
+
+/// Represents the verifier key of the KZG polynomial commitment scheme, with
+/// its `G2` elements prepared for pairings.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: PairingVar<E, ConstraintF>"),
+    Debug(bound = "P: PairingVar<E, ConstraintF>")
+)]
+pub struct VerifierKeyVar<
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    ConstraintF: PrimeField = <<E as Pairing>::G1 as CurveGroup>::BaseField,
+> {
+    /// The generator of `G1` that polynomials are committed with.
+    pub g: P::G1Var,
+    /// The preparation of `h`.
+    pub prepared_h: P::G2PreparedVar,
+    /// The preparation of `-beta_h`.
+    pub prepared_neg_beta_h: P::G2PreparedVar,
+}
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: PrimeField>
+    AllocVar<VerifierKey<E>, ConstraintF> for VerifierKeyVar<E, P, ConstraintF>
+{
+    /// Allocates `Self`. If `mode` is `AllocationMode::Constant`, the line
+    /// coefficients of `h` and `-beta_h` are precomputed natively, so that
+    /// the pairings need no constraints to evaluate them. Otherwise, `h` and
+    /// `beta_h` are allocated as `G2` elements and prepared in the circuit.
+    #[tracing::instrument(target = "r1cs", skip(cs, f))]
+    fn new_variable<T: Borrow<VerifierKey<E>>>(
+        cs: impl Into<Namespace<ConstraintF>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let ns = cs.into();
+        let cs = ns.cs();
+
+        let vk = f().map(|vk| vk.borrow().clone());
+        let vk = vk.as_ref().map_err(|e| *e);
+        let g = <P::G1Var as AllocVar<E::G1Affine, _>>::new_variable(
+            ark_relations::ns!(cs, "g"),
+            || vk.map(|vk| vk.g),
+            mode,
+        )?;
+        let (prepared_h, prepared_neg_beta_h) = if mode == AllocationMode::Constant {
+            let vk = vk?;
+            let prepared_h = P::G2PreparedVar::new_constant(
+                ark_relations::ns!(cs, "prepared_h"),
+                E::G2Prepared::from(vk.h),
+            )?;
+            let prepared_neg_beta_h = P::G2PreparedVar::new_constant(
+                ark_relations::ns!(cs, "prepared_neg_beta_h"),
+                E::G2Prepared::from(-vk.beta_h.into_group()),
+            )?;
+            (prepared_h, prepared_neg_beta_h)
+        } else {
+            let h = <P::G2Var as AllocVar<E::G2Affine, _>>::new_variable(
+                ark_relations::ns!(cs, "h"),
+                || vk.map(|vk| vk.h),
+                mode,
+            )?;
+            let beta_h = <P::G2Var as AllocVar<E::G2Affine, _>>::new_variable(
+                ark_relations::ns!(cs, "beta_h"),
+                || vk.map(|vk| vk.beta_h),
+                mode,
+            )?;
+            (P::prepare_g2(&h)?, P::prepare_g2(&beta_h.negate()?)?)
+        };
+        Ok(Self {
+            g,
+            prepared_h,
+            prepared_neg_beta_h,
+        })
+    }
+}
+
+/// Verifies openings of KZG polynomial commitments over the pairing `E`, using
+/// the pairing gadget `P`.
+///
+/// Points and evaluations lie in the scalar field of `E`, and are thus
+/// represented as `NonNativeFieldVar`s.
+pub struct KZGVerifierGadget<
+    E: Pairing,
+    P: PairingVar<E, ConstraintF>,
+    ConstraintF: PrimeField = <<E as Pairing>::G1 as CurveGroup>::BaseField,
+>(PhantomData<(E, P, ConstraintF)>);
+
+type ScalarVar<E, ConstraintF> = NonNativeFieldVar<<E as Pairing>::ScalarField, ConstraintF>;
+
+impl<E: Pairing, P: PairingVar<E, ConstraintF>, ConstraintF: PrimeField>
+    KZGVerifierGadget<E, P, ConstraintF>
+{
+    /// Outputs a `Boolean` that is true if and only if `proof` shows that the
+    /// polynomial committed to by `commitment` evaluates to `value` at
+    /// `point`.
+    ///
+    /// This checks `e(C - y * g + z * π, h) = e(π, τ * h)`, where `C` is the
+    /// commitment, `z` the point, `y` the value and `π` the proof.
+    #[tracing::instrument(target = "r1cs", skip(vk, commitment, point, value, proof))]
+    pub fn verify(
+        vk: &VerifierKeyVar<E, P, ConstraintF>,
+        commitment: &P::G1Var,
+        point: &ScalarVar<E, ConstraintF>,
+        value: &ScalarVar<E, ConstraintF>,
+        proof: &P::G1Var,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        let y_g = vk.g.scalar_mul_le(value.to_bits_le()?.iter())?;
+        let z_proof = proof.scalar_mul_le(point.to_bits_le()?.iter())?;
+        Self::check(vk, &(commitment.clone() - y_g + z_proof), proof)
+    }
+
+    /// Outputs a `Boolean` that is true if and only if `proof` shows that the
+    /// polynomials committed to by `commitments` evaluate to `values` at
+    /// `point`, where `proof` opens the linear combination of the polynomials
+    /// with coefficients `1, challenge, challenge^2, ...`.
+    ///
+    /// `challenge` should be sampled after `commitments` and `values` are
+    /// fixed, for example with a Fiat-Shamir transform.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `commitments.len() != values.len()`.
+    #[tracing::instrument(
+        target = "r1cs",
+        skip(vk, commitments, point, values, proof, challenge)
+    )]
+    pub fn verify_combined(
+        vk: &VerifierKeyVar<E, P, ConstraintF>,
+        commitments: &[P::G1Var],
+        point: &ScalarVar<E, ConstraintF>,
+        values: &[ScalarVar<E, ConstraintF>],
+        proof: &P::G1Var,
+        challenge: &ScalarVar<E, ConstraintF>,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        assert_eq!(
+            commitments.len(),
+            values.len(),
+            "the number of commitments and values must be equal"
+        );
+        let powers = Self::powers(challenge, commitments.len());
+        let commitment = match commitments.split_first() {
+            Some((first, rest)) => {
+                P::G1Var::variable_base_msm(rest, &Self::bits(&powers[1..])?)? + first
+            },
+            None => P::G1Var::zero(),
+        };
+        let value = Self::inner_product(values, &powers);
+        Self::verify(vk, &commitment, point, &value, proof)
+    }
+
+    /// Outputs a `Boolean` that is true if and only if, for every `i`,
+    /// `proofs[i]` shows that the polynomial committed to by `commitments[i]`
+    /// evaluates to `values[i]` at `points[i]`.
+    ///
+    /// The openings are checked at once, by checking the linear combination of
+    /// their equations with coefficients `1, challenge, challenge^2, ...`.
+    /// `challenge` should be sampled after all the inputs are fixed, for
+    /// example with a Fiat-Shamir transform.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the lengths of `commitments`, `points`, `values`
+    /// and `proofs` are not equal.
+    #[tracing::instrument(
+        target = "r1cs",
+        skip(vk, commitments, points, values, proofs, challenge)
+    )]
+    pub fn batch_verify(
+        vk: &VerifierKeyVar<E, P, ConstraintF>,
+        commitments: &[P::G1Var],
+        points: &[ScalarVar<E, ConstraintF>],
+        values: &[ScalarVar<E, ConstraintF>],
+        proofs: &[P::G1Var],
+        challenge: &ScalarVar<E, ConstraintF>,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        let n = commitments.len();
+        assert!(
+            points.len() == n && values.len() == n && proofs.len() == n,
+            "the number of commitments, points, values and proofs must be equal"
+        );
+        let powers = Self::powers(challenge, n);
+
+        // Σᵢ rⁱ * (Cᵢ - yᵢ * g + zᵢ * πᵢ) = Σᵢ rⁱ * Cᵢ + Σᵢ (rⁱ * zᵢ) * πᵢ - (Σᵢ rⁱ * yᵢ) * g
+        let r_z = points
+            .iter()
+            .zip(&powers)
+            .map(|(z, r)| z * r)
+            .collect::<Vec<_>>();
+        let r_y = Self::inner_product(values, &powers);
+        let bases = [commitments, proofs].concat();
+        let scalars = Self::bits(&[&powers[..], &r_z[..]].concat())?;
+        let lhs = P::G1Var::variable_base_msm(&bases, &scalars)?
+            - vk.g.scalar_mul_le(r_y.to_bits_le()?.iter())?;
+        let proof = P::G1Var::variable_base_msm(proofs, &scalars[..n])?;
+        Self::check(vk, &lhs, &proof)
+    }
+
+    /// Outputs a `Boolean` that is true if and only if
+    /// `e(lhs, h) = e(proof, τ * h)`.
+    fn check(
+        vk: &VerifierKeyVar<E, P, ConstraintF>,
+        lhs: &P::G1Var,
+        proof: &P::G1Var,
+    ) -> Result<Boolean<ConstraintF>, SynthesisError> {
+        let lhs = P::prepare_g1(lhs)?;
+        let proof = P::prepare_g1(proof)?;
+        P::product_of_pairings(
+            &[lhs, proof],
+            &[vk.prepared_h.clone(), vk.prepared_neg_beta_h.clone()],
+        )?
+        .is_one()
+    }
+
+    /// Outputs `1, challenge, ..., challenge^(n - 1)`.
+    fn powers(challenge: &ScalarVar<E, ConstraintF>, n: usize) -> Vec<ScalarVar<E, ConstraintF>> {
+        let mut powers: Vec<ScalarVar<E, ConstraintF>> = Vec::with_capacity(n);
+        for i in 0..n {
+            let power = match i {
+                0 => ScalarVar::<E, ConstraintF>::one(),
+                _ => &powers[i - 1] * challenge,
+            };
+            powers.push(power);
+        }
+        powers
+    }
+
+    /// Outputs `Σᵢ(aᵢ * bᵢ)`.
+    fn inner_product(
+        a: &[ScalarVar<E, ConstraintF>],
+        b: &[ScalarVar<E, ConstraintF>],
+    ) -> ScalarVar<E, ConstraintF> {
+        a.iter()
+            .zip(b)
+            .fold(ScalarVar::<E, ConstraintF>::zero(), |acc, (a, b)| {
+                acc + a * b
+            })
+    }
+
+    /// Outputs the little-endian `Boolean` representations of `scalars`.
+    fn bits(
+        scalars: &[ScalarVar<E, ConstraintF>],
+    ) -> Result<Vec<Vec<Boolean<ConstraintF>>>, SynthesisError> {
+        scalars.iter().map(|s| s.to_bits_le()).collect()
+    }
+}
@@ -47,6 +47,17 @@ pub mod groups;
 /// groups.
 pub mod pairing;
 
+/// This module implements a gadget for verifying Groth16 proofs.
+pub mod groth16;
+
+/// This module implements gadgets for verifying BLS signatures and aggregate
+/// signatures.
+pub mod bls;
+
+/// This module implements a gadget for verifying openings of KZG polynomial
+/// commitments.
+pub mod kzg;
+
 /// This module describes a trait for allocating new variables in a constraint
 /// system.
 pub mod alloc;
@@ -1,35 +1,68 @@
-use ark_relations::r1cs::SynthesisError;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
 
-use super::PairingVar as PG;
+use super::{
+    residue::{
+        biguint_from_limbs, characteristic, constant_copies, prepared_cs, residue_witness,
+        PreparedVar,
+    },
+    PairingVar as PG,
+};
 
 use crate::{
-    fields::{fp::FpVar, fp12::Fp12Var, fp2::Fp2Var, FieldVar},
+    fields::{
+        fp::FpVar, fp12::Fp12VarWithBase, fp2::Fp2VarWithBase, fp6_3over2::Fp6VarWithBase,
+        nonnative::BigUintVar, FieldOpsBounds, FieldVar,
+    },
     groups::bls12::{G1AffineVar, G1PreparedVar, G1Var, G2PreparedVar, G2Var},
+    prelude::*,
+    Vec,
+};
+use ark_ec::{
+    bls12::{Bls12, Bls12Config, TwistType},
+    CurveConfig,
 };
-use ark_ec::bls12::{Bls12, Bls12Config, TwistType};
-use ark_ff::BitIteratorBE;
+use ark_ff::{BitIteratorBE, PrimeField};
 use ark_std::marker::PhantomData;
 
 /// Specifies the constraints for computing a pairing in a BLS12 bilinear group.
-pub struct PairingVar<P: Bls12Config>(PhantomData<P>);
+///
+/// By default, the pairing is computed over the base field `P::Fp` of the
+/// curve. To compute it over another constraint field `ConstraintF`, `F` can
+/// be set to `NonNativeFieldVar<P::Fp, ConstraintF>`.
+pub struct PairingVar<
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF> = FpVar<<P as Bls12Config>::Fp>,
+    ConstraintF: PrimeField = <P as Bls12Config>::Fp,
+>(PhantomData<(P, F, ConstraintF)>)
+where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>;
 
-type Fp2V<P> = Fp2Var<<P as Bls12Config>::Fp2Config>;
+type Fp2V<P, F, ConstraintF> = Fp2VarWithBase<<P as Bls12Config>::Fp2Config, F, ConstraintF>;
+type Fp12V<P, F, ConstraintF> = Fp12VarWithBase<<P as Bls12Config>::Fp12Config, F, ConstraintF>;
 
-impl<P: Bls12Config> PairingVar<P> {
+impl<P, F, ConstraintF> PairingVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     // Evaluate the line function at point p.
     #[tracing::instrument(target = "r1cs")]
     fn ell(
-        f: &mut Fp12Var<P::Fp12Config>,
-        coeffs: &(Fp2V<P>, Fp2V<P>),
-        p: &G1AffineVar<P>,
+        f: &mut Fp12V<P, F, ConstraintF>,
+        coeffs: &(Fp2V<P, F, ConstraintF>, Fp2V<P, F, ConstraintF>),
+        p: &G1AffineVar<P, F, ConstraintF>,
     ) -> Result<(), SynthesisError> {
-        let zero = FpVar::<P::Fp>::zero();
+        let zero = F::zero();
 
         match P::TWIST_TYPE {
             TwistType::M => {
                 let c0 = coeffs.0.clone();
                 let mut c1 = coeffs.1.clone();
-                let c2 = Fp2V::<P>::new(p.y.clone(), zero);
+                let c2 = Fp2V::<P, F, ConstraintF>::new(p.y.clone(), zero);
 
                 c1.c0 *= &p.x;
                 c1.c1 *= &p.x;
@@ -37,7 +70,7 @@ impl<P: Bls12Config> PairingVar<P> {
                 Ok(())
             },
             TwistType::D => {
-                let c0 = Fp2V::<P>::new(p.y.clone(), zero);
+                let c0 = Fp2V::<P, F, ConstraintF>::new(p.y.clone(), zero);
                 let mut c1 = coeffs.0.clone();
                 let c2 = coeffs.1.clone();
 
@@ -50,32 +83,36 @@ impl<P: Bls12Config> PairingVar<P> {
     }
 
     #[tracing::instrument(target = "r1cs")]
-    fn exp_by_x(f: &Fp12Var<P::Fp12Config>) -> Result<Fp12Var<P::Fp12Config>, SynthesisError> {
+    fn exp_by_x(f: &Fp12V<P, F, ConstraintF>) -> Result<Fp12V<P, F, ConstraintF>, SynthesisError> {
         let mut result = f.optimized_cyclotomic_exp(P::X)?;
         if P::X_IS_NEGATIVE {
             result = result.unitary_inverse()?;
         }
         Ok(result)
     }
-}
-
-impl<P: Bls12Config> PG<Bls12<P>, P::Fp> for PairingVar<P> {
-    type G1Var = G1Var<P>;
-    type G2Var = G2Var<P>;
-    type G1PreparedVar = G1PreparedVar<P>;
-    type G2PreparedVar = G2PreparedVar<P>;
-    type GTVar = Fp12Var<P::Fp12Config>;
 
+    /// Computes the Miller loop over `ps` and `qs`, and multiplies its output
+    /// by `c^|x|` if `c` is given. As `c^|x|` is computed by square-and-multiply
+    /// along the bits of `|x|`, it shares the squarings of the Miller loop.
     #[tracing::instrument(target = "r1cs")]
-    fn miller_loop(
-        ps: &[Self::G1PreparedVar],
-        qs: &[Self::G2PreparedVar],
-    ) -> Result<Self::GTVar, SynthesisError> {
+    fn miller_loop_with_accumulator(
+        ps: &[G1PreparedVar<P, F, ConstraintF>],
+        qs: &[G2PreparedVar<P, F, ConstraintF>],
+        c: Option<&Fp12V<P, F, ConstraintF>>,
+    ) -> Result<Fp12V<P, F, ConstraintF>, SynthesisError> {
         let mut pairs = vec![];
         for (p, q) in ps.iter().zip(qs.iter()) {
             pairs.push((p, q.ell_coeffs.iter()));
         }
-        let mut f = Self::GTVar::one();
+        // If `x` is negative, the output is conjugated below, so we accumulate
+        // the conjugate of `c` instead.
+        let c = c
+            .map(|c| match P::X_IS_NEGATIVE {
+                true => c.unitary_inverse(),
+                false => Ok(c.clone()),
+            })
+            .transpose()?;
+        let mut f = c.clone().unwrap_or_else(Fp12V::<P, F, ConstraintF>::one);
 
         for i in BitIteratorBE::new(P::X).skip(1) {
             f.square_in_place()?;
@@ -88,6 +125,9 @@ impl<P: Bls12Config> PG<Bls12<P>, P::Fp> for PairingVar<P> {
                 for &mut (p, ref mut coeffs) in pairs.iter_mut() {
                     Self::ell(&mut f, &coeffs.next().unwrap(), &p.0)?;
                 }
+                if let Some(c) = &c {
+                    f *= c;
+                }
             }
         }
 
@@ -97,6 +137,74 @@ impl<P: Bls12Config> PG<Bls12<P>, P::Fp> for PairingVar<P> {
 
         Ok(f)
     }
+}
+
+impl<P, F, ConstraintF> PreparedVar<ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF> {
+        self.0.x.cs().or(self.0.y.cs()).or(self.0.infinity.cs())
+    }
+
+    fn constant_copy(&self) -> Result<Self, SynthesisError> {
+        Self::new_constant(ConstraintSystemRef::None, self.value()?)
+    }
+}
+
+impl<P, F, ConstraintF> PreparedVar<ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF> {
+        self.ell_coeffs
+            .iter()
+            .fold(ConstraintSystemRef::None, |acc, (c0, c1)| {
+                acc.or(c0.cs()).or(c1.cs())
+            })
+    }
+
+    fn constant_copy(&self) -> Result<Self, SynthesisError> {
+        let ell_coeffs = self
+            .ell_coeffs
+            .iter()
+            .map(|(c0, c1)| {
+                Ok((
+                    Fp2V::<P, F, ConstraintF>::constant(c0.value()?),
+                    Fp2V::<P, F, ConstraintF>::constant(c1.value()?),
+                ))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        Ok(Self { ell_coeffs })
+    }
+}
+
+impl<P, F, ConstraintF> PG<Bls12<P>, ConstraintF> for PairingVar<P, F, ConstraintF>
+where
+    P: Bls12Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    type G1Var = G1Var<P, F, ConstraintF>;
+    type G2Var = G2Var<P, F, ConstraintF>;
+    type G1PreparedVar = G1PreparedVar<P, F, ConstraintF>;
+    type G2PreparedVar = G2PreparedVar<P, F, ConstraintF>;
+    type GTVar = Fp12V<P, F, ConstraintF>;
+
+    #[tracing::instrument(target = "r1cs")]
+    fn miller_loop(
+        ps: &[Self::G1PreparedVar],
+        qs: &[Self::G2PreparedVar],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        Self::miller_loop_with_accumulator(ps, qs, None)
+    }
 
     #[tracing::instrument(target = "r1cs")]
     fn final_exponentiation(f: &Self::GTVar) -> Result<Self::GTVar, SynthesisError> {
@@ -155,6 +263,202 @@ impl<P: Bls12Config> PG<Bls12<P>, P::Fp> for PairingVar<P> {
         })
     }
 
+    /// Following Novakovic and Eagen, "On Proving Pairings"
+    /// (<https://eprint.iacr.org/2024/640>), this witnesses `c` and `w` such
+    /// that `c^λ = f * w`, where `f` is the Miller loop output, `λ = ±(p - x)`
+    /// is a multiple of the group order, and `w` is killed by the easy part of
+    /// the final exponentiation. This replaces the final exponentiation by a
+    /// few multiplications, since `c^|x|` shares the squarings of the Miller
+    /// loop.
+    #[tracing::instrument(target = "r1cs")]
+    fn enforce_pairing_product_is_one(
+        ps: &[Self::G1PreparedVar],
+        qs: &[Self::G2PreparedVar],
+    ) -> Result<(), SynthesisError> {
+        let cs = prepared_cs(ps, qs);
+        if cs.is_none() {
+            let f = Self::product_of_pairings(ps, qs)?;
+            return f.enforce_equal(&Self::GTVar::one());
+        }
+
+        // With `x > 0`, `λ = p - x`, so that `c^λ = f * w` iff
+        // `c^p = f * c^x * w`. With `x < 0`, `λ = x - p`, so that `c^λ = f * w`
+        // iff `f * c^|x| * w * c^p = 1`.
+        let witness = (|| {
+            let (ps, qs) = constant_copies(ps, qs)?;
+            let f = Self::miller_loop_with_accumulator(&ps, &qs, None)?.value()?;
+            let p = BigInt::from(characteristic::<P::Fp>());
+            let x = BigInt::from(biguint_from_limbs(P::X));
+            let lambda = match P::X_IS_NEGATIVE {
+                false => p - x,
+                true => -(p + x),
+            };
+            let r = <<P::G1Config as CurveConfig>::ScalarField as PrimeField>::MODULUS.into();
+            Ok(residue_witness(&f, &lambda, &r))
+        })();
+        let c = Fp12V::<P, F, ConstraintF>::new_witness(ark_relations::ns!(cs, "c"), || {
+            witness.map(|(c, _)| c)
+        })?;
+        let w = Fp12V::<P, F, ConstraintF>::new_witness(ark_relations::ns!(cs, "w"), || {
+            witness.map(|(_, w)| w)
+        })?;
+
+        // `w^((p^6 - 1)(p^2 + 1)) = 1` iff `w^(p^2 + 1)` lies in `Fp6`.
+        (&w * w.frobenius_map(2)?)
+            .c1
+            .enforce_equal(&Fp6VarWithBase::zero())?;
+
+        let f = Self::miller_loop_with_accumulator(ps, qs, Some(&c))? * &w;
+        if P::X_IS_NEGATIVE {
+            (f * c.frobenius_map(1)?).enforce_equal(&Self::GTVar::one())
+        } else {
+            // Checking `f * c^(-p) = 1` rather than `f = c^p` also enforces that
+            // `c` is non-zero, as otherwise both sides of the latter are zero.
+            (f * c.inverse()?.frobenius_map(1)?).enforce_equal(&Self::GTVar::one())
+        }
+    }
+
+    /// As `p = x mod r`, the Frobenius map acts on `GT` as exponentiation by
+    /// `x`. Following Galbraith and Scott (<https://eprint.iacr.org/2008/117>),
+    /// `k` is decomposed out of circuit into its digits `k_i < |x|` in base
+    /// `|x|` modulo `r`, and `gt^k` is computed by a joint square-and-multiply
+    /// over the `gt^(|x|^i)`, which needs a quarter of the squarings. The
+    /// decomposition is enforced with emulated integer arithmetic.
+    ///
+    /// `gt` must be in `GT`, as otherwise the Frobenius map does not act as
+    /// exponentiation by `x`.
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    fn gt_pow_le(
+        gt: &Self::GTVar,
+        bits: &[Boolean<ConstraintF>],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        let x = biguint_from_limbs(P::X);
+        let num_bits = x.bits() as usize;
+        // For short exponents, the decomposition does not pay off.
+        if bits.is_constant() || bits.len() <= 2 * num_bits {
+            return gt.cyclotomic_pow_le(bits);
+        }
+        let cs = gt.cs().or(bits.cs());
+
+        // Each `k_i` is represented by the `(num_bits + 1)`-bit integer
+        // `u_i = k_i + 2^num_bits - 1`.
+        let offset = (BigUint::one() << num_bits) - 1u8;
+        let powers_of_x = [BigUint::one(), x.clone(), &x * &x, &x * &x * &x];
+
+        // Enforce `Σ_i u_i * |x|^i + c = k + q * r` over the integers, where `c`
+        // is a multiple of `r` minus `(2^num_bits - 1) * Σ_i |x|^i` that is
+        // large enough for `q` to be non-negative.
+        let modulus: BigUint =
+            <<P::G1Config as CurveConfig>::ScalarField as PrimeField>::MODULUS.into();
+        let max_scalar = (BigUint::one() << bits.len()) - 1u8;
+        let c = {
+            let sum = powers_of_x.iter().sum::<BigUint>();
+            let negated_offset = (&modulus - (&offset * sum) % &modulus) % &modulus;
+            negated_offset + (&max_scalar / &modulus + 1u8) * &modulus
+        };
+        let max_u = (BigUint::one() << (num_bits + 1)) - 1u8;
+        let max_q = (powers_of_x.iter().map(|x_i| &max_u * x_i).sum::<BigUint>() + &c) / &modulus;
+
+        let witness = || -> Result<(Vec<BigUint>, BigUint), SynthesisError> {
+            let mut k = BigUint::zero();
+            for (i, bit) in bits.iter().enumerate() {
+                if bit.value()? {
+                    k.set_bit(i as u64, true);
+                }
+            }
+            // As `r < |x|^4`, `k mod r` has at most four digits in base `|x|`.
+            let mut digits = &k % &modulus;
+            let mut us = Vec::new();
+            for _ in 0..4 {
+                us.push(&digits % &x + &offset);
+                digits /= &x;
+            }
+            let left = us
+                .iter()
+                .zip(&powers_of_x)
+                .map(|(u, x_i)| u * x_i)
+                .sum::<BigUint>()
+                + &c;
+            Ok((us, (left - k) / &modulus))
+        };
+        let witness = witness();
+        let u_bits = (0..4)
+            .map(|i| {
+                (0..=num_bits)
+                    .map(|j| {
+                        Boolean::new_witness(ark_relations::ns!(cs, "bit"), || {
+                            witness
+                                .as_ref()
+                                .map(|w| w.0[i].bit(j as u64))
+                                .map_err(|e| *e)
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let q = BigUintVar::new_witness_with_num_bits(
+            ark_relations::ns!(cs, "quotient"),
+            || witness.as_ref().map(|w| w.1.clone()).map_err(|e| *e),
+            max_q.bits() as usize,
+        )?;
+
+        let mut left = BigUintVar::constant(&c);
+        for (u, x_i) in u_bits.iter().zip(&powers_of_x) {
+            left = left.add(&BigUintVar::constant(x_i).mul(&BigUintVar::from_bits_le(u)?)?)?;
+        }
+        let right =
+            BigUintVar::from_bits_le(bits)?.add(&q.mul(&BigUintVar::constant(&modulus))?)?;
+        left.enforce_equal(&right)?;
+
+        // `powers[i] = gt^(|x|^i)`. If `x` is negative, the Frobenius map acts
+        // as exponentiation by `-|x|`, which we undo by a unitary inverse.
+        let mut powers = vec![gt.clone()];
+        for i in 1..4 {
+            let power = powers[i - 1].frobenius_map(1)?;
+            powers.push(match P::X_IS_NEGATIVE {
+                true => power.unitary_inverse()?,
+                false => power,
+            });
+        }
+
+        // As in `ProjectiveVar::scalar_mul_le_glv`, `k_i` equals
+        // `u_i[0] + Σ_j 2^j * (2 * u_i[j + 1] - 1)`, whose digits are in
+        // `{-1, 1}`. Bit `i - 1` of the index into `table` determines the sign
+        // of `powers[i]` relative to that of `powers[0]`.
+        let mut table = vec![powers[0].clone()];
+        for power in &powers[1..] {
+            let inverse = power.unitary_inverse()?;
+            let positive = table.iter().map(|t| t * power);
+            let negative = table.iter().map(|t| t * &inverse);
+            table = positive.chain(negative).collect();
+        }
+        let lookup = |j: usize| -> Result<Self::GTVar, SynthesisError> {
+            let mut entries = table.clone();
+            for u in &u_bits[1..] {
+                let is_negative = u[j].xor(&u_bits[0][j])?;
+                entries = entries
+                    .chunks(2)
+                    .map(|pair| is_negative.select(&pair[1], &pair[0]))
+                    .collect::<Result<Vec<_>, _>>()?;
+            }
+            let entry = entries.pop().unwrap();
+            u_bits[0][j].select(&entry, &entry.unitary_inverse()?)
+        };
+
+        let mut result = lookup(num_bits)?;
+        for j in (1..num_bits).rev() {
+            result = result.cyclotomic_square()?;
+            result *= lookup(j)?;
+        }
+
+        // Finally, multiply by `powers[i]^(u_i[0])`.
+        for (u, power) in u_bits.iter().zip(&powers) {
+            let product = &result * power;
+            result = u[0].select(&product, &result)?;
+        }
+        Ok(result)
+    }
+
     #[tracing::instrument(target = "r1cs")]
     fn prepare_g1(p: &Self::G1Var) -> Result<Self::G1PreparedVar, SynthesisError> {
         Self::G1PreparedVar::from_group_var(p)
@@ -0,0 +1,356 @@
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+use super::{
+    residue::{characteristic, constant_copies, prepared_cs, residue_witness, PreparedVar},
+    PairingVar as PG,
+};
+
+use crate::{
+    fields::{
+        fp::FpVar, fp12::Fp12VarWithBase, fp2::Fp2VarWithBase, fp6_3over2::Fp6VarWithBase,
+        FieldOpsBounds, FieldVar,
+    },
+    groups::bn::{G1AffineVar, G1PreparedVar, G1Var, G2PreparedVar, G2Var},
+    prelude::*,
+    Vec,
+};
+use ark_ec::{
+    bn::{Bn, BnConfig, TwistType},
+    CurveConfig,
+};
+use ark_ff::PrimeField;
+use ark_std::marker::PhantomData;
+
+/// Specifies the constraints for computing a pairing in a BN bilinear group.
+///
+/// By default, the pairing is computed over the base field `P::Fp` of the
+/// curve. To compute it over another constraint field `ConstraintF`, `F` can
+/// be set to `NonNativeFieldVar<P::Fp, ConstraintF>`.
+pub struct PairingVar<
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF> = FpVar<<P as BnConfig>::Fp>,
+    ConstraintF: PrimeField = <P as BnConfig>::Fp,
+>(PhantomData<(P, F, ConstraintF)>)
+where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>;
+
+type Fp2V<P, F, ConstraintF> = Fp2VarWithBase<<P as BnConfig>::Fp2Config, F, ConstraintF>;
+type Fp12V<P, F, ConstraintF> = Fp12VarWithBase<<P as BnConfig>::Fp12Config, F, ConstraintF>;
+/// A residue witness `c` together with its inverse.
+type ResidueWitness<'a, P, F, ConstraintF> =
+    (&'a Fp12V<P, F, ConstraintF>, &'a Fp12V<P, F, ConstraintF>);
+
+impl<P, F, ConstraintF> PairingVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    // Evaluate the line function at point p.
+    #[tracing::instrument(target = "r1cs")]
+    fn ell(
+        f: &mut Fp12V<P, F, ConstraintF>,
+        coeffs: &(Fp2V<P, F, ConstraintF>, Fp2V<P, F, ConstraintF>),
+        p: &G1AffineVar<P, F, ConstraintF>,
+    ) -> Result<(), SynthesisError> {
+        let zero = F::zero();
+
+        match P::TWIST_TYPE {
+            TwistType::M => {
+                let c0 = coeffs.0.clone();
+                let mut c1 = coeffs.1.clone();
+                let c2 = Fp2V::<P, F, ConstraintF>::new(p.y.clone(), zero);
+
+                c1.c0 *= &p.x;
+                c1.c1 *= &p.x;
+                *f = f.mul_by_014(&c0, &c1, &c2)?;
+                Ok(())
+            },
+            TwistType::D => {
+                let c0 = Fp2V::<P, F, ConstraintF>::new(p.y.clone(), zero);
+                let mut c1 = coeffs.0.clone();
+                let c2 = coeffs.1.clone();
+
+                c1.c0 *= &p.x;
+                c1.c1 *= &p.x;
+                *f = f.mul_by_034(&c0, &c1, &c2)?;
+                Ok(())
+            },
+        }
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn exp_by_neg_x(
+        f: &Fp12V<P, F, ConstraintF>,
+    ) -> Result<Fp12V<P, F, ConstraintF>, SynthesisError> {
+        let mut result = f.optimized_cyclotomic_exp(P::X)?;
+        if !P::X_IS_NEGATIVE {
+            result = result.unitary_inverse()?;
+        }
+        Ok(result)
+    }
+
+    /// Computes the Miller loop over `ps` and `qs`, and multiplies its output
+    /// by `c^|6x + 2|` if `c` and its inverse are given. As `c^|6x + 2|` is
+    /// computed along the NAF of `|6x + 2|`, it shares the squarings of the
+    /// Miller loop.
+    #[tracing::instrument(target = "r1cs")]
+    fn miller_loop_with_accumulator(
+        ps: &[G1PreparedVar<P, F, ConstraintF>],
+        qs: &[G2PreparedVar<P, F, ConstraintF>],
+        c: Option<ResidueWitness<'_, P, F, ConstraintF>>,
+    ) -> Result<Fp12V<P, F, ConstraintF>, SynthesisError> {
+        let mut pairs = vec![];
+        for (p, q) in ps.iter().zip(qs.iter()) {
+            pairs.push((p, q.ell_coeffs.iter()));
+        }
+        // If `x` is negative, the accumulator is conjugated below, so we
+        // accumulate the conjugate of `c` instead.
+        let c = c
+            .map(|(c, c_inv)| match P::X_IS_NEGATIVE {
+                true => Ok((c.unitary_inverse()?, c_inv.unitary_inverse()?)),
+                false => Ok((c.clone(), c_inv.clone())),
+            })
+            .transpose()?;
+        let mut f = c
+            .as_ref()
+            .map_or_else(Fp12V::<P, F, ConstraintF>::one, |(c, _)| c.clone());
+
+        // The loop runs over the NAF of `6x + 2`.
+        for i in (1..P::ATE_LOOP_COUNT.len()).rev() {
+            if i != P::ATE_LOOP_COUNT.len() - 1 || c.is_some() {
+                f.square_in_place()?;
+            }
+
+            for &mut (p, ref mut coeffs) in pairs.iter_mut() {
+                Self::ell(&mut f, coeffs.next().unwrap(), &p.0)?;
+            }
+
+            let bit = P::ATE_LOOP_COUNT[i - 1];
+            if bit == 1 || bit == -1 {
+                for &mut (p, ref mut coeffs) in pairs.iter_mut() {
+                    Self::ell(&mut f, coeffs.next().unwrap(), &p.0)?;
+                }
+                if let Some((c, c_inv)) = &c {
+                    f *= if bit == 1 { c } else { c_inv };
+                }
+            }
+        }
+
+        if P::X_IS_NEGATIVE {
+            f = f.unitary_inverse()?;
+        }
+
+        // The line functions through `π(q)` and `-π^2(q)`.
+        for &mut (p, ref mut coeffs) in pairs.iter_mut() {
+            Self::ell(&mut f, coeffs.next().unwrap(), &p.0)?;
+        }
+        for &mut (p, ref mut coeffs) in pairs.iter_mut() {
+            Self::ell(&mut f, coeffs.next().unwrap(), &p.0)?;
+        }
+
+        Ok(f)
+    }
+}
+
+impl<P, F, ConstraintF> PreparedVar<ConstraintF> for G1PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF> {
+        self.0.x.cs().or(self.0.y.cs()).or(self.0.infinity.cs())
+    }
+
+    fn constant_copy(&self) -> Result<Self, SynthesisError> {
+        Self::new_constant(ConstraintSystemRef::None, self.value()?)
+    }
+}
+
+impl<P, F, ConstraintF> PreparedVar<ConstraintF> for G2PreparedVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF> {
+        self.ell_coeffs
+            .iter()
+            .fold(ConstraintSystemRef::None, |acc, (c0, c1)| {
+                acc.or(c0.cs()).or(c1.cs())
+            })
+    }
+
+    fn constant_copy(&self) -> Result<Self, SynthesisError> {
+        let ell_coeffs = self
+            .ell_coeffs
+            .iter()
+            .map(|(c0, c1)| {
+                Ok((
+                    Fp2V::<P, F, ConstraintF>::constant(c0.value()?),
+                    Fp2V::<P, F, ConstraintF>::constant(c1.value()?),
+                ))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+        Ok(Self { ell_coeffs })
+    }
+}
+
+impl<P, F, ConstraintF> PG<Bn<P>, ConstraintF> for PairingVar<P, F, ConstraintF>
+where
+    P: BnConfig,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    type G1Var = G1Var<P, F, ConstraintF>;
+    type G2Var = G2Var<P, F, ConstraintF>;
+    type G1PreparedVar = G1PreparedVar<P, F, ConstraintF>;
+    type G2PreparedVar = G2PreparedVar<P, F, ConstraintF>;
+    type GTVar = Fp12V<P, F, ConstraintF>;
+
+    #[tracing::instrument(target = "r1cs")]
+    fn miller_loop(
+        ps: &[Self::G1PreparedVar],
+        qs: &[Self::G2PreparedVar],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        Self::miller_loop_with_accumulator(ps, qs, None)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn final_exponentiation(f: &Self::GTVar) -> Result<Self::GTVar, SynthesisError> {
+        // Easy part: r = f^((p^6 - 1)(p^2 + 1)).
+        let f1 = f.unitary_inverse()?;
+
+        f.inverse().and_then(|mut f2| {
+            // f2 = f^(-1);
+            // r = f^(p^6 - 1)
+            let mut r = f1;
+            r *= &f2;
+
+            // f2 = f^(p^6 - 1)
+            f2 = r.clone();
+            // r = f^((p^6 - 1)(p^2))
+            r.frobenius_map_in_place(2)?;
+
+            // r = f^((p^6 - 1)(p^2) + (p^6 - 1))
+            // r = f^((p^6 - 1)(p^2 + 1))
+            r *= &f2;
+
+            // Hard part of the final exponentation is below, following
+            // Fuentes-Castañeda et al., "Faster hashing to G2", which computes
+            // r^(2x * (6x^2 + 3x + 1) * (p^4 - p^2 + 1) / q).
+            let y0 = Self::exp_by_neg_x(&r)?;
+            let y1 = y0.cyclotomic_square()?;
+            let y2 = y1.cyclotomic_square()?;
+            let y3 = y2 * &y1;
+            let y4 = Self::exp_by_neg_x(&y3)?;
+            let y5 = y4.cyclotomic_square()?;
+            let y6 = Self::exp_by_neg_x(&y5)?;
+            let y3 = y3.unitary_inverse()?;
+            let y6 = y6.unitary_inverse()?;
+            let y7 = y6 * &y4;
+            let mut y8 = y7 * &y3;
+            let y9 = y8.clone() * &y1;
+            let y10 = y8.clone() * &y4;
+            let y11 = y10 * &r;
+            let mut y12 = y9.clone();
+            y12.frobenius_map_in_place(1)?;
+            let y13 = y12 * &y11;
+            y8.frobenius_map_in_place(2)?;
+            let y14 = y8 * &y13;
+            r = r.unitary_inverse()?;
+            let mut y15 = r * &y9;
+            y15.frobenius_map_in_place(3)?;
+            Ok(y15 * &y14)
+        })
+    }
+
+    /// Following Novakovic and Eagen, "On Proving Pairings"
+    /// (<https://eprint.iacr.org/2024/640>), this witnesses `c` and `w` such
+    /// that `c^λ = f * w`, where `f` is the Miller loop output,
+    /// `λ = ±(6x + 2 + p - p^2 + p^3)` is a multiple of the group order, and
+    /// `w` is killed by the easy part of the final exponentiation. This
+    /// replaces the final exponentiation by a few multiplications, since
+    /// `c^|6x + 2|` shares the squarings of the Miller loop.
+    #[tracing::instrument(target = "r1cs")]
+    fn enforce_pairing_product_is_one(
+        ps: &[Self::G1PreparedVar],
+        qs: &[Self::G2PreparedVar],
+    ) -> Result<(), SynthesisError> {
+        let cs = prepared_cs(ps, qs);
+        if cs.is_none() {
+            let f = Self::product_of_pairings(ps, qs)?;
+            return f.enforce_equal(&Self::GTVar::one());
+        }
+
+        // Let `t = |6x + 2|`. With `x > 0`, `λ = -(t + p - p^2 + p^3)`, so that
+        // `c^λ = f * w` iff `f * c^t * w * c^p * c^(p^3) = c^(p^2)`. With
+        // `x < 0`, `λ = -t + p - p^2 + p^3`, so that `c^λ = f * w` iff
+        // `c^p * c^(p^3) = f * c^t * w * c^(p^2)`.
+        let witness = (|| {
+            let (ps, qs) = constant_copies(ps, qs)?;
+            let f = Self::miller_loop_with_accumulator(&ps, &qs, None)?.value()?;
+            let p = BigInt::from(characteristic::<P::Fp>());
+            let t = P::ATE_LOOP_COUNT
+                .iter()
+                .rev()
+                .fold(BigInt::zero(), |acc, &bit| (acc << 1) + bit);
+            let lambda = &p - p.pow(2u32) + p.pow(3u32);
+            let lambda = match P::X_IS_NEGATIVE {
+                false => -(t + lambda),
+                true => lambda - t,
+            };
+            let r = <<P::G1Config as CurveConfig>::ScalarField as PrimeField>::MODULUS.into();
+            Ok(residue_witness(&f, &lambda, &r))
+        })();
+        let c = Fp12V::<P, F, ConstraintF>::new_witness(ark_relations::ns!(cs, "c"), || {
+            witness.map(|(c, _)| c)
+        })?;
+        let w = Fp12V::<P, F, ConstraintF>::new_witness(ark_relations::ns!(cs, "w"), || {
+            witness.map(|(_, w)| w)
+        })?;
+        // This also enforces that `c` is non-zero, which in turn implies that
+        // both sides of the final check are non-zero.
+        let c_inv = c.inverse()?;
+
+        // `w^((p^6 - 1)(p^2 + 1)) = 1` iff `w^(p^2 + 1)` lies in `Fp6`.
+        (&w * w.frobenius_map(2)?)
+            .c1
+            .enforce_equal(&Fp6VarWithBase::zero())?;
+
+        let f = Self::miller_loop_with_accumulator(ps, qs, Some((&c, &c_inv)))? * &w;
+        let c_p = c.frobenius_map(1)?;
+        let c_p2 = c.frobenius_map(2)?;
+        let c_p3 = c.frobenius_map(3)?;
+        if P::X_IS_NEGATIVE {
+            (c_p * c_p3).enforce_equal(&(f * c_p2))
+        } else {
+            (f * c_p * c_p3).enforce_equal(&c_p2)
+        }
+    }
+
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    fn gt_pow_le(
+        gt: &Self::GTVar,
+        bits: &[Boolean<ConstraintF>],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        gt.cyclotomic_pow_le(bits)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn prepare_g1(p: &Self::G1Var) -> Result<Self::G1PreparedVar, SynthesisError> {
+        Self::G1PreparedVar::from_group_var(p)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn prepare_g2(q: &Self::G2Var) -> Result<Self::G2PreparedVar, SynthesisError> {
+        Self::G2PreparedVar::from_group_var(q)
+    }
+}
@@ -0,0 +1,263 @@
+use ark_relations::r1cs::SynthesisError;
+
+use super::PairingVar as PG;
+
+use crate::{
+    boolean::Boolean,
+    fields::{fp::FpVar, fp6_2over3::Fp6VarWithBase, FieldOpsBounds, FieldVar},
+    groups::bw6::{G1AffineVar, G1PreparedVar, G1Var, G2PreparedVar, G2Var},
+};
+use ark_ec::bw6::{BW6Config, TwistType, BW6};
+use ark_ff::{BitIteratorBE, PrimeField};
+use ark_std::marker::PhantomData;
+
+/// Specifies the constraints for computing a pairing in a BW6 bilinear group.
+///
+/// By default, the pairing is computed over the base field `P::Fp` of the
+/// curve. To compute it over another constraint field `ConstraintF`, `F` can
+/// be set to `NonNativeFieldVar<P::Fp, ConstraintF>`.
+pub struct PairingVar<
+    P: BW6Config,
+    F: FieldVar<P::Fp, ConstraintF> = FpVar<<P as BW6Config>::Fp>,
+    ConstraintF: PrimeField = <P as BW6Config>::Fp,
+>(PhantomData<(P, F, ConstraintF)>)
+where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>;
+
+type Fp6V<P, F, ConstraintF> = Fp6VarWithBase<<P as BW6Config>::Fp6Config, F, ConstraintF>;
+
+impl<P, F, ConstraintF> PairingVar<P, F, ConstraintF>
+where
+    P: BW6Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    // Evaluate the line function at point p.
+    #[tracing::instrument(target = "r1cs")]
+    fn ell(
+        f: &mut Fp6V<P, F, ConstraintF>,
+        coeffs: &(F, F),
+        p: &G1AffineVar<P, F, ConstraintF>,
+    ) -> Result<(), SynthesisError> {
+        match P::TWIST_TYPE {
+            TwistType::M => {
+                let c1 = &coeffs.1 * &p.x;
+                *f = f.mul_by_014(&coeffs.0, &c1, &p.y)?;
+                Ok(())
+            },
+            TwistType::D => {
+                let c1 = &coeffs.0 * &p.x;
+                *f = f.mul_by_034(&p.y, &c1, &coeffs.1)?;
+                Ok(())
+            },
+        }
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn exp_by_x(f: &Fp6V<P, F, ConstraintF>) -> Result<Fp6V<P, F, ConstraintF>, SynthesisError> {
+        let mut result = f.cyclotomic_exp(P::X)?;
+        if P::X_IS_NEGATIVE {
+            result = result.unitary_inverse()?;
+        }
+        Ok(result)
+    }
+
+    /// Computes `f^((p^3 - 1)(p + 1))`, the easy part of the final
+    /// exponentiation.
+    #[tracing::instrument(target = "r1cs")]
+    fn final_exponentiation_first_chunk(
+        f: &Fp6V<P, F, ConstraintF>,
+    ) -> Result<Fp6V<P, F, ConstraintF>, SynthesisError> {
+        // f_p3_over_f = f^(p^3 - 1)
+        let f_p3_over_f = f.unitary_inverse()? * f.inverse()?;
+        // alpha = f^((p^3 - 1) * p)
+        let mut alpha = f_p3_over_f.clone();
+        alpha.frobenius_map_in_place(1)?;
+        Ok(alpha * &f_p3_over_f)
+    }
+
+    /// Computes the hard part of the final exponentiation, following
+    /// Algorithm 6 of <https://eprint.iacr.org/2020/351.pdf>.
+    #[tracing::instrument(target = "r1cs")]
+    fn final_exponentiation_last_chunk(
+        f: &Fp6V<P, F, ConstraintF>,
+    ) -> Result<Fp6V<P, F, ConstraintF>, SynthesisError> {
+        // R0(x) := (-103*x^7 + 70*x^6 + 269*x^5 - 197*x^4 - 314*x^3 - 73*x^2 - 263*x - 220)
+        // R1(x) := (103*x^9 - 276*x^8 + 77*x^7 + 492*x^6 - 445*x^5 - 65*x^4 + 452*x^3 - 181*x^2 + 34*x + 229)
+        // f ^ R0(u) * (f ^ p) ^ R1(u) in a 2-NAF multi-exp fashion.
+        let frobenius = |f: &Fp6V<P, F, ConstraintF>| f.frobenius_map(1);
+
+        // steps 1,2,3
+        let f0 = f.clone();
+        let f0p = frobenius(&f0)?;
+        let f1 = Self::exp_by_x(&f0)?;
+        let f1p = frobenius(&f1)?;
+        let f2 = Self::exp_by_x(&f1)?;
+        let f2p = frobenius(&f2)?;
+        let f3 = Self::exp_by_x(&f2)?;
+        let f3p = frobenius(&f3)?;
+        let f4 = Self::exp_by_x(&f3)?;
+        let f4p = frobenius(&f4)?;
+        let f5 = Self::exp_by_x(&f4)?;
+        let f5p = frobenius(&f5)?;
+        let f6 = Self::exp_by_x(&f5)?;
+        let f6p = frobenius(&f6)?;
+        let f7 = Self::exp_by_x(&f6)?;
+        let f7p = frobenius(&f7)?;
+
+        // step 4
+        let f8p = Self::exp_by_x(&f7p)?;
+        let f9p = Self::exp_by_x(&f8p)?;
+
+        // step 5
+        let result1 = &f3p * &f6p * f5p.unitary_inverse()?;
+
+        // step 6
+        let result2 = result1.square()?;
+        let f4_2p = &f4 * &f2p;
+        let tmp1_p3 = (&f0 * &f1 * &f3 * &f4_2p * &f8p).unitary_inverse()?;
+        let result3 = result2 * &f5 * &f0p * tmp1_p3;
+
+        // step 7
+        let result4 = result3.square()?;
+        let result5 = result4 * &f9p * f7.unitary_inverse()?;
+
+        // step 8
+        let result6 = result5.square()?;
+        let f2_4p = &f2 * &f4p;
+        let f4_2p_5p = &f4_2p * &f5p;
+        let tmp2_p3 = (&f2_4p * &f3 * &f3p).unitary_inverse()?;
+        let result7 = result6 * &f4_2p_5p * &f6 * &f7p * tmp2_p3;
+
+        // step 9
+        let result8 = result7.square()?;
+        let tmp3_p3 = (&f0p * &f9p).unitary_inverse()?;
+        let result9 = result8 * &f0 * &f7 * &f1p * tmp3_p3;
+
+        // step 10
+        let result10 = result9.square()?;
+        let f6p_8p = &f6p * &f8p;
+        let f5_7p = &f5 * &f7p;
+        let tmp4_p3 = f6p_8p.unitary_inverse()?;
+        let result11 = result10 * &f5_7p * &f2p * tmp4_p3;
+
+        // step 11
+        let result12 = result11.square()?;
+        let f3_6 = &f3 * &f6;
+        let f1_7 = &f1 * &f7;
+        let tmp5_p3 = (&f1_7 * &f2).unitary_inverse()?;
+        let result13 = result12 * &f3_6 * &f9p * tmp5_p3;
+
+        // step 12
+        let result14 = result13.square()?;
+        let tmp6_p3 = (&f4_2p * &f5_7p * &f6p_8p).unitary_inverse()?;
+        let result15 = result14 * &f0 * &f0p * &f3p * &f5p * tmp6_p3;
+
+        // step 13
+        let result16 = result15.square()?;
+        let tmp7_p3 = f3_6.unitary_inverse()?;
+        let result17 = result16 * &f1p * tmp7_p3;
+
+        // step 14
+        let result18 = result17.square()?;
+        let tmp8_p3 = (&f2_4p * &f4_2p_5p * &f9p).unitary_inverse()?;
+        Ok(result18 * &f1_7 * &f5_7p * &f0p * tmp8_p3)
+    }
+}
+
+impl<P, F, ConstraintF> PG<BW6<P>, ConstraintF> for PairingVar<P, F, ConstraintF>
+where
+    P: BW6Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    type G1Var = G1Var<P, F, ConstraintF>;
+    type G2Var = G2Var<P, F, ConstraintF>;
+    type G1PreparedVar = G1PreparedVar<P, F, ConstraintF>;
+    type G2PreparedVar = G2PreparedVar<P, F, ConstraintF>;
+    type GTVar = Fp6V<P, F, ConstraintF>;
+
+    #[tracing::instrument(target = "r1cs")]
+    fn miller_loop(
+        ps: &[Self::G1PreparedVar],
+        qs: &[Self::G2PreparedVar],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        // Algorithm 5 of https://eprint.iacr.org/2020/351.pdf.
+        let mut pairs_1 = vec![];
+        let mut pairs_2 = vec![];
+        for (p, q) in ps.iter().zip(qs.iter()) {
+            pairs_1.push((p, q.ell_coeffs_1.iter()));
+            pairs_2.push((p, q.ell_coeffs_2.iter()));
+        }
+
+        // f_{x + 1, Q}(P)
+        let mut f_1 = Self::GTVar::one();
+        for i in BitIteratorBE::without_leading_zeros(P::ATE_LOOP_COUNT_1).skip(1) {
+            f_1.square_in_place()?;
+
+            for &mut (p, ref mut coeffs) in pairs_1.iter_mut() {
+                Self::ell(&mut f_1, coeffs.next().unwrap(), &p.0)?;
+            }
+
+            if i {
+                for &mut (p, ref mut coeffs) in pairs_1.iter_mut() {
+                    Self::ell(&mut f_1, coeffs.next().unwrap(), &p.0)?;
+                }
+            }
+        }
+        if P::ATE_LOOP_COUNT_1_IS_NEGATIVE {
+            f_1 = f_1.unitary_inverse()?;
+        }
+
+        // f_{x^3 - x^2 - x, Q}(P)
+        let mut f_2 = Self::GTVar::one();
+        for i in (1..P::ATE_LOOP_COUNT_2.len()).rev() {
+            if i != P::ATE_LOOP_COUNT_2.len() - 1 {
+                f_2.square_in_place()?;
+            }
+
+            for &mut (p, ref mut coeffs) in pairs_2.iter_mut() {
+                Self::ell(&mut f_2, coeffs.next().unwrap(), &p.0)?;
+            }
+
+            let bit = P::ATE_LOOP_COUNT_2[i - 1];
+            if bit == 1 || bit == -1 {
+                for &mut (p, ref mut coeffs) in pairs_2.iter_mut() {
+                    Self::ell(&mut f_2, coeffs.next().unwrap(), &p.0)?;
+                }
+            }
+        }
+        if P::ATE_LOOP_COUNT_2_IS_NEGATIVE {
+            f_2 = f_2.unitary_inverse()?;
+        }
+        f_2.frobenius_map_in_place(1)?;
+
+        Ok(f_1 * &f_2)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn final_exponentiation(f: &Self::GTVar) -> Result<Self::GTVar, SynthesisError> {
+        let f = Self::final_exponentiation_first_chunk(f)?;
+        Self::final_exponentiation_last_chunk(&f)
+    }
+
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    fn gt_pow_le(
+        gt: &Self::GTVar,
+        bits: &[Boolean<ConstraintF>],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        gt.cyclotomic_pow_le(bits)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn prepare_g1(p: &Self::G1Var) -> Result<Self::G1PreparedVar, SynthesisError> {
+        Self::G1PreparedVar::from_group_var(p)
+    }
+
+    #[tracing::instrument(target = "r1cs")]
+    fn prepare_g2(q: &Self::G2Var) -> Result<Self::G2PreparedVar, SynthesisError> {
+        Self::G2PreparedVar::from_group_var(q)
+    }
+}
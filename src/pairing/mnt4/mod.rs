@@ -3,29 +3,58 @@ use ark_relations::r1cs::SynthesisError;
 use super::PairingVar as PG;
 
 use crate::{
-    fields::{fp::FpVar, fp2::Fp2Var, fp4::Fp4Var, FieldVar},
+    boolean::Boolean,
+    fields::{fp::FpVar, fp2::Fp2VarWithBase, fp4::Fp4VarWithBase, FieldOpsBounds, FieldVar},
     groups::mnt4::{
         AteAdditionCoefficientsVar, AteDoubleCoefficientsVar, G1PreparedVar, G1Var, G2PreparedVar,
         G2ProjectiveExtendedVar, G2Var,
     },
+    Vec,
 };
 use ark_ec::mnt4::{MNT4Config, MNT4};
+use ark_ff::PrimeField;
 
 use core::marker::PhantomData;
 
 /// Specifies the constraints for computing a pairing in a MNT4 bilinear group.
-pub struct PairingVar<P: MNT4Config>(PhantomData<P>);
-
-type Fp2G<P> = Fp2Var<<P as MNT4Config>::Fp2Config>;
-type Fp4G<P> = Fp4Var<<P as MNT4Config>::Fp4Config>;
+pub struct PairingVar<
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF> = FpVar<<P as MNT4Config>::Fp>,
+    ConstraintF: PrimeField = <P as MNT4Config>::Fp,
+>(PhantomData<(P, F, ConstraintF)>)
+where
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>;
+
+type Fp2G<P, F, ConstraintF> = Fp2VarWithBase<<P as MNT4Config>::Fp2Config, F, ConstraintF>;
+type Fp4G<P, F, ConstraintF> = Fp4VarWithBase<<P as MNT4Config>::Fp4Config, F, ConstraintF>;
 /// A variable corresponding to `ark_ec::mnt4::GT`.
-pub type GTVar<P> = Fp4G<P>;
-
-impl<P: MNT4Config> PairingVar<P> {
+pub type GTVar<P, F = FpVar<<P as MNT4Config>::Fp>, ConstraintF = <P as MNT4Config>::Fp> =
+    Fp4G<P, F, ConstraintF>;
+
+/// The point and line coefficients output by a doubling step of the Miller
+/// loop.
+type DoublingStep<P, F, ConstraintF> = (
+    G2ProjectiveExtendedVar<P, F, ConstraintF>,
+    AteDoubleCoefficientsVar<P, F, ConstraintF>,
+);
+/// The point and line coefficients output by an addition step of the Miller
+/// loop.
+type AdditionStep<P, F, ConstraintF> = (
+    G2ProjectiveExtendedVar<P, F, ConstraintF>,
+    AteAdditionCoefficientsVar<P, F, ConstraintF>,
+);
+
+impl<P, F, ConstraintF> PairingVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
     #[tracing::instrument(target = "r1cs", skip(r))]
     pub(crate) fn doubling_step_for_flipped_miller_loop(
-        r: &G2ProjectiveExtendedVar<P>,
-    ) -> Result<(G2ProjectiveExtendedVar<P>, AteDoubleCoefficientsVar<P>), SynthesisError> {
+        r: &G2ProjectiveExtendedVar<P, F, ConstraintF>,
+    ) -> Result<DoublingStep<P, F, ConstraintF>, SynthesisError> {
         let a = r.t.square()?;
         let b = r.x.square()?;
         let c = r.y.square()?;
@@ -60,10 +89,10 @@ impl<P: MNT4Config> PairingVar<P> {
 
     #[tracing::instrument(target = "r1cs", skip(r))]
     pub(crate) fn mixed_addition_step_for_flipped_miller_loop(
-        x: &Fp2G<P>,
-        y: &Fp2G<P>,
-        r: &G2ProjectiveExtendedVar<P>,
-    ) -> Result<(G2ProjectiveExtendedVar<P>, AteAdditionCoefficientsVar<P>), SynthesisError> {
+        x: &Fp2G<P, F, ConstraintF>,
+        y: &Fp2G<P, F, ConstraintF>,
+        r: &G2ProjectiveExtendedVar<P, F, ConstraintF>,
+    ) -> Result<AdditionStep<P, F, ConstraintF>, SynthesisError> {
         let a = y.square()?;
         let b = &r.t * x;
         let d = ((&r.z + y).square()? - &a - &r.t) * &r.t;
@@ -91,70 +120,93 @@ impl<P: MNT4Config> PairingVar<P> {
         Ok((r2, coeff))
     }
 
-    #[tracing::instrument(target = "r1cs", skip(p, q))]
+    /// Computes the Miller loop over all pairs of `ps` and `qs` at once. The
+    /// line functions of all pairs are multiplied into a single accumulator,
+    /// so that each step needs only one squaring, regardless of the number of
+    /// pairs.
+    #[tracing::instrument(target = "r1cs", skip(ps, qs))]
     pub(crate) fn ate_miller_loop(
-        p: &G1PreparedVar<P>,
-        q: &G2PreparedVar<P>,
-    ) -> Result<Fp4G<P>, SynthesisError> {
-        let l1_coeff = Fp2G::<P>::new(p.x.clone(), FpVar::<P::Fp>::zero()) - &q.x_over_twist;
-
-        let mut f = Fp4G::<P>::one();
+        ps: &[G1PreparedVar<P, F, ConstraintF>],
+        qs: &[G2PreparedVar<P, F, ConstraintF>],
+    ) -> Result<Fp4G<P, F, ConstraintF>, SynthesisError> {
+        let pairs = ps
+            .iter()
+            .zip(qs)
+            .map(|(p, q)| {
+                let l1_coeff =
+                    Fp2G::<P, F, ConstraintF>::new(p.x.clone(), F::zero()) - &q.x_over_twist;
+                let y_over_twist_neg = q.y_over_twist.negate()?;
+                Ok((p, q, l1_coeff, y_over_twist_neg))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        let mut f = Fp4G::<P, F, ConstraintF>::one();
 
         let mut add_idx: usize = 0;
 
         // code below gets executed for all bits (EXCEPT the MSB itself) of
         // mnt6_param_p (skipping leading zeros) in MSB to LSB order
-        let y_over_twist_neg = &q.y_over_twist.negate()?;
+        //
+        // Since `p.x_twist = p.x * TWIST` and `p.y_twist = p.y * TWIST`, the
+        // line functions only need to scale (constant multiples of) the
+        // coefficients of `q` by `p.x` and `p.y`, which is cheaper than
+        // multiplying by the twisted coordinates directly.
         for (dbl_idx, bit) in P::ATE_LOOP_COUNT.iter().skip(1).enumerate() {
-            let dc = &q.double_coefficients[dbl_idx];
-
-            let g_rr_at_p = Fp4G::<P>::new(
-                &dc.c_l - &dc.c_4c - &dc.c_j * &p.x_twist,
-                &dc.c_h * &p.y_twist,
-            );
-
-            f = f.square()? * &g_rr_at_p;
-
-            let g_rq_at_p;
-            // Compute l_{R,Q}(P) if bit == 1, and l_{R,-Q}(P) if bit == -1
-            if *bit == 1 {
-                let ac = &q.addition_coefficients[add_idx];
-                add_idx += 1;
-
-                g_rq_at_p = Fp4G::<P>::new(
-                    &ac.c_rz * &p.y_twist,
-                    (&q.y_over_twist * &ac.c_rz + &l1_coeff * &ac.c_l1).negate()?,
-                );
-            } else if *bit == -1 {
-                let ac = &q.addition_coefficients[add_idx];
-                add_idx += 1;
+            f.square_in_place()?;
+
+            for (p, q, ..) in &pairs {
+                let dc = &q.double_coefficients[dbl_idx];
+
+                // g_rr_at_p = (c_l - c_4c - c_j * x_twist, c_h * y_twist)
+                let c_j = &dc.c_j * P::TWIST;
+                let g_rr_at_p_c0 = &dc.c_l
+                    - &dc.c_4c
+                    - Fp2G::<P, F, ConstraintF>::new(&c_j.c0 * &p.x, &c_j.c1 * &p.x);
+                let g_rr_at_p_c1 = &dc.c_h * P::TWIST;
+                f = f.mul_by_c0_yc1(&g_rr_at_p_c0, &g_rr_at_p_c1, &p.y)?;
+            }
 
-                g_rq_at_p = Fp4G::<P>::new(
-                    &ac.c_rz * &p.y_twist,
-                    (y_over_twist_neg * &ac.c_rz + &l1_coeff * &ac.c_l1).negate()?,
-                );
-            } else {
+            if *bit == 0 {
                 continue;
             }
+            for (p, q, l1_coeff, y_over_twist_neg) in &pairs {
+                // Compute l_{R,Q}(P) if bit == 1, and l_{R,-Q}(P) if bit == -1
+                let y_over_twist = if *bit == 1 {
+                    &q.y_over_twist
+                } else {
+                    y_over_twist_neg
+                };
+                let ac = &q.addition_coefficients[add_idx];
 
-            f *= &g_rq_at_p;
+                // g_rq_at_p = (c_rz * y_twist, -(y_over_twist * c_rz + l1_coeff * c_l1))
+                let g_rq_at_p_c0 = &ac.c_rz * P::TWIST;
+                let g_rq_at_p_c1 = (y_over_twist * &ac.c_rz + l1_coeff * &ac.c_l1).negate()?;
+                f = f.mul_by_yc0_c1(&g_rq_at_p_c0, &g_rq_at_p_c1, &p.y)?;
+            }
+            add_idx += 1;
         }
 
         if P::ATE_IS_LOOP_COUNT_NEG {
-            let ac = &q.addition_coefficients[add_idx];
+            // The inverse of the product is the product of the inverses, so a
+            // single inversion suffices for all pairs.
+            for (p, q, l1_coeff, _) in &pairs {
+                let ac = &q.addition_coefficients[add_idx];
 
-            let g_rnegr_at_p = Fp4G::<P>::new(
-                &ac.c_rz * &p.y_twist,
-                (&q.y_over_twist * &ac.c_rz + &l1_coeff * &ac.c_l1).negate()?,
-            );
-            f = (&f * &g_rnegr_at_p).inverse()?;
+                let g_rnegr_at_p_c0 = &ac.c_rz * P::TWIST;
+                let g_rnegr_at_p_c1 =
+                    (&q.y_over_twist * &ac.c_rz + l1_coeff * &ac.c_l1).negate()?;
+                f = f.mul_by_yc0_c1(&g_rnegr_at_p_c0, &g_rnegr_at_p_c1, &p.y)?;
+            }
+            f = f.inverse()?;
         }
 
         Ok(f)
     }
 
     #[tracing::instrument(target = "r1cs", skip(value))]
-    pub(crate) fn final_exponentiation(value: &Fp4G<P>) -> Result<GTVar<P>, SynthesisError> {
+    pub(crate) fn final_exponentiation(
+        value: &Fp4G<P, F, ConstraintF>,
+    ) -> Result<GTVar<P, F, ConstraintF>, SynthesisError> {
         let value_inv = value.inverse()?;
         let value_to_first_chunk = Self::final_exponentiation_first_chunk(value, &value_inv)?;
         let value_inv_to_first_chunk = Self::final_exponentiation_first_chunk(&value_inv, value)?;
@@ -163,9 +215,9 @@ impl<P: MNT4Config> PairingVar<P> {
 
     #[tracing::instrument(target = "r1cs", skip(elt, elt_inv))]
     fn final_exponentiation_first_chunk(
-        elt: &Fp4G<P>,
-        elt_inv: &Fp4G<P>,
-    ) -> Result<Fp4G<P>, SynthesisError> {
+        elt: &Fp4G<P, F, ConstraintF>,
+        elt_inv: &Fp4G<P, F, ConstraintF>,
+    ) -> Result<Fp4G<P, F, ConstraintF>, SynthesisError> {
         // (q^2-1)
 
         // elt_q2 = elt^(q^2)
@@ -176,9 +228,9 @@ impl<P: MNT4Config> PairingVar<P> {
 
     #[tracing::instrument(target = "r1cs", skip(elt, elt_inv))]
     fn final_exponentiation_last_chunk(
-        elt: &Fp4G<P>,
-        elt_inv: &Fp4G<P>,
-    ) -> Result<Fp4G<P>, SynthesisError> {
+        elt: &Fp4G<P, F, ConstraintF>,
+        elt_inv: &Fp4G<P, F, ConstraintF>,
+    ) -> Result<Fp4G<P, F, ConstraintF>, SynthesisError> {
         let elt_clone = elt.clone();
         let elt_inv_clone = elt_inv.clone();
 
@@ -196,24 +248,25 @@ impl<P: MNT4Config> PairingVar<P> {
     }
 }
 
-impl<P: MNT4Config> PG<MNT4<P>, P::Fp> for PairingVar<P> {
-    type G1Var = G1Var<P>;
-    type G2Var = G2Var<P>;
-    type G1PreparedVar = G1PreparedVar<P>;
-    type G2PreparedVar = G2PreparedVar<P>;
-    type GTVar = GTVar<P>;
+impl<P, F, ConstraintF> PG<MNT4<P>, ConstraintF> for PairingVar<P, F, ConstraintF>
+where
+    P: MNT4Config,
+    F: FieldVar<P::Fp, ConstraintF>,
+    for<'a> &'a F: FieldOpsBounds<'a, P::Fp, F>,
+    ConstraintF: PrimeField,
+{
+    type G1Var = G1Var<P, F, ConstraintF>;
+    type G2Var = G2Var<P, F, ConstraintF>;
+    type G1PreparedVar = G1PreparedVar<P, F, ConstraintF>;
+    type G2PreparedVar = G2PreparedVar<P, F, ConstraintF>;
+    type GTVar = GTVar<P, F, ConstraintF>;
 
     #[tracing::instrument(target = "r1cs")]
     fn miller_loop(
         ps: &[Self::G1PreparedVar],
         qs: &[Self::G2PreparedVar],
     ) -> Result<Self::GTVar, SynthesisError> {
-        let mut result = Fp4G::<P>::one();
-        for (p, q) in ps.iter().zip(qs) {
-            result *= Self::ate_miller_loop(p, q)?;
-        }
-
-        Ok(result)
+        Self::ate_miller_loop(ps, qs)
     }
 
     #[tracing::instrument(target = "r1cs")]
@@ -221,6 +274,14 @@ impl<P: MNT4Config> PG<MNT4<P>, P::Fp> for PairingVar<P> {
         Self::final_exponentiation(r)
     }
 
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    fn gt_pow_le(
+        gt: &Self::GTVar,
+        bits: &[Boolean<ConstraintF>],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        gt.cyclotomic_pow_le(bits)
+    }
+
     #[tracing::instrument(target = "r1cs")]
     fn prepare_g1(p: &Self::G1Var) -> Result<Self::G1PreparedVar, SynthesisError> {
         Self::G1PreparedVar::from_group_var(p)
@@ -7,11 +7,17 @@ use core::fmt::Debug;
 
 /// This module implements pairings for BLS12 bilinear groups.
 pub mod bls12;
+/// This module implements pairings for BN bilinear groups.
+pub mod bn;
+/// This module implements pairings for BW6 bilinear groups.
+pub mod bw6;
 /// This module implements pairings for MNT4 bilinear groups.
 pub mod mnt4;
 /// This module implements pairings for MNT6 bilinear groups.
 pub mod mnt6;
 
+mod residue;
+
 /// Specifies the constraints for computing a pairing in the yybilinear group
 /// `E`.
 pub trait PairingVar<E: Pairing, ConstraintF: Field = <<E as Pairing>::G1 as CurveGroup>::BaseField>
@@ -78,6 +84,40 @@ pub trait PairingVar<E: Pairing, ConstraintF: Field = <<E as Pairing>::G1 as Cur
         Self::final_exponentiation(&miller_result)
     }
 
+    /// Enforces that the product of pairings over the elements in `p` and `q`
+    /// is one.
+    ///
+    /// The default implementation computes [`Self::product_of_pairings`].
+    /// Implementations may override this to avoid the final exponentiation,
+    /// for example by witnessing a residue of the Miller loop output.
+    #[tracing::instrument(target = "r1cs")]
+    fn enforce_pairing_product_is_one(
+        p: &[Self::G1PreparedVar],
+        q: &[Self::G2PreparedVar],
+    ) -> Result<(), SynthesisError> {
+        Self::product_of_pairings(p, q)?.enforce_equal(&Self::GTVar::one())
+    }
+
+    /// Computes `gt^k` for an element `gt` of `GT`, where `bits` is the
+    /// little-endian bit representation of the possibly non-constant `k`.
+    ///
+    /// `gt` must lie in `GT`, i.e., in the subgroup of order `r` of the
+    /// cyclotomic subgroup, such as the output of
+    /// [`Self::final_exponentiation`]. Implementations may rely on this, and
+    /// the result is unspecified otherwise; use [`FieldVar::pow_le`] for
+    /// arbitrary field elements.
+    ///
+    /// The default implementation computes [`FieldVar::pow_le`].
+    /// Implementations may override this to exploit that `GT` lies in the
+    /// cyclotomic subgroup, for example via `QuadExtVar::cyclotomic_pow_le`.
+    #[tracing::instrument(target = "r1cs", skip(bits))]
+    fn gt_pow_le(
+        gt: &Self::GTVar,
+        bits: &[Boolean<ConstraintF>],
+    ) -> Result<Self::GTVar, SynthesisError> {
+        gt.pow_le(bits)
+    }
+
     /// Performs the precomputation to generate `Self::G1PreparedVar`.
     fn prepare_g1(q: &Self::G1Var) -> Result<Self::G1PreparedVar, SynthesisError>;
 
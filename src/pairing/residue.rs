@@ -0,0 +1,118 @@
+use ark_ff::{Field, PrimeField};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::Vec;
+
+/// A prepared input of a Miller loop, for which the residue witnesses are
+/// computed out of circuit.
+pub(crate) trait PreparedVar<ConstraintF: Field>: Sized {
+    /// Returns the constraint system of the variables of `self`, which is
+    /// `None` if they are all constants.
+    fn cs(&self) -> ConstraintSystemRef<ConstraintF>;
+
+    /// Outputs a copy of `self` in which every variable is replaced by a
+    /// constant with the same value.
+    fn constant_copy(&self) -> Result<Self, SynthesisError>;
+}
+
+/// Returns the constraint system of the variables of `ps` and `qs`.
+pub(crate) fn prepared_cs<ConstraintF, G1, G2>(
+    ps: &[G1],
+    qs: &[G2],
+) -> ConstraintSystemRef<ConstraintF>
+where
+    ConstraintF: Field,
+    G1: PreparedVar<ConstraintF>,
+    G2: PreparedVar<ConstraintF>,
+{
+    ps.iter()
+        .map(G1::cs)
+        .chain(qs.iter().map(G2::cs))
+        .fold(ConstraintSystemRef::None, |acc, cs| acc.or(cs))
+}
+
+/// Outputs constant copies of `ps` and `qs`, whose Miller loop can be
+/// evaluated without generating constraints.
+pub(crate) fn constant_copies<ConstraintF, G1, G2>(
+    ps: &[G1],
+    qs: &[G2],
+) -> Result<(Vec<G1>, Vec<G2>), SynthesisError>
+where
+    ConstraintF: Field,
+    G1: PreparedVar<ConstraintF>,
+    G2: PreparedVar<ConstraintF>,
+{
+    let ps = ps.iter().map(G1::constant_copy).collect::<Result<_, _>>()?;
+    let qs = qs.iter().map(G2::constant_copy).collect::<Result<_, _>>()?;
+    Ok((ps, qs))
+}
+
+/// Converts little-endian `u64` limbs into a `BigUint`.
+pub(crate) fn biguint_from_limbs(limbs: &[u64]) -> BigUint {
+    limbs
+        .iter()
+        .rev()
+        .fold(BigUint::zero(), |acc, limb| (acc << 64) + *limb)
+}
+
+/// Outputs the characteristic of `F`.
+pub(crate) fn characteristic<F: Field>() -> BigUint {
+    F::BasePrimeField::MODULUS.into()
+}
+
+/// Outputs the inverse of `a` modulo `m`, if it exists.
+fn mod_inverse(a: &BigInt, m: &BigUint) -> Option<BigUint> {
+    let m = BigInt::from(m.clone());
+    let egcd = a.mod_floor(&m).extended_gcd(&m);
+    egcd.gcd
+        .is_one()
+        .then(|| egcd.x.mod_floor(&m).to_biguint().unwrap())
+}
+
+/// Given the output `f` of a Miller loop for a pairing with embedding degree
+/// 12 and a multiple `lambda` of the group order `r`, outputs `(c, w)` such
+/// that `c^lambda = f * w` and `w^((p^6 - 1)(p^2 + 1)) = 1`, following
+/// Novakovic and Eagen, "On Proving Pairings" (<https://eprint.iacr.org/2024/640>).
+///
+/// Such a pair exists if and only if `f^((p^12 - 1) / r) = 1`, that is, if
+/// the final exponentiation of `f` is one. Otherwise, the output does not
+/// satisfy the above equation.
+///
+/// # Panics
+///
+/// This function panics if `lambda / r` shares a factor with the part of
+/// `p^12 - 1` that is not killed by the easy part of the final
+/// exponentiation, in which case such a `w` does not always exist.
+pub(crate) fn residue_witness<F: Field>(f: &F, lambda: &BigInt, r: &BigUint) -> (F, F) {
+    let p = characteristic::<F>();
+    let n = p.pow(12) - 1u32;
+    let p_squared = p.pow(2);
+    let phi = p_squared.pow(2) - &p_squared + 1u32;
+
+    // Split `n = n_0 * n_1`, where `n_1` consists of the primes that divide
+    // `phi = Φ_12(p)`. The subgroup of order `n_0` is killed by the easy part of
+    // the final exponentiation.
+    let mut n_0 = n;
+    let mut n_1 = BigUint::one();
+    let mut g = n_0.gcd(&phi);
+    while !g.is_one() {
+        n_0 /= &g;
+        n_1 *= &g;
+        g = n_0.gcd(&g);
+    }
+
+    // Choose `w` to cancel the component of `f` in the subgroup of order `n_0`.
+    let e_0 = &n_1 * mod_inverse(&BigInt::from(n_1.clone()), &n_0).unwrap();
+    let w = f.pow(e_0.to_u64_digits()).inverse().unwrap();
+
+    // If `f^((p^12 - 1) / r) = 1`, then the order of `f * w` divides `n_1 / r`,
+    // which is coprime to `lambda`.
+    let n_1_over_r = n_1 / r;
+    let e_1 = mod_inverse(lambda, &n_1_over_r)
+        .expect("`lambda / r` must be coprime to the order of the residues");
+    let c = (*f * &w).pow(e_1.to_u64_digits());
+    (c, w)
+}
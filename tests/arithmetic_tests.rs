@@ -10,13 +10,17 @@ use ark_r1cs_std::{
     alloc::AllocVar,
     eq::EqGadget,
     fields::{
-        nonnative::{AllocatedNonNativeFieldVar, NonNativeFieldVar},
+        nonnative::{
+            params::{NonNativeFieldParams, OptimizationType},
+            AllocatedNonNativeFieldVar, NonNativeFieldConfig, NonNativeFieldMulResultVar,
+            NonNativeFieldVar, NonNativeFieldVarWithParams,
+        },
         FieldVar,
     },
     R1CSVar,
 };
 use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef};
-use ark_std::rand::RngCore;
+use ark_std::{rand::RngCore, UniformRand};
 
 #[cfg(not(ci))]
 const NUM_REPETITIONS: usize = 100;
@@ -559,6 +563,52 @@ fn inverse_stress_test<TargetField: PrimeField, BaseField: PrimeField, R: RngCor
     }
 }
 
+fn mul_result_accumulation_test<TargetField: PrimeField, BaseField: PrimeField, R: RngCore>(
+    cs: ConstraintSystemRef<BaseField>,
+    rng: &mut R,
+) {
+    let mut acc_native = TargetField::zero();
+    let mut acc = NonNativeFieldMulResultVar::<TargetField, BaseField>::zero();
+    for i in 0..TEST_COUNT {
+        let a_native = TargetField::rand(rng);
+        let b_native = TargetField::rand(rng);
+        let c_native = TargetField::rand(rng);
+        let a = NonNativeFieldVar::<TargetField, BaseField>::new_witness(
+            ark_relations::ns!(cs, "a"),
+            || Ok(a_native),
+        )
+        .unwrap();
+        let b = NonNativeFieldVar::<TargetField, BaseField>::new_witness(
+            ark_relations::ns!(cs, "b"),
+            || Ok(b_native),
+        )
+        .unwrap();
+        let prod = a.mul_without_reduce(&b).unwrap();
+
+        match i % 4 {
+            0 => {
+                acc += &prod;
+                acc_native += a_native * b_native;
+            },
+            1 => {
+                acc -= &prod;
+                acc_native -= a_native * b_native;
+            },
+            2 => {
+                acc += prod.mul_constant(&c_native).unwrap();
+                acc_native += a_native * b_native * c_native;
+            },
+            _ => {
+                acc = acc.mul_constant(&-TargetField::from(3u8)).unwrap() - c_native;
+                acc_native = acc_native * -TargetField::from(3u8) - c_native;
+            },
+        }
+    }
+
+    let acc = acc.reduce().unwrap();
+    assert_eq!(acc.value().unwrap(), acc_native);
+}
+
 macro_rules! nonnative_test_individual {
     ($test_method:ident, $test_name:ident, $test_target_field:ty, $test_base_field:ty) => {
         paste::item! {
@@ -668,6 +718,12 @@ macro_rules! nonnative_test {
             $test_target_field,
             $test_base_field
         );
+        nonnative_test_individual!(
+            mul_result_accumulation_test,
+            $test_name,
+            $test_target_field,
+            $test_base_field
+        );
     };
 }
 
@@ -719,3 +775,145 @@ nonnative_test!(
 );
 nonnative_test!(PallasFqFr, ark_pallas::Fq, ark_pallas::Fr);
 nonnative_test!(PallasFrFq, ark_pallas::Fr, ark_pallas::Fq);
+
+#[derive(Clone, Debug)]
+struct SixtyFourBitLimbs;
+
+impl NonNativeFieldParams for SixtyFourBitLimbs {
+    fn params(
+        target_field_size: usize,
+        _base_field_size: usize,
+        _optimization_type: OptimizationType,
+    ) -> NonNativeFieldConfig {
+        NonNativeFieldConfig {
+            num_limbs: (target_field_size + 63) / 64,
+            bits_per_limb: 64,
+        }
+    }
+}
+
+#[test]
+fn custom_params_test() {
+    type TargetField = <Bls12_381 as Pairing>::ScalarField;
+    type BaseField = <MNT4_298 as Pairing>::ScalarField;
+    type Var = NonNativeFieldVarWithParams<TargetField, BaseField, SixtyFourBitLimbs>;
+
+    let rng = &mut ark_std::test_rng();
+    let cs = ConstraintSystem::<BaseField>::new_ref();
+
+    let a_native = TargetField::rand(rng);
+    let b_native = TargetField::rand(rng);
+    let a = Var::new_witness(ark_relations::ns!(cs, "a"), || Ok(a_native)).unwrap();
+    let b = Var::new_witness(ark_relations::ns!(cs, "b"), || Ok(b_native)).unwrap();
+
+    match &a {
+        NonNativeFieldVarWithParams::Var(v) => {
+            assert_eq!(v.limbs.len(), 4);
+            for limb in &v.limbs {
+                assert!(limb.value().unwrap().into_bigint().num_bits() <= 64);
+            }
+        },
+        NonNativeFieldVarWithParams::Constant(_) => unreachable!(),
+    }
+
+    let c = &a * &b + &a;
+    assert_eq!(c.value().unwrap(), a_native * b_native + a_native);
+    c.enforce_equal(&Var::constant(a_native * b_native + a_native))
+        .unwrap();
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[derive(Clone, Debug)]
+struct TooFewLimbs;
+
+impl NonNativeFieldParams for TooFewLimbs {
+    fn params(
+        target_field_size: usize,
+        _base_field_size: usize,
+        _optimization_type: OptimizationType,
+    ) -> NonNativeFieldConfig {
+        NonNativeFieldConfig {
+            num_limbs: target_field_size / 64,
+            bits_per_limb: 64,
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "cannot represent")]
+fn invalid_custom_params_test() {
+    type TargetField = <Bls12_381 as Pairing>::ScalarField;
+    type BaseField = <MNT4_298 as Pairing>::ScalarField;
+    type Var = NonNativeFieldVarWithParams<TargetField, BaseField, TooFewLimbs>;
+
+    let cs = ConstraintSystem::<BaseField>::new_ref();
+    let _ = Var::new_witness(ark_relations::ns!(cs, "a"), || Ok(TargetField::from(1u8)));
+}
+
+#[derive(Clone, Debug)]
+struct TooManyLimbs;
+
+impl NonNativeFieldParams for TooManyLimbs {
+    fn params(
+        target_field_size: usize,
+        _base_field_size: usize,
+        _optimization_type: OptimizationType,
+    ) -> NonNativeFieldConfig {
+        NonNativeFieldConfig {
+            num_limbs: target_field_size / 32 + 2,
+            bits_per_limb: 32,
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "are too many")]
+fn too_many_limbs_custom_params_test() {
+    type TargetField = <Bls12_381 as Pairing>::ScalarField;
+    type BaseField = <MNT4_298 as Pairing>::ScalarField;
+    type Var = NonNativeFieldVarWithParams<TargetField, BaseField, TooManyLimbs>;
+
+    let cs = ConstraintSystem::<BaseField>::new_ref();
+    let _ = Var::new_witness(ark_relations::ns!(cs, "a"), || Ok(TargetField::from(1u8)));
+}
+
+#[test]
+fn mul_result_accumulation_auto_reduce_test() {
+    type TargetField = <Bls12_381 as Pairing>::ScalarField;
+    type BaseField = <MNT4_298 as Pairing>::ScalarField;
+
+    let rng = &mut ark_std::test_rng();
+    let cs = ConstraintSystem::<BaseField>::new_ref();
+
+    let a_native = TargetField::rand(rng);
+    let b_native = TargetField::rand(rng);
+    let a = NonNativeFieldVar::<TargetField, BaseField>::new_witness(
+        ark_relations::ns!(cs, "a"),
+        || Ok(a_native),
+    )
+    .unwrap();
+    let b = NonNativeFieldVar::<TargetField, BaseField>::new_witness(
+        ark_relations::ns!(cs, "b"),
+        || Ok(b_native),
+    )
+    .unwrap();
+    let prod = match a.mul_without_reduce(&b).unwrap() {
+        NonNativeFieldMulResultVar::Var(v) => v,
+        NonNativeFieldMulResultVar::Constant(_) => unreachable!(),
+    };
+
+    // Accumulate far more products than the limbs can hold without a
+    // reduction; the accumulator must reduce itself along the way.
+    let mut acc = prod.clone();
+    for _ in 1..1024 {
+        acc = acc.add(&prod).unwrap();
+        assert!(acc.limb_bit_bound() < BaseField::MODULUS_BIT_SIZE as usize);
+    }
+
+    let acc = acc.reduce().unwrap();
+    assert_eq!(
+        acc.value().unwrap(),
+        a_native * b_native * TargetField::from(1024u32)
+    );
+    assert!(cs.is_satisfied().unwrap());
+}
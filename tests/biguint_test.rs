@@ -0,0 +1,227 @@
+use ark_r1cs_std::{
+    alloc::AllocVar, boolean::Boolean, eq::EqGadget, fields::nonnative::BigUintVar, R1CSVar,
+};
+use ark_relations::r1cs::ConstraintSystem;
+use ark_std::{cmp::Ordering, rand::RngCore, vec::Vec};
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+type F = ark_bls12_381::Fr;
+
+fn rand_biguint<R: RngCore>(num_bits: usize, rng: &mut R) -> BigUint {
+    let mut bytes = vec![0u8; (num_bits + 7) / 8];
+    rng.fill_bytes(&mut bytes);
+    BigUint::from_bytes_le(&bytes) >> (bytes.len() * 8 - num_bits)
+}
+
+fn to_bits_le(value: &BigUint, num_bits: usize) -> Vec<bool> {
+    (0..num_bits).map(|i| value.bit(i as u64)).collect()
+}
+
+#[test]
+fn biguint_arithmetic_test() {
+    let mut rng = ark_std::test_rng();
+    for (a_bits, b_bits) in [(256, 256), (512, 130), (64, 1000), (2048, 2048)] {
+        let cs = ConstraintSystem::<F>::new_ref();
+        let a = rand_biguint(a_bits, &mut rng);
+        let b = rand_biguint(b_bits, &mut rng);
+        let a_var =
+            BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(a.clone()), a_bits).unwrap();
+        let b_var =
+            BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(b.clone()), b_bits).unwrap();
+
+        let sum = a_var.add(&b_var).unwrap();
+        assert_eq!(sum.value().unwrap(), &a + &b);
+
+        let product = a_var.mul(&b_var).unwrap();
+        assert_eq!(product.value().unwrap(), &a * &b);
+
+        let (quotient, remainder) = product.add(&a_var).unwrap().div_rem(&b_var).unwrap();
+        let c = &a * &b + &a;
+        assert_eq!(quotient.value().unwrap(), &c / &b);
+        assert_eq!(remainder.value().unwrap(), &c % &b);
+
+        // Products of unnormalized values are normalized as needed.
+        let square = product.mul(&product).unwrap().add(&sum).unwrap();
+        assert_eq!(square.value().unwrap(), &a * &b * &a * &b + &a + &b);
+        let normalized = square.normalize().unwrap();
+        assert!(normalized.is_normalized());
+        normalized.enforce_equal(&square).unwrap();
+
+        let constant = BigUintVar::constant(&b);
+        let mixed = a_var.mul(&constant).unwrap().add(&constant).unwrap();
+        assert_eq!(mixed.value().unwrap(), &a * &b + &b);
+        mixed.enforce_equal(&product.add(&b_var).unwrap()).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn biguint_cmp_test() {
+    let mut rng = ark_std::test_rng();
+    for _ in 0..10 {
+        let cs = ConstraintSystem::<F>::new_ref();
+        let a = rand_biguint(300, &mut rng);
+        let b = rand_biguint(200, &mut rng) + (rng.next_u32() % 2) * &a;
+        let a_var =
+            BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(a.clone()), 300).unwrap();
+        let b_var =
+            BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(b.clone()), 301).unwrap();
+
+        for ordering in [Ordering::Less, Ordering::Greater] {
+            for check_equality in [false, true] {
+                let expected = a.cmp(&b) == ordering || (check_equality && a == b);
+                let is_cmp = a_var.is_cmp(&b_var, ordering, check_equality).unwrap();
+                assert_eq!(is_cmp.value().unwrap(), expected);
+            }
+        }
+        assert_eq!(a_var.is_eq(&b_var).unwrap().value().unwrap(), a == b);
+        assert!(cs.is_satisfied().unwrap());
+
+        let ordering = if a < b {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        };
+        a_var.enforce_cmp(&b_var, ordering, false).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn biguint_wrapping_test() {
+    let mut rng = ark_std::test_rng();
+    for (num_bits, width) in [(256, 256), (300, 64), (2048, 2048), (100, 1), (130, 0)] {
+        let cs = ConstraintSystem::<F>::new_ref();
+        let a = rand_biguint(num_bits, &mut rng);
+        let b = rand_biguint(num_bits, &mut rng);
+        let modulus: BigUint = BigUint::from(1u8) << width;
+        let a_var = BigUintVar::new_witness(cs.clone(), || Ok(a.clone())).unwrap();
+        let b_var = BigUintVar::new_witness(cs.clone(), || Ok(b.clone())).unwrap();
+
+        let sum = a_var.wrapping_add(&b_var, width).unwrap();
+        assert_eq!(sum.value().unwrap(), (&a + &b) % &modulus);
+        assert!(sum.num_bits() <= width);
+        let product = a_var.wrapping_mul(&b_var, width).unwrap();
+        assert_eq!(product.value().unwrap(), (&a * &b) % &modulus);
+        assert!(product.num_bits() <= width);
+
+        let constant = BigUintVar::constant(&b);
+        let product = a_var.wrapping_mul(&constant, width).unwrap();
+        assert_eq!(product.value().unwrap(), (&a * &b) % &modulus);
+        let product = BigUintVar::<F>::constant(&a)
+            .wrapping_mul(&constant, width)
+            .unwrap();
+        assert!(product.is_constant());
+        assert_eq!(product.value().unwrap(), (&a * &b) % &modulus);
+
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    // Truncation keeps the low bits, and its result is constrained.
+    let cs = ConstraintSystem::<F>::new_ref();
+    let a = BigUintVar::new_witness(cs.clone(), || Ok(BigUint::from(300u16))).unwrap();
+    let low = a.truncate(8).unwrap();
+    assert_eq!(low.value().unwrap(), BigUint::from(44u8));
+    assert!(cs.is_satisfied().unwrap());
+    let wrong = BigUintVar::new_witness(cs.clone(), || Ok(BigUint::from(45u8))).unwrap();
+    low.enforce_equal(&wrong).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn biguint_alloc_test() {
+    let cs = ConstraintSystem::<F>::new_ref();
+    let value: BigUint = BigUint::from(1u8) << 200;
+    let var = BigUintVar::new_witness(cs.clone(), || Ok(value.clone())).unwrap();
+    assert_eq!(var.value().unwrap(), value);
+    assert_eq!(var.num_bits(), 201);
+    let input = BigUintVar::new_input(cs.clone(), || Ok(value.clone())).unwrap();
+    input.enforce_equal(&var).unwrap();
+    let constant = BigUintVar::new_constant(cs.clone(), value.clone()).unwrap();
+    assert!(constant.is_constant());
+    constant.enforce_equal(&var).unwrap();
+    assert!(cs.is_satisfied().unwrap());
+
+    // The number of limbs depends on the value, which must be available.
+    assert!(BigUintVar::new_witness(cs, || Err::<BigUint, _>(
+        ark_relations::r1cs::SynthesisError::AssignmentMissing
+    ))
+    .is_err());
+}
+
+#[test]
+fn biguint_div_rem_rejects_wrong_remainder_test() {
+    let cs = ConstraintSystem::<F>::new_ref();
+    let a =
+        BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(BigUint::from(100u8)), 8).unwrap();
+    let m =
+        BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(BigUint::from(7u8)), 8).unwrap();
+    let (_, r) = a.div_rem(&m).unwrap();
+    assert_eq!(r.value().unwrap(), BigUint::from(2u8));
+    assert!(cs.is_satisfied().unwrap());
+
+    let wrong =
+        BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(BigUint::from(9u8)), 8).unwrap();
+    r.enforce_equal(&wrong).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+
+    let zero = BigUintVar::constant(&BigUint::zero());
+    assert!(BigUintVar::<F>::constant(&BigUint::from(1u8))
+        .div_rem(&zero)
+        .is_err());
+}
+
+#[test]
+fn biguint_pow_mod_test() {
+    let mut rng = ark_std::test_rng();
+
+    // An RSA-style verification with a public exponent and a witness modulus.
+    let cs = ConstraintSystem::<F>::new_ref();
+    let n = rand_biguint(1024, &mut rng) | BigUint::from(1u8);
+    let base = rand_biguint(1024, &mut rng) % &n;
+    let e = BigUint::from(65537u32);
+    let n_var = BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(n.clone()), 1024).unwrap();
+    let base_var =
+        BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(base.clone()), 1024).unwrap();
+    let e_bits = to_bits_le(&e, 17)
+        .into_iter()
+        .map(Boolean::constant)
+        .collect::<Vec<_>>();
+    let result = base_var.pow_mod_le(&e_bits, &n_var).unwrap();
+    assert_eq!(result.value().unwrap(), base.modpow(&e, &n));
+    assert!(cs.is_satisfied().unwrap());
+
+    // A witness exponent.
+    let cs = ConstraintSystem::<F>::new_ref();
+    let n = rand_biguint(256, &mut rng) | BigUint::from(1u8);
+    let base = rand_biguint(300, &mut rng);
+    let e = rand_biguint(64, &mut rng);
+    let n_var = BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(n.clone()), 256).unwrap();
+    let base_var =
+        BigUintVar::new_witness_with_num_bits(cs.clone(), || Ok(base.clone()), 300).unwrap();
+    let e_bits = to_bits_le(&e, 64)
+        .into_iter()
+        .map(|b| Boolean::new_witness(cs.clone(), || Ok(b)).unwrap())
+        .collect::<Vec<_>>();
+    let result = base_var.pow_mod_le(&e_bits, &n_var).unwrap();
+    assert_eq!(result.value().unwrap(), base.modpow(&e, &n));
+    assert!(cs.is_satisfied().unwrap());
+
+    // A constant base, modulus and exponent. Only the leading one bit may
+    // skip the squaring and the multiplication.
+    for (base, e) in [(3u32, 3u32), (3, 2), (7, 0b1011), (0, 5), (5, 0)] {
+        let n = BigUint::from(1000003u32);
+        let (base, e) = (BigUint::from(base), BigUint::from(e));
+        let e_bits = to_bits_le(&e, 8)
+            .into_iter()
+            .map(Boolean::constant)
+            .collect::<Vec<_>>();
+        let result = BigUintVar::<F>::constant(&base)
+            .pow_mod_le(&e_bits, &BigUintVar::constant(&n))
+            .unwrap();
+        assert!(result.is_constant());
+        assert_eq!(result.value().unwrap(), base.modpow(&e, &n));
+    }
+}
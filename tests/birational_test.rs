@@ -0,0 +1,124 @@
+use ark_ec::{
+    short_weierstrass::{self, SWCurveConfig},
+    twisted_edwards::{self, TECurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{Field, One, UniformRand, Zero};
+use ark_r1cs_std::{
+    alloc::{AllocVar, AllocationMode::Witness},
+    fields::fp::FpVar,
+    groups::{
+        curves::{
+            short_weierstrass::ProjectiveVar,
+            twisted_edwards::{AffineVar, MontgomeryAffineVar},
+        },
+        CurveVar,
+    },
+    R1CSVar,
+};
+use ark_relations::r1cs::ConstraintSystem;
+
+type Config = ark_bls12_377::g1::Config;
+type Fq = ark_bls12_377::Fq;
+type TEVar = AffineVar<Config, FpVar<Fq>>;
+type SWVar = ProjectiveVar<Config, FpVar<Fq>>;
+type MontVar = MontgomeryAffineVar<Config, FpVar<Fq>>;
+
+#[test]
+fn edwards_weierstrass_test() {
+    let mut rng = ark_std::test_rng();
+    let te_generator = <Config as TECurveConfig>::GENERATOR;
+    let sw_generator = <Config as SWCurveConfig>::GENERATOR;
+    // `(0, -1)` is the point of order two with `y = 0` in the short Weierstrass
+    // form `y^2 = x^3 + 1`.
+    let te_two_torsion = twisted_edwards::Affine::<Config>::new_unchecked(Fq::zero(), -Fq::one());
+    let sw_two_torsion = short_weierstrass::Affine::<Config>::new_unchecked(-Fq::one(), Fq::zero());
+    let scalar = ark_bls12_377::Fr::rand(&mut rng);
+    let cases = [
+        (te_generator, sw_generator),
+        (
+            (te_generator * scalar).into_affine(),
+            (sw_generator * scalar).into_affine(),
+        ),
+        (te_two_torsion, sw_two_torsion),
+        (
+            (te_generator * scalar + te_two_torsion).into_affine(),
+            (sw_generator * scalar + sw_two_torsion).into_affine(),
+        ),
+        (
+            twisted_edwards::Affine::zero(),
+            short_weierstrass::Affine::zero(),
+        ),
+    ];
+    for (te, sw) in cases {
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        // Points of order two are not in the prime-order subgroup.
+        let te_var =
+            TEVar::new_variable_omit_prime_order_check(cs.clone(), || Ok(te.into()), Witness)
+                .unwrap();
+        let sw_var =
+            SWVar::new_variable_omit_prime_order_check(cs.clone(), || Ok(sw.into()), Witness)
+                .unwrap();
+        assert_eq!(
+            te_var.into_short_weierstrass().unwrap().value().unwrap(),
+            sw
+        );
+        assert_eq!(
+            TEVar::from_short_weierstrass(&sw_var)
+                .unwrap()
+                .value()
+                .unwrap(),
+            te
+        );
+        if !te.is_zero() {
+            let mont = MontVar::from_edwards(&te_var).unwrap();
+            assert_eq!(
+                mont.value().unwrap(),
+                MontVar::from_edwards_to_coords(&te).unwrap()
+            );
+            assert_eq!(mont.into_edwards().unwrap().value().unwrap(), te);
+            assert_eq!(mont.into_short_weierstrass().value().unwrap(), sw);
+            let mont = MontVar::from_short_weierstrass(&sw_var).unwrap();
+            assert_eq!(mont.into_edwards().unwrap().value().unwrap(), te);
+        }
+        assert!(cs.is_satisfied().unwrap());
+
+        // Constants are converted to constants.
+        let sw_result = TEVar::constant(te.into()).into_short_weierstrass().unwrap();
+        assert!(sw_result.is_constant());
+        assert_eq!(sw_result.value().unwrap(), sw);
+        let te_result = TEVar::from_short_weierstrass(&SWVar::constant(sw.into())).unwrap();
+        assert!(te_result.is_constant());
+        assert_eq!(te_result.value().unwrap(), te);
+    }
+}
+
+#[test]
+fn montgomery_identity_test() {
+    // `MontgomeryAffineVar` cannot represent the point at infinity.
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let te_var = TEVar::new_witness(cs.clone(), || Ok(twisted_edwards::Affine::zero())).unwrap();
+    let _ = MontVar::from_edwards(&te_var).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let sw_var = SWVar::new_witness(cs.clone(), || Ok(short_weierstrass::Affine::zero())).unwrap();
+    let _ = MontVar::from_short_weierstrass(&sw_var).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn weierstrass_without_edwards_image_test() {
+    // The other points of order two of `y^2 = x^3 + 1` are the points at
+    // infinity of the (incomplete) twisted Edwards form.
+    let omega = (-Fq::from(3u8)).sqrt().unwrap();
+    let x = (Fq::one() + omega) * Fq::from(2u8).inverse().unwrap();
+    let p = short_weierstrass::Affine::<Config>::new_unchecked(x, Fq::zero());
+    assert!(p.is_on_curve());
+
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let sw_var =
+        SWVar::new_variable_omit_prime_order_check(cs.clone(), || Ok(p.into()), Witness).unwrap();
+    let _ = TEVar::from_short_weierstrass(&sw_var).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+}
@@ -0,0 +1,146 @@
+use ark_ec::{pairing::Pairing, CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    bls::{BlsSignatureVar, BlsVariant, MessageHashGadget, MinPk, MinSig},
+    pairing::PairingVar,
+    prelude::*,
+};
+use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef, SynthesisError};
+use ark_std::{marker::PhantomData, UniformRand, Zero};
+
+/// Hashes `msg` to `msg * g`, where `g` is the generator. This is insecure,
+/// but cheap, and demonstrates that message hashes are pluggable.
+struct ScalarMulHasher<V>(PhantomData<V>);
+
+impl<C: CurveGroup, CF: PrimeField, V: CurveVar<C, CF>> MessageHashGadget<C, CF>
+    for ScalarMulHasher<V>
+{
+    type CurveVar = V;
+
+    fn hash(&self, msg: &[UInt8<CF>]) -> Result<V, SynthesisError> {
+        V::constant(C::generator()).scalar_mul_le(msg.to_bits_le()?.iter())
+    }
+}
+
+fn hash_native<C: CurveGroup>(msg: &[u8]) -> C {
+    C::generator() * C::ScalarField::from_le_bytes_mod_order(msg)
+}
+
+fn bls_test<E, P, V, CF>()
+where
+    E: Pairing,
+    P: PairingVar<E, CF>,
+    V: BlsVariant<E, P, CF>,
+    V::PublicKeyGroup: Group<ScalarField = E::ScalarField>,
+    V::SignatureGroup: Group<ScalarField = E::ScalarField>,
+    CF: PrimeField,
+{
+    let mut rng = ark_std::test_rng();
+    let hasher = ScalarMulHasher::<V::SignatureVar>(PhantomData);
+    let msgs = [
+        b"first message".as_ref(),
+        b"second message",
+        b"third message",
+    ];
+    let sks = (0..3)
+        .map(|_| E::ScalarField::rand(&mut rng))
+        .collect::<Vec<_>>();
+    let pks = sks
+        .iter()
+        .map(|sk| V::PublicKeyGroup::generator() * sk)
+        .collect::<Vec<_>>();
+    let sign = |sk: &E::ScalarField, msg: &[u8]| hash_native::<V::SignatureGroup>(msg) * sk;
+
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let pk_vars = pks
+        .iter()
+        .map(|pk| {
+            <V::PublicKeyVar as AllocVar<V::PublicKeyGroup, _>>::new_witness(cs.clone(), || Ok(*pk))
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let msg_vars = msgs
+        .iter()
+        .map(|msg| UInt8::new_witness_vec(cs.clone(), msg).unwrap())
+        .collect::<Vec<_>>();
+    let msg_vars = msg_vars.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    let signature = |cs: ConstraintSystemRef<CF>, sig: V::SignatureGroup| {
+        BlsSignatureVar::<E, P, V, CF>::new_witness(cs, || Ok(sig)).unwrap()
+    };
+
+    // A single signature.
+    let sig = signature(cs.clone(), sign(&sks[0], msgs[0]));
+    let valid = sig.verify(&pk_vars[0], msg_vars[0], &hasher).unwrap();
+    let wrong_msg = sig.verify(&pk_vars[0], msg_vars[1], &hasher).unwrap();
+    let wrong_pk = sig.verify(&pk_vars[1], msg_vars[0], &hasher).unwrap();
+    assert!(valid.value().unwrap());
+    assert!(!wrong_msg.value().unwrap());
+    assert!(!wrong_pk.value().unwrap());
+
+    // An aggregate signature of a single message.
+    let aggregate = sks.iter().map(|sk| sign(sk, msgs[0])).sum();
+    let sig = signature(cs.clone(), aggregate);
+    let valid = sig
+        .verify_aggregate_same_message(&pk_vars, msg_vars[0], &hasher)
+        .unwrap();
+    let missing_pk = sig
+        .verify_aggregate_same_message(&pk_vars[1..], msg_vars[0], &hasher)
+        .unwrap();
+    assert!(valid.value().unwrap());
+    assert!(!missing_pk.value().unwrap());
+
+    // An aggregate signature of distinct messages.
+    let aggregate = sks.iter().zip(msgs).map(|(sk, msg)| sign(sk, msg)).sum();
+    let sig = signature(cs.clone(), aggregate);
+    let valid = sig.verify_aggregate(&pk_vars, &msg_vars, &hasher).unwrap();
+    let swapped = sig
+        .verify_aggregate(&pk_vars, &[msg_vars[1], msg_vars[0], msg_vars[2]], &hasher)
+        .unwrap();
+    assert!(valid.value().unwrap());
+    assert!(!swapped.value().unwrap());
+
+    // The identity is not a valid public key, even though its pairing with any
+    // message hash equals that of the generator with the identity.
+    let zero_pk =
+        <V::PublicKeyVar as AllocVar<V::PublicKeyGroup, _>>::new_witness(cs.clone(), || {
+            Ok(V::PublicKeyGroup::zero())
+        })
+        .unwrap();
+    let sig = signature(cs.clone(), V::SignatureGroup::zero());
+    let invalid = sig.verify(&zero_pk, msg_vars[0], &hasher).unwrap();
+    assert!(!invalid.value().unwrap());
+
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn bls12_381_min_pk_test() {
+    type E = ark_bls12_381::Bls12_381;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_381::Config>;
+
+    bls_test::<E, P, MinPk, ark_bls12_381::Fq>();
+}
+
+#[test]
+fn bls12_381_min_sig_test() {
+    type E = ark_bls12_381::Bls12_381;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_381::Config>;
+
+    bls_test::<E, P, MinSig, ark_bls12_381::Fq>();
+}
+
+#[test]
+fn bls12_377_min_pk_test() {
+    type E = ark_bls12_377::Bls12_377;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_377::Config>;
+
+    bls_test::<E, P, MinPk, ark_bls12_377::Fq>();
+}
+
+#[test]
+fn bls12_377_min_sig_test() {
+    type E = ark_bls12_377::Bls12_377;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_377::Config>;
+
+    bls_test::<E, P, MinSig, ark_bls12_377::Fq>();
+}
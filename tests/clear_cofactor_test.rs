@@ -0,0 +1,125 @@
+use ark_ec::{
+    short_weierstrass::{self, SWCurveConfig},
+    twisted_edwards::{self, TECurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{Field, UniformRand};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    fields::{fp::FpVar, fp2::Fp2Var},
+    groups::{
+        bls12::{self, G1Var, G2Var},
+        curves::{short_weierstrass::ProjectiveVar, twisted_edwards::AffineVar},
+        CurveVar,
+    },
+};
+use ark_relations::r1cs::ConstraintSystem;
+
+type SWG1Var = ProjectiveVar<ark_bls12_377::g1::Config, FpVar<ark_bls12_377::Fq>>;
+type SWG2Var = ProjectiveVar<ark_bls12_381::g2::Config, Fp2Var<ark_bls12_381::Fq2Config>>;
+type TEVar = AffineVar<ark_ed_on_bls12_377::EdwardsConfig, FpVar<ark_ed_on_bls12_377::Fq>>;
+
+/// Samples a point on the curve that is not in the prime-order subgroup.
+fn sw_point<P: SWCurveConfig>() -> short_weierstrass::Affine<P> {
+    let mut rng = ark_std::test_rng();
+    loop {
+        let x = P::BaseField::rand(&mut rng);
+        if let Some(p) = short_weierstrass::Affine::<P>::get_point_from_x_unchecked(x, false) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                return p;
+            }
+        }
+    }
+}
+
+/// Samples a point on the curve that is not in the prime-order subgroup.
+fn te_point<P: TECurveConfig>() -> twisted_edwards::Affine<P> {
+    let mut rng = ark_std::test_rng();
+    loop {
+        let y = P::BaseField::rand(&mut rng);
+        if let Some(p) = twisted_edwards::Affine::<P>::get_point_from_y_unchecked(y, false) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                return p;
+            }
+        }
+    }
+}
+
+/// Checks that `clear_cofactor` agrees with `expected`, for both a witness and
+/// a constant.
+fn check<C, GG>(point: C::Affine, expected: C::Affine, clear_cofactor: impl Fn(&GG) -> GG)
+where
+    C: CurveGroup,
+    GG: CurveVar<C, <C::BaseField as Field>::BasePrimeField>,
+{
+    let cs = ConstraintSystem::new_ref();
+    let point_var = GG::new_witness(cs.clone(), || Ok(point)).unwrap();
+    assert_eq!(
+        clear_cofactor(&point_var).value().unwrap().into_affine(),
+        expected
+    );
+    assert!(cs.is_satisfied().unwrap());
+
+    let point_var = GG::constant(point.into());
+    assert_eq!(
+        clear_cofactor(&point_var).value().unwrap().into_affine(),
+        expected
+    );
+}
+
+#[test]
+fn clear_cofactor_test() {
+    let p = sw_point::<ark_bls12_377::g1::Config>();
+    check::<_, SWG1Var>(p, p.mul_by_cofactor(), |p| p.clear_cofactor().unwrap());
+
+    let p = sw_point::<ark_bls12_381::g2::Config>();
+    check::<_, SWG2Var>(p, p.mul_by_cofactor(), |p| p.clear_cofactor().unwrap());
+
+    let p = te_point::<ark_ed_on_bls12_377::EdwardsConfig>();
+    check::<_, TEVar>(p, p.mul_by_cofactor(), |p| p.clear_cofactor().unwrap());
+}
+
+#[test]
+fn bls12_clear_cofactor_test() {
+    // `G1Var` and `G2Var` use the effective cofactors, as do the native
+    // implementations.
+    let p = sw_point::<ark_bls12_377::g1::Config>();
+    check::<_, G1Var<ark_bls12_377::Config>>(p, p.clear_cofactor(), |p| {
+        p.clear_cofactor().unwrap()
+    });
+    check(p, p.clear_cofactor(), |p: &SWG1Var| {
+        bls12::clear_g1_cofactor::<ark_bls12_377::Config, _, _, _>(p).unwrap()
+    });
+    let p = sw_point::<ark_bls12_377::g2::Config>();
+    check::<_, G2Var<ark_bls12_377::Config>>(p, p.clear_cofactor(), |p| {
+        p.clear_cofactor().unwrap()
+    });
+    let p = sw_point::<ark_bls12_381::g1::Config>();
+    check::<_, G1Var<ark_bls12_381::Config>>(p, p.clear_cofactor(), |p| {
+        p.clear_cofactor().unwrap()
+    });
+    let p = sw_point::<ark_bls12_381::g2::Config>();
+    let cleared = p.clear_cofactor();
+    assert!(cleared.is_in_correct_subgroup_assuming_on_curve());
+    check::<_, G2Var<ark_bls12_381::Config>>(p, cleared, |p| p.clear_cofactor().unwrap());
+    check(p, cleared, |p: &SWG2Var| {
+        bls12::clear_g2_cofactor::<ark_bls12_381::Config, _, _, _>(p).unwrap()
+    });
+
+    // The effective cofactor of G2 is much cheaper than the cofactor.
+    let cs = ConstraintSystem::new_ref();
+    let num_constraints = cs.num_constraints();
+    G2Var::<ark_bls12_381::Config>::new_witness(cs.clone(), || Ok(p))
+        .unwrap()
+        .clear_cofactor()
+        .unwrap();
+    let effective_cost = cs.num_constraints() - num_constraints;
+    let num_constraints = cs.num_constraints();
+    SWG2Var::new_witness(cs.clone(), || Ok(p))
+        .unwrap()
+        .clear_cofactor()
+        .unwrap();
+    let cofactor_cost = cs.num_constraints() - num_constraints;
+    assert!(2 * effective_cost < cofactor_cost);
+    assert!(cs.is_satisfied().unwrap());
+}
@@ -0,0 +1,255 @@
+use ark_ec::{short_weierstrass, twisted_edwards, AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    fields::{fp::FpVar, fp2::Fp2Var, FieldVar},
+    groups::{
+        curves::{short_weierstrass::ProjectiveVar, twisted_edwards::AffineVar},
+        PointCompressionGadget,
+    },
+    uint8::UInt8,
+    R1CSVar,
+};
+use ark_relations::r1cs::ConstraintSystem;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{vec::Vec, UniformRand, Zero};
+
+type G1Var = ProjectiveVar<ark_bls12_377::g1::Config, FpVar<ark_bls12_377::Fq>>;
+type G2Var = ProjectiveVar<ark_bls12_377::g2::Config, Fp2Var<ark_bls12_377::Fq2Config>>;
+type Secp256k1Var = ProjectiveVar<ark_secp256k1::Config, FpVar<ark_secp256k1::Fq>>;
+type TEVar = AffineVar<ark_ed_on_bls12_377::EdwardsConfig, FpVar<ark_ed_on_bls12_377::Fq>>;
+
+fn serialize<C: CurveGroup>(point: &C) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    point
+        .into_affine()
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    bytes
+}
+
+/// Returns whether decompressing `bytes` inside a circuit is satisfiable.
+fn decompresses<C, GG>(bytes: &[u8]) -> bool
+where
+    C: CurveGroup,
+    C::BaseField: Field,
+    GG: PointCompressionGadget<C, <C::BaseField as Field>::BasePrimeField>,
+{
+    let cs = ConstraintSystem::new_ref();
+    let bytes = UInt8::new_witness_vec(cs.clone(), bytes).unwrap();
+    GG::from_compressed_bytes(&bytes).unwrap();
+    cs.is_satisfied().unwrap()
+}
+
+fn check_roundtrip<C, GG>(point: C)
+where
+    C: CurveGroup,
+    GG: PointCompressionGadget<C, <C::BaseField as Field>::BasePrimeField>,
+{
+    let expected = serialize(&point);
+
+    // Witness point and witness bytes.
+    let cs = ConstraintSystem::new_ref();
+    let point_var = GG::new_witness(cs.clone(), || Ok(point)).unwrap();
+    let bytes = point_var.to_compressed_bytes().unwrap();
+    assert_eq!(bytes.value().unwrap(), expected);
+    let bytes_var = UInt8::new_witness_vec(cs.clone(), &expected).unwrap();
+    let decompressed = GG::from_compressed_bytes(&bytes_var).unwrap();
+    assert_eq!(decompressed.value().unwrap(), point);
+    decompressed.enforce_equal(&point_var).unwrap();
+    assert!(cs.is_satisfied().unwrap());
+
+    // Constant point and constant bytes.
+    let point_var = GG::constant(point);
+    let bytes = point_var.to_compressed_bytes().unwrap();
+    assert!(bytes.cs().is_none());
+    assert_eq!(bytes.value().unwrap(), expected);
+    let decompressed = GG::from_compressed_bytes(&UInt8::constant_vec(&expected)).unwrap();
+    assert!(decompressed.cs().is_none());
+    assert_eq!(decompressed.value().unwrap(), point);
+}
+
+fn check_sw<P, F>()
+where
+    P: short_weierstrass::SWCurveConfig,
+    F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+    for<'a> &'a F: ark_r1cs_std::fields::FieldOpsBounds<'a, P::BaseField, F>,
+{
+    let mut rng = ark_std::test_rng();
+    for _ in 0..3 {
+        let point = short_weierstrass::Projective::<P>::rand(&mut rng);
+        check_roundtrip::<_, ProjectiveVar<P, F>>(point);
+        check_roundtrip::<_, ProjectiveVar<P, F>>(-point);
+    }
+    check_roundtrip::<_, ProjectiveVar<P, F>>(short_weierstrass::Projective::<P>::zero());
+
+    let point = short_weierstrass::Projective::<P>::rand(&mut rng);
+    let bytes = serialize(&point);
+    let last = bytes.len() - 1;
+    assert!(decompresses::<_, ProjectiveVar<P, F>>(&bytes));
+
+    // Both the infinity and the sign flag are set.
+    let mut infinity = serialize(&short_weierstrass::Projective::<P>::zero());
+    infinity[last] |= 1 << 7;
+    assert!(!decompresses::<_, ProjectiveVar<P, F>>(&infinity));
+
+    // The infinity flag is set, but `x` is nonzero.
+    let mut invalid = bytes.clone();
+    invalid[last] &= !(1 << 7);
+    invalid[last] |= 1 << 6;
+    assert!(!decompresses::<_, ProjectiveVar<P, F>>(&invalid));
+
+    // `x` is not the x-coordinate of a point on the curve.
+    let mut x = P::BaseField::ONE;
+    while short_weierstrass::Affine::<P>::get_point_from_x_unchecked(x, true).is_some() {
+        x += P::BaseField::ONE;
+    }
+    let mut invalid = Vec::new();
+    x.serialize_compressed(&mut invalid).unwrap();
+    invalid.resize(bytes.len(), 0);
+    assert!(!decompresses::<_, ProjectiveVar<P, F>>(&invalid));
+
+    // `x` is on the curve, but not in the prime-order subgroup.
+    if !P::cofactor_is_one() {
+        let mut x = P::BaseField::ONE;
+        let point = loop {
+            if let Some(point) = short_weierstrass::Affine::<P>::get_point_from_x_unchecked(x, true)
+            {
+                if !point.is_in_correct_subgroup_assuming_on_curve() {
+                    break point;
+                }
+            }
+            x += P::BaseField::ONE;
+        };
+        let mut invalid = Vec::new();
+        point.serialize_compressed(&mut invalid).unwrap();
+        assert!(!decompresses::<_, ProjectiveVar<P, F>>(&invalid));
+    }
+}
+
+#[test]
+fn short_weierstrass_compression_test() {
+    check_sw::<ark_bls12_377::g1::Config, FpVar<ark_bls12_377::Fq>>();
+    check_sw::<ark_bls12_377::g2::Config, Fp2Var<ark_bls12_377::Fq2Config>>();
+    check_sw::<ark_secp256k1::Config, FpVar<ark_secp256k1::Fq>>();
+}
+
+#[test]
+fn bls12_381_compression_test() {
+    // BLS12-381 uses the big-endian layout of Zcash, with the flags in the
+    // first byte.
+    fn check<P, F>()
+    where
+        P: short_weierstrass::SWCurveConfig,
+        F: FieldVar<P::BaseField, <P::BaseField as Field>::BasePrimeField>,
+        for<'a> &'a F: ark_r1cs_std::fields::FieldOpsBounds<'a, P::BaseField, F>,
+    {
+        let mut rng = ark_std::test_rng();
+        for _ in 0..3 {
+            let point = short_weierstrass::Projective::<P>::rand(&mut rng);
+            check_roundtrip::<_, ProjectiveVar<P, F>>(point);
+            check_roundtrip::<_, ProjectiveVar<P, F>>(-point);
+        }
+        let zero = short_weierstrass::Projective::<P>::zero();
+        check_roundtrip::<_, ProjectiveVar<P, F>>(zero);
+        assert_eq!(serialize(&zero)[0], 0b1100_0000);
+
+        // The compression flag is not set.
+        let mut bytes = serialize(&short_weierstrass::Projective::<P>::rand(&mut rng));
+        assert!(decompresses::<_, ProjectiveVar<P, F>>(&bytes));
+        bytes[0] &= !(1 << 7);
+        assert!(!decompresses::<_, ProjectiveVar<P, F>>(&bytes));
+    }
+    check::<ark_bls12_381::g1::Config, FpVar<ark_bls12_381::Fq>>();
+    check::<ark_bls12_381::g2::Config, Fp2Var<ark_bls12_381::Fq2Config>>();
+}
+
+#[test]
+fn non_canonical_compression_test() {
+    // `x + p` fits in the bits of BLS12-377's base field for some `x`, but must
+    // be rejected because it is not canonical.
+    let mut rng = ark_std::test_rng();
+    let (point, x) = loop {
+        let point = ark_bls12_377::G1Projective::rand(&mut rng).into_affine();
+        let mut x = point.x.into_bigint();
+        x.add_with_carry(&ark_bls12_377::Fq::MODULUS);
+        if x.num_bits() <= ark_bls12_377::Fq::MODULUS_BIT_SIZE {
+            break (point, x);
+        }
+    };
+    let mut bytes = serialize(&point.into_group());
+    let num_bytes = bytes.len();
+    let flags = bytes[num_bytes - 1] & 0b1100_0000;
+    bytes.copy_from_slice(&x.to_bytes_le()[..num_bytes]);
+    bytes[num_bytes - 1] |= flags;
+    assert!(!decompresses::<_, G1Var>(&bytes));
+
+    assert!(decompresses::<_, G1Var>(&serialize(&point.into_group())));
+    assert!(decompresses::<_, G2Var>(&serialize(
+        &ark_bls12_377::G2Projective::rand(&mut rng)
+    )));
+    assert!(decompresses::<_, Secp256k1Var>(&serialize(
+        &ark_secp256k1::Projective::rand(&mut rng)
+    )));
+}
+
+#[test]
+fn twisted_edwards_compression_test() {
+    let mut rng = ark_std::test_rng();
+    for _ in 0..3 {
+        let point = ark_ed_on_bls12_377::EdwardsProjective::rand(&mut rng);
+        check_roundtrip::<_, TEVar>(point);
+        check_roundtrip::<_, TEVar>(-point);
+    }
+    check_roundtrip::<_, TEVar>(ark_ed_on_bls12_377::EdwardsProjective::zero());
+
+    // `(0, -1)` is on the curve, but has order 2.
+    let point =
+        twisted_edwards::Affine::<ark_ed_on_bls12_377::EdwardsConfig>::get_point_from_y_unchecked(
+            -ark_ed_on_bls12_377::Fq::ONE,
+            false,
+        )
+        .unwrap();
+    assert!(!point.is_in_correct_subgroup_assuming_on_curve());
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    assert!(!decompresses::<_, TEVar>(&bytes));
+
+    // `y` is not the y-coordinate of a point on the curve.
+    let mut y = ark_ed_on_bls12_377::Fq::ONE;
+    while twisted_edwards::Affine::<ark_ed_on_bls12_377::EdwardsConfig>::get_point_from_y_unchecked(
+        y, true,
+    )
+    .is_some()
+    {
+        y += ark_ed_on_bls12_377::Fq::ONE;
+    }
+    let mut bytes = Vec::new();
+    y.serialize_compressed(&mut bytes).unwrap();
+    assert!(!decompresses::<_, TEVar>(&bytes));
+}
+
+#[test]
+fn sqrt_with_sign_test() {
+    let mut rng = ark_std::test_rng();
+    for _ in 0..10 {
+        let root = ark_bls12_377::Fq2::rand(&mut rng);
+        let square = root.square();
+        for root in [root, -root] {
+            let is_positive = root <= -root;
+            let cs = ConstraintSystem::new_ref();
+            let square_var =
+                Fp2Var::<ark_bls12_377::Fq2Config>::new_witness(cs.clone(), || Ok(square)).unwrap();
+            let root_var = Fp2Var::new_witness(cs.clone(), || Ok(root)).unwrap();
+            assert_eq!(
+                root_var.is_positive().unwrap().value().unwrap(),
+                is_positive
+            );
+            let sign = ark_r1cs_std::boolean::Boolean::new_witness(cs.clone(), || Ok(is_positive))
+                .unwrap();
+            let computed = square_var.sqrt_with_sign(&sign).unwrap();
+            assert_eq!(computed.value().unwrap(), root);
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+}
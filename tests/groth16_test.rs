@@ -0,0 +1,123 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::{BitIteratorLE, PrimeField};
+use ark_groth16::{Groth16, PreparedVerifyingKey};
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    groth16::{Groth16VerifierGadget, PreparedVerifyingKeyVar, ProofVar, VerifyingKeyVar},
+    pairing::PairingVar,
+    prelude::*,
+};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, SynthesisError,
+};
+use ark_std::UniformRand;
+
+/// Proves knowledge of `a` and `b` such that `a * b = c`, for a public `c`.
+struct MulCircuit<F: PrimeField> {
+    a: Option<F>,
+    b: Option<F>,
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for MulCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let a = FpVar::new_witness(cs.clone(), || {
+            self.a.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let b = FpVar::new_witness(cs.clone(), || {
+            self.b.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let c = FpVar::new_input(cs, || Ok(a.value()? * b.value()?))?;
+        (a * b).enforce_equal(&c)
+    }
+}
+
+fn input_bits<E: Pairing, CF: PrimeField>(
+    cs: ConstraintSystemRef<CF>,
+    input: E::ScalarField,
+) -> Vec<Boolean<CF>> {
+    let bits = BitIteratorLE::new(input.into_bigint())
+        .take(E::ScalarField::MODULUS_BIT_SIZE as usize)
+        .collect::<Vec<_>>();
+    Vec::new_input(cs, || Ok(bits)).unwrap()
+}
+
+fn groth16_test<E: Pairing, P: PairingVar<E, CF>, CF: PrimeField>() {
+    let mut rng = ark_std::test_rng();
+    let circuit = MulCircuit::<E::ScalarField> { a: None, b: None };
+    let pk = Groth16::<E>::generate_random_parameters_with_reduction(circuit, &mut rng).unwrap();
+    let pvk = PreparedVerifyingKey::from(pk.vk.clone());
+
+    let a = E::ScalarField::rand(&mut rng);
+    let b = E::ScalarField::rand(&mut rng);
+    let circuit = MulCircuit {
+        a: Some(a),
+        b: Some(b),
+    };
+    let proof = Groth16::<E>::create_random_proof_with_reduction(circuit, &pk, &mut rng).unwrap();
+    assert!(Groth16::<E>::verify_proof(&pvk, &proof, &[a * b]).unwrap());
+
+    // A witness verifying key.
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let vk = &pk.vk;
+    let vk = (
+        vk.alpha_g1,
+        vk.beta_g2,
+        vk.gamma_g2,
+        vk.delta_g2,
+        vk.gamma_abc_g1.clone(),
+    );
+    let vk_var = VerifyingKeyVar::<E, P, CF>::new_witness(cs.clone(), || Ok(vk)).unwrap();
+    let proof_var =
+        ProofVar::<E, P, CF>::new_witness(cs.clone(), || Ok((proof.a, proof.b, proof.c))).unwrap();
+    let input = input_bits::<E, CF>(cs.clone(), a * b);
+    let valid = Groth16VerifierGadget::verify(&vk_var, &[input], &proof_var).unwrap();
+    let wrong_input = input_bits::<E, CF>(cs.clone(), a + b);
+    let invalid = Groth16VerifierGadget::verify(&vk_var, &[wrong_input], &proof_var).unwrap();
+    assert!(valid.value().unwrap());
+    assert!(!invalid.value().unwrap());
+    assert!(cs.is_satisfied().unwrap());
+
+    // A constant prepared verifying key.
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let pvk = (
+        pvk.alpha_g1_beta_g2,
+        pvk.gamma_g2_neg_pc.clone(),
+        pvk.delta_g2_neg_pc.clone(),
+        pvk.vk.gamma_abc_g1.clone(),
+    );
+    let pvk_var = PreparedVerifyingKeyVar::<E, P, CF>::new_constant(cs.clone(), pvk).unwrap();
+    let proof_var =
+        ProofVar::<E, P, CF>::new_witness(cs.clone(), || Ok((proof.a, proof.b, proof.c))).unwrap();
+    let input = input_bits::<E, CF>(cs.clone(), a * b);
+    let valid =
+        Groth16VerifierGadget::verify_with_prepared_vk(&pvk_var, &[input], &proof_var).unwrap();
+    valid.enforce_equal(&Boolean::TRUE).unwrap();
+    assert!(cs.is_satisfied().unwrap());
+
+    let result = Groth16VerifierGadget::verify_with_prepared_vk(&pvk_var, &[], &proof_var);
+    assert!(matches!(result, Err(SynthesisError::MalformedVerifyingKey)));
+}
+
+#[test]
+fn mnt4_298_groth16_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config>;
+
+    groth16_test::<E, P, ark_mnt4_298::Fq>();
+}
+
+#[test]
+fn mnt6_298_groth16_test() {
+    type E = ark_mnt6_298::MNT6_298;
+    type P = ark_r1cs_std::pairing::mnt6::PairingVar<ark_mnt6_298::Config>;
+
+    groth16_test::<E, P, ark_mnt6_298::Fq>();
+}
+
+#[test]
+fn bls12_377_groth16_test() {
+    type E = ark_bls12_377::Bls12_377;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_377::Config>;
+
+    groth16_test::<E, P, ark_bls12_377::Fq>();
+}
@@ -0,0 +1,612 @@
+use ark_bls12_381::{Fq, Fq2, Fq2Config};
+use ark_ec::{
+    hashing::{
+        curve_maps::{
+            swu::SWUMap,
+            wb::{WBConfig, WBMap},
+        },
+        map_to_curve_hasher::MapToCurve,
+    },
+    short_weierstrass, twisted_edwards, AffineRepr, CurveGroup, Group,
+};
+use ark_ff::{BigInteger, Field, One, PrimeField, UniformRand, Zero};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    bls::{BlsSignatureVar, HashToCurveGadget, MinPk},
+    boolean::Boolean,
+    fields::{
+        field_hashers::{expand_message_xmd, hash_to_field, DigestGadget},
+        fp::FpVar,
+        fp2::Fp2Var,
+        FieldVar,
+    },
+    groups::{
+        bls12::{G1MapToCurveGadget, G1Var, G2MapToCurveGadget},
+        hashing::{
+            hash_to_curve, Elligator2MapGadget, MapToCurveGadget, SWUMapGadget, WBMapGadget,
+        },
+    },
+    uint32::UInt32,
+    uint8::UInt8,
+    R1CSVar, ToBitsGadget,
+};
+use ark_relations::r1cs::{ConstraintSystem, SynthesisError};
+use ark_std::vec::Vec;
+use num_bigint::BigUint;
+
+/// SHA-256, implemented with `UInt32`s.
+struct Sha256Gadget;
+
+const SHA256_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SHA256_ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+fn shr<F: PrimeField>(x: &UInt32<F>, by: usize) -> UInt32<F> {
+    let mut bits = x.to_bits_le()[by..].to_vec();
+    bits.resize(32, Boolean::FALSE);
+    UInt32::from_bits_le(&bits)
+}
+
+fn bitwise<F: PrimeField>(
+    a: &UInt32<F>,
+    b: &UInt32<F>,
+    c: &UInt32<F>,
+    f: impl Fn(&Boolean<F>, &Boolean<F>, &Boolean<F>) -> Result<Boolean<F>, SynthesisError>,
+) -> Result<UInt32<F>, SynthesisError> {
+    let bits = a
+        .to_bits_le()
+        .iter()
+        .zip(b.to_bits_le())
+        .zip(c.to_bits_le())
+        .map(|((a, b), c)| f(a, &b, &c))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(UInt32::from_bits_le(&bits))
+}
+
+impl<F: PrimeField> DigestGadget<F> for Sha256Gadget {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+
+    fn digest(input: &[UInt8<F>]) -> Result<Vec<UInt8<F>>, SynthesisError> {
+        let mut msg = input.to_vec();
+        msg.push(UInt8::constant(0x80));
+        while msg.len() % 64 != 56 {
+            msg.push(UInt8::constant(0));
+        }
+        msg.extend(UInt8::constant_vec(&(8 * input.len() as u64).to_be_bytes()));
+
+        let mut state = SHA256_IV.map(UInt32::constant).to_vec();
+        for block in msg.chunks(64) {
+            let mut w = block
+                .chunks(4)
+                .map(|word| {
+                    let mut bits = Vec::new();
+                    for byte in word.iter().rev() {
+                        bits.extend(byte.to_bits_le()?);
+                    }
+                    Ok(UInt32::from_bits_le(&bits))
+                })
+                .collect::<Result<Vec<_>, SynthesisError>>()?;
+            for t in 16..64 {
+                let s0 = w[t - 15]
+                    .rotr(7)
+                    .xor(&w[t - 15].rotr(18))?
+                    .xor(&shr(&w[t - 15], 3))?;
+                let s1 = w[t - 2]
+                    .rotr(17)
+                    .xor(&w[t - 2].rotr(19))?
+                    .xor(&shr(&w[t - 2], 10))?;
+                w.push(UInt32::addmany(&[
+                    w[t - 16].clone(),
+                    s0,
+                    w[t - 7].clone(),
+                    s1,
+                ])?);
+            }
+
+            let mut v = state.clone();
+            for t in 0..64 {
+                let s1 = v[4].rotr(6).xor(&v[4].rotr(11))?.xor(&v[4].rotr(25))?;
+                let ch = bitwise(&v[4], &v[5], &v[6], |e, f, g| e.select(f, g))?;
+                let temp1 = UInt32::addmany(&[
+                    v[7].clone(),
+                    s1,
+                    ch,
+                    UInt32::constant(SHA256_ROUND_CONSTANTS[t]),
+                    w[t].clone(),
+                ])?;
+                let s0 = v[0].rotr(2).xor(&v[0].rotr(13))?.xor(&v[0].rotr(22))?;
+                let maj = bitwise(&v[0], &v[1], &v[2], |a, b, c| {
+                    a.and(b)?.xor(&c.and(&a.xor(b)?)?)
+                })?;
+                let temp2 = UInt32::addmany(&[s0, maj])?;
+                v.rotate_right(1);
+                v[4] = UInt32::addmany(&[v[4].clone(), temp1.clone()])?;
+                v[0] = UInt32::addmany(&[temp1, temp2])?;
+            }
+            for (s, v) in state.iter_mut().zip(v) {
+                *s = UInt32::addmany(&[s.clone(), v])?;
+            }
+        }
+
+        let mut output = Vec::with_capacity(32);
+        for word in state {
+            let bits = word.to_bits_le();
+            output.extend(bits.chunks(8).rev().map(UInt8::from_bits_le));
+        }
+        Ok(output)
+    }
+}
+
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn fq(hex: &str) -> Fq {
+    Fq::from(BigUint::parse_bytes(hex.trim_start_matches("0x").as_bytes(), 16).unwrap())
+}
+
+fn fq2(hex: &str) -> Fq2 {
+    let (c0, c1) = hex.split_once(',').unwrap();
+    Fq2::new(fq(c0), fq(c1))
+}
+
+#[test]
+fn sha256_test() {
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let msg = UInt8::new_witness_vec(cs.clone(), b"abc").unwrap();
+    let digest = Sha256Gadget::digest(&msg).unwrap();
+    assert_eq!(
+        digest.value().unwrap(),
+        hex_to_bytes("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn expand_message_xmd_test() {
+    // Test vectors from RFC 9380, Appendix K.1.
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let long_dst = [
+        b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-".as_ref(),
+        &[b'1'; 208],
+    ]
+    .concat();
+    let vectors: [(&[u8], &[u8], usize, &str); 5] = [
+        (
+            dst,
+            b"",
+            0x20,
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+        ),
+        (
+            dst,
+            b"abc",
+            0x20,
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+        ),
+        (
+            dst,
+            b"abcdef0123456789",
+            0x20,
+            "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+        ),
+        (
+            dst,
+            b"abc",
+            0x80,
+            "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a\
+             647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635\
+             bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00\
+             058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40",
+        ),
+        (
+            &long_dst,
+            b"abc",
+            0x20,
+            "52dbf4f36cf560fca57dedec2ad924ee9c266341d8f3d6afe5171733b16bbb12",
+        ),
+    ];
+    for (dst, msg, len_in_bytes, expected) in vectors {
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let msg = UInt8::new_witness_vec(cs.clone(), msg).unwrap();
+        let output = expand_message_xmd::<Sha256Gadget, _>(&msg, dst, len_in_bytes).unwrap();
+        assert_eq!(output.value().unwrap(), hex_to_bytes(expected));
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn bls12_381_g1_hash_to_curve_test() {
+    // Test vectors from RFC 9380, Appendix J.9.1.
+    let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+    let vectors: [(&[u8], [&str; 2], [[&str; 2]; 3]); 2] = [
+        (
+            b"",
+            [
+                "0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb3ae5438a614fb61d6835ad59f29c564f",
+                "019b9bd7979f12657976de2884c7cce192b82c177c80e0ec604436a7f538d231552f0d96d9f7babe5fa3b19b3ff25ac9",
+            ],
+            [
+                [
+                    "11a3cce7e1d90975990066b2f2643b9540fa40d6137780df4e753a8054d07580db3b7f1f03396333d4a359d1fe3766fe",
+                    "0eeaf6d794e479e270da10fdaf768db4c96b650a74518fc67b04b03927754bac66f3ac720404f339ecdcc028afa091b7",
+                ],
+                [
+                    "160003aaf1632b13396dbad518effa00fff532f604de1a7fc2082ff4cb0afa2d63b2c32da1bef2bf6c5ca62dc6b72f9c",
+                    "0d8bb2d14e20cf9f6036152ed386d79189415b6d015a20133acb4e019139b94e9c146aaad5817f866c95d609a361735e",
+                ],
+                [
+                    "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+                    "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+                ],
+            ],
+        ),
+        (
+            b"abc",
+            [
+                "0d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83e9afa5c088573c2281410369fbd32951",
+                "003574a00b109ada2f26a37a91f9d1e740dffd8d69ec0c35e1e9f4652c7dba61123e9dd2e76c655d956e2b3462611139",
+            ],
+            [
+                [
+                    "125435adce8e1cbd1c803e7123f45392dc6e326d292499c2c45c5865985fd74fe8f042ecdeeec5ecac80680d04317d80",
+                    "0e8828948c989126595ee30e4f7c931cbd6f4570735624fd25aef2fa41d3f79cfb4b4ee7b7e55a8ce013af2a5ba20bf2",
+                ],
+                [
+                    "11def93719829ecda3b46aa8c31fc3ac9c34b428982b898369608e4f042babee6c77ab9218aad5c87ba785481eff8ae4",
+                    "0007c9cef122ccf2efd233d6eb9bfc680aa276652b0661f4f820a653cec1db7ff69899f8e52b8e92b025a12c822a6ce6",
+                ],
+                [
+                    "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903",
+                    "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+                ],
+            ],
+        ),
+    ];
+    for (msg, u, [q0, q1, p]) in vectors {
+        let point = |[x, y]: [&str; 2]| {
+            short_weierstrass::Affine::<ark_bls12_381::g1::Config>::new_unchecked(fq(x), fq(y))
+        };
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let msg = UInt8::new_witness_vec(cs.clone(), msg).unwrap();
+        let u_var = hash_to_field::<Fq, Fq, FpVar<Fq>, Sha256Gadget>(&msg, dst, 2).unwrap();
+        assert_eq!(u_var.value().unwrap(), vec![fq(u[0]), fq(u[1])]);
+        for (u, q) in u_var.iter().zip([q0, q1]) {
+            let q_var = G1MapToCurveGadget::<ark_bls12_381::Config>::map_to_curve(u).unwrap();
+            assert_eq!(q_var.value().unwrap(), point(q));
+        }
+        let p_var = hash_to_curve::<_, _, G1MapToCurveGadget<ark_bls12_381::Config>, Sha256Gadget>(
+            &msg, dst,
+        )
+        .unwrap();
+        assert_eq!(p_var.value().unwrap(), point(p));
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn bls12_381_g2_hash_to_curve_test() {
+    // Test vectors from RFC 9380, Appendix J.10.1.
+    let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+    let u = [
+        "03dbc2cce174e91ba93cbb08f26b917f98194a2ea08d1cce75b2b9cc9f21689d80bd79b594a613d0a68eb807dfdc1cf8,\
+         05a2acec64114845711a54199ea339abd125ba38253b70a92c876df10598bd1986b739cad67961eb94f7076511b3b39a",
+        "02f99798e8a5acdeed60d7e18e9120521ba1f47ec090984662846bc825de191b5b7641148c0dbc237726a334473eee94,\
+         145a81e418d4010cc027a68f14391b30074e89e60ee7a22f87217b2f6eb0c4b94c9115b436e6fa4607e95a98de30a435",
+    ];
+    let q = [
+        [
+            "019ad3fc9c72425a998d7ab1ea0e646a1f6093444fc6965f1cad5a3195a7b1e099c050d57f45e3fa191cc6d75ed7458c,\
+             171c88b0b0efb5eb2b88913a9e74fe111a4f68867b59db252ce5868af4d1254bfab77ebde5d61cd1a86fb2fe4a5a1c1d",
+            "0ba10604e62bdd9eeeb4156652066167b72c8d743b050fb4c1016c31b505129374f76e03fa127d6a156213576910fef3,\
+             0eb22c7a543d3d376e9716a49b72e79a89c9bfe9feee8533ed931cbb5373dde1fbcd7411d8052e02693654f71e15410a",
+        ],
+        [
+            "113d2b9cd4bd98aee53470b27abc658d91b47a78a51584f3d4b950677cfb8a3e99c24222c406128c91296ef6b45608be,\
+             13855912321c5cb793e9d1e88f6f8d342d49c0b0dbac613ee9e17e3c0b3c97dfbb5a49cc3fb45102fdbaf65e0efe2632",
+            "0fd3def0b7574a1d801be44fde617162aa2e89da47f464317d9bb5abc3a7071763ce74180883ad7ad9a723a9afafcdca,\
+             056f617902b3c0d0f78a9a8cbda43a26b65f602f8786540b9469b060db7b38417915b413ca65f875c130bebfaa59790c",
+        ],
+    ];
+    let p = [
+        "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a,\
+         05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+        "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92,\
+         12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+    ];
+    let point = |[x, y]: [&str; 2]| {
+        short_weierstrass::Affine::<ark_bls12_381::g2::Config>::new_unchecked(fq2(x), fq2(y))
+    };
+
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let msg = UInt8::new_witness_vec(cs.clone(), b"").unwrap();
+    let u_var = hash_to_field::<Fq2, Fq, Fp2Var<Fq2Config>, Sha256Gadget>(&msg, dst, 2).unwrap();
+    assert_eq!(u_var.value().unwrap(), vec![fq2(u[0]), fq2(u[1])]);
+    for (u, q) in u_var.iter().zip(q) {
+        let q_var = G2MapToCurveGadget::<ark_bls12_381::Config>::map_to_curve(u).unwrap();
+        assert_eq!(q_var.value().unwrap(), point(q));
+    }
+    let p_var =
+        hash_to_curve::<_, _, G2MapToCurveGadget<ark_bls12_381::Config>, Sha256Gadget>(&msg, dst)
+            .unwrap();
+    assert_eq!(p_var.value().unwrap(), point(p));
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn bls12_381_min_pk_hash_to_curve_test() {
+    type E = ark_bls12_381::Bls12_381;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_381::Config>;
+
+    // The hash of the empty message, from RFC 9380, Appendix J.10.1.
+    let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+    let hash = short_weierstrass::Affine::<ark_bls12_381::g2::Config>::new_unchecked(
+        fq2("0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a,\
+             05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d"),
+        fq2("0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92,\
+             12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6"),
+    );
+    let mut rng = ark_std::test_rng();
+    let sk = ark_bls12_381::Fr::rand(&mut rng);
+    let pk = ark_bls12_381::G1Projective::generator() * sk;
+    let hasher =
+        HashToCurveGadget::<G2MapToCurveGadget<ark_bls12_381::Config>, Sha256Gadget>::new(dst);
+
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let pk_var = G1Var::<ark_bls12_381::Config>::new_witness(cs.clone(), || Ok(pk)).unwrap();
+    let sig_var =
+        BlsSignatureVar::<E, P, MinPk>::new_witness(cs.clone(), || Ok(hash * sk)).unwrap();
+    for (msg, expected) in [(b"".as_ref(), true), (b"abc", false)] {
+        let msg = UInt8::new_witness_vec(cs.clone(), msg).unwrap();
+        let valid = sig_var.verify(&pk_var, &msg, &hasher).unwrap();
+        assert_eq!(valid.value().unwrap(), expected);
+    }
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn swu_map_matches_native_test() {
+    type IsoConfig = <ark_bls12_381::g1::Config as WBConfig>::IsogenousCurve;
+    type G2IsoConfig = <ark_bls12_381::g2::Config as WBConfig>::IsogenousCurve;
+    let mut rng = ark_std::test_rng();
+
+    for i in 0..10 {
+        let u = if i == 0 {
+            Fq::zero()
+        } else {
+            Fq::rand(&mut rng)
+        };
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let u_var = FpVar::new_witness(cs.clone(), || Ok(u)).unwrap();
+        let swu = SWUMapGadget::<IsoConfig, FpVar<Fq>>::map_to_curve(&u_var).unwrap();
+        let native = SWUMap::<IsoConfig>::new().unwrap().map_to_curve(u).unwrap();
+        assert_eq!(swu.value().unwrap(), native);
+        let wb = WBMapGadget::<ark_bls12_381::g1::Config, FpVar<Fq>>::map_to_curve(&u_var).unwrap();
+        let native = WBMap::<ark_bls12_381::g1::Config>::new()
+            .unwrap()
+            .map_to_curve(u)
+            .unwrap();
+        assert_eq!(wb.value().unwrap().into_affine(), native);
+        assert!(cs.is_satisfied().unwrap());
+
+        // The map also works with constants.
+        let swu = SWUMapGadget::<IsoConfig, FpVar<Fq>>::map_to_curve(&FpVar::constant(u)).unwrap();
+        assert!(swu.cs().is_none());
+        assert_eq!(
+            swu.value().unwrap(),
+            SWUMap::<IsoConfig>::new().unwrap().map_to_curve(u).unwrap()
+        );
+
+        let u = Fq2::rand(&mut rng);
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let u_var = Fp2Var::new_witness(cs.clone(), || Ok(u)).unwrap();
+        let swu = SWUMapGadget::<G2IsoConfig, Fp2Var<Fq2Config>>::map_to_curve(&u_var).unwrap();
+        let native = SWUMap::<G2IsoConfig>::new()
+            .unwrap()
+            .map_to_curve(u)
+            .unwrap();
+        assert_eq!(swu.value().unwrap(), native);
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+/// A native implementation of the Elligator 2 map of RFC 9380, Section 6.7.1,
+/// to the Montgomery curve `K * t^2 = s^3 + J * s^2 + s`, followed by the
+/// rational map to `P`. If the Montgomery `B` coefficient of `P` is a square,
+/// then `K = 1` and the map is scaled by `c = sqrt(B)`, as in Section 6.8.2
+/// for edwards25519. Otherwise, `K = B` and `c = 1`.
+fn elligator2_native<P>(u: P::BaseField) -> twisted_edwards::Affine<P>
+where
+    P: twisted_edwards::TECurveConfig,
+    P::BaseField: PrimeField,
+{
+    use ark_ec::twisted_edwards::MontCurveConfig;
+    type F<P> = <P as ark_ec::CurveConfig>::BaseField;
+    let sgn0 = |f: F<P>| f.into_bigint().is_odd();
+    let j = P::MontCurveConfig::COEFF_A;
+    let b = P::MontCurveConfig::COEFF_B;
+    let (k, c) = match b.sqrt() {
+        Some(c) => (F::<P>::one(), if sgn0(c) { -c } else { c }),
+        None => (b, F::<P>::one()),
+    };
+    let mut z = F::<P>::one();
+    while z.legendre().is_qr() && (-z).legendre().is_qr() {
+        z += F::<P>::one();
+    }
+    let z = if z.legendre().is_qnr() { z } else { -z };
+
+    let g = |x: F<P>| x * x * x + j / k * x * x + x / (k * k);
+    let mut x1 = -(j / k)
+        * (F::<P>::one() + z * u * u)
+            .inverse()
+            .unwrap_or(F::<P>::zero());
+    if x1.is_zero() {
+        x1 = -(j / k);
+    }
+    let x2 = -x1 - j / k;
+    let (x, y) = match g(x1).sqrt() {
+        Some(y) => (x1, if sgn0(y) { y } else { -y }),
+        None => {
+            let y = g(x2).sqrt().unwrap();
+            (x2, if sgn0(y) { -y } else { y })
+        },
+    };
+    let (s, t) = (x * k, y * k);
+    if (t * (s + F::<P>::one())).is_zero() {
+        return twisted_edwards::Affine::zero();
+    }
+    twisted_edwards::Affine::new_unchecked(c * s / t, (s - F::<P>::one()) / (s + F::<P>::one()))
+}
+
+/// Checks `Elligator2MapGadget` against `elligator2_native` on random inputs.
+fn check_elligator2<P>()
+where
+    P: twisted_edwards::TECurveConfig,
+    P::BaseField: PrimeField,
+{
+    let mut rng = ark_std::test_rng();
+    for i in 0..20 {
+        let u = if i == 0 {
+            P::BaseField::zero()
+        } else {
+            P::BaseField::rand(&mut rng)
+        };
+        let expected = elligator2_native::<P>(u);
+        assert!(expected.is_on_curve());
+
+        let cs = ConstraintSystem::new_ref();
+        let u_var = FpVar::new_witness(cs.clone(), || Ok(u)).unwrap();
+        let point = Elligator2MapGadget::<P, FpVar<P::BaseField>>::map_to_curve(&u_var).unwrap();
+        assert_eq!(point.x.value().unwrap(), expected.x);
+        assert_eq!(point.y.value().unwrap(), expected.y);
+
+        let cleared =
+            Elligator2MapGadget::<P, FpVar<P::BaseField>>::clear_cofactor(&point).unwrap();
+        assert_eq!(
+            cleared.value().unwrap().into_affine(),
+            expected.mul_by_cofactor()
+        );
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn elligator2_test() {
+    use ark_ec::twisted_edwards::MontCurveConfig;
+
+    // The Montgomery `B` coefficient of `ed_on_bls12_377` is not a square, so
+    // `K = B`.
+    type P = ark_ed_on_bls12_377::EdwardsConfig;
+    assert!(
+        <P as twisted_edwards::TECurveConfig>::MontCurveConfig::COEFF_B
+            .sqrt()
+            .is_none()
+    );
+    check_elligator2::<P>();
+}
+
+#[test]
+fn elligator2_square_coeff_b_test() {
+    use ark_ec::twisted_edwards::MontCurveConfig;
+
+    // The Montgomery `B` coefficient of Jubjub is a square, so `K = 1`.
+    type P = ark_test_curves::ed_on_bls12_381::EdwardsConfig;
+    assert!(
+        <P as twisted_edwards::TECurveConfig>::MontCurveConfig::COEFF_B
+            .sqrt()
+            .is_some()
+    );
+    check_elligator2::<P>();
+}
+
+#[test]
+fn elligator2_edwards25519_test() {
+    // Test vectors from RFC 9380, Appendix J.5.2. Each `u` is mapped directly,
+    // so no SHA-512 gadget is needed.
+    type P = ark_ed25519::EdwardsConfig;
+    let vectors: [(&str, [&str; 2], [&str; 2]); 5] = [
+        (
+            "7f3e7fb9428103ad7f52db32f9df32505d7b427d894c5093f7a0f0374a30641d",
+            [
+                "42836f691d05211ebc65ef8fcf01e0fb6328ec9c4737c26050471e50803022eb",
+                "22cb4aaa555e23bd460262d2130d6a3c9207aa8bbb85060928beb263d6d42a95",
+            ],
+            [
+                "1ff2b70ecf862799e11b7ae744e3489aa058ce805dd323a936375a84695e76da",
+                "222e314d04a4d5725e9f2aff9fb2a6b69ef375a1214eb19021ceab2d687f0f9b",
+            ],
+        ),
+        (
+            "09cfa30ad79bd59456594a0f5d3a76f6b71c6787b04de98be5cd201a556e253b",
+            [
+                "333e41b61c6dd43af220c1ac34a3663e1cf537f996bab50ab66e33c4bd8e4e19",
+                "51b6f178eb08c4a782c820e306b82c6e273ab22e258d972cd0c511787b2a3443",
+            ],
+            [
+                "5f13cc69c891d86927eb37bd4afc6672360007c63f68a33ab423a3aa040fd2a8",
+                "67732d50f9a26f73111dd1ed5dba225614e538599db58ba30aaea1f5c827fa42",
+            ],
+        ),
+        (
+            "475ccff99225ef90d78cc9338e9f6a6bb7b17607c0c4428937de75d33edba941",
+            [
+                "55186c242c78e7d0ec5b6c9553f04c6aeef64e69ec2e824472394da32647cfc6",
+                "5b9ea3c265ee42256a8f724f616307ef38496ef7eba391c08f99f3bea6fa88f0",
+            ],
+            [
+                "1dd2fefce934ecfd7aae6ec998de088d7dd03316aa1847198aecf699ba6613f1",
+                "2f8a6c24dd1adde73909cada6a4a137577b0f179d336685c4a955a0a8e1a86fb",
+            ],
+        ),
+        (
+            "049a1c8bd51bcb2aec339f387d1ff51428b88d0763a91bcdf6929814ac95d03d",
+            [
+                "024b6e1621606dca8071aa97b43dce4040ca78284f2a527dcf5d0fbfac2b07e7",
+                "5102353883d739bdc9f8a3af650342b171217167dcce34f8db57208ec1dfdbf2",
+            ],
+            [
+                "35fbdc5143e8a97afd3096f2b843e07df72e15bfca2eaf6879bf97c5d3362f73",
+                "2af6ff6ef5ebba128b0774f4296cb4c2279a074658b083b8dcca91f57a603450",
+            ],
+        ),
+        (
+            "3cb0178a8137cefa5b79a3a57c858d7eeeaa787b2781be4a362a2f0750d24fa0",
+            [
+                "3e6368cff6e88a58e250c54bd27d2c989ae9b3acb6067f2651ad282ab8c21cd9",
+                "38fb39f1566ca118ae6c7af42810c0bb9767ae5960abb5a8ca792530bfb9447d",
+            ],
+            [
+                "6e5e1f37e99345887fc12111575fc1c3e36df4b289b8759d23af14d774b66bff",
+                "2c90c3d39eb18ff291d33441b35f3262cdd307162cc97c31bfcc7a4245891a37",
+            ],
+        ),
+    ];
+    let fe = |hex: &str| ark_ed25519::Fq::from(BigUint::parse_bytes(hex.as_bytes(), 16).unwrap());
+    let point = |[x, y]: [&str; 2]| twisted_edwards::Affine::<P>::new_unchecked(fe(x), fe(y));
+    for (u, q, p) in vectors {
+        let cs = ConstraintSystem::new_ref();
+        let u_var = FpVar::new_witness(cs.clone(), || Ok(fe(u))).unwrap();
+        let q_var = Elligator2MapGadget::<P, FpVar<ark_ed25519::Fq>>::map_to_curve(&u_var).unwrap();
+        assert_eq!(q_var.value().unwrap(), point(q));
+        let p_var =
+            Elligator2MapGadget::<P, FpVar<ark_ed25519::Fq>>::clear_cofactor(&q_var).unwrap();
+        assert_eq!(p_var.value().unwrap(), point(p));
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
@@ -0,0 +1,179 @@
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_ff::{Field, PrimeField};
+use ark_r1cs_std::{
+    fields::nonnative::NonNativeFieldVar,
+    kzg::{KZGVerifierGadget, VerifierKey, VerifierKeyVar},
+    pairing::PairingVar,
+    prelude::*,
+};
+use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef};
+use ark_std::UniformRand;
+
+/// Evaluates the polynomial with coefficients `coeffs` at `x`.
+fn evaluate<F: Field>(coeffs: &[F], x: F) -> F {
+    coeffs.iter().rev().fold(F::zero(), |acc, c| acc * x + c)
+}
+
+/// A native KZG opening.
+struct Opening<E: Pairing> {
+    commitment: E::G1,
+    value: E::ScalarField,
+    proof: E::G1,
+}
+
+/// Opens the polynomial with coefficients `coeffs` at `point`.
+fn open<E: Pairing>(
+    vk: &VerifierKey<E>,
+    tau: E::ScalarField,
+    coeffs: &[E::ScalarField],
+    point: E::ScalarField,
+) -> Opening<E> {
+    let value = evaluate(coeffs, point);
+    let p_tau = evaluate(coeffs, tau);
+    // The quotient `(p(X) - p(z)) / (X - z)` evaluated at `τ`.
+    let q_tau = (p_tau - value) / (tau - point);
+    Opening {
+        commitment: vk.g * p_tau,
+        value,
+        proof: vk.g * q_tau,
+    }
+}
+
+fn kzg_test<E: Pairing, P: PairingVar<E, CF>, CF: PrimeField>(mode: AllocationMode) {
+    let mut rng = ark_std::test_rng();
+    let tau = E::ScalarField::rand(&mut rng);
+    let h = E::G2::rand(&mut rng);
+    let vk = VerifierKey::<E> {
+        g: E::G1::rand(&mut rng).into_affine(),
+        h: h.into_affine(),
+        beta_h: (h * tau).into_affine(),
+    };
+    let polynomials = (0..3)
+        .map(|_| {
+            (0..5)
+                .map(|_| E::ScalarField::rand(&mut rng))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let points = (0..3)
+        .map(|_| E::ScalarField::rand(&mut rng))
+        .collect::<Vec<_>>();
+    let openings = polynomials
+        .iter()
+        .zip(&points)
+        .map(|(p, z)| open(&vk, tau, p, *z))
+        .collect::<Vec<_>>();
+
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let vk_var = VerifierKeyVar::<E, P, CF>::new_variable(cs.clone(), || Ok(&vk), mode).unwrap();
+    let scalar = |cs: ConstraintSystemRef<CF>, s: E::ScalarField| {
+        NonNativeFieldVar::<E::ScalarField, CF>::new_witness(cs, || Ok(s)).unwrap()
+    };
+    let g1 = |cs: ConstraintSystemRef<CF>, g: E::G1| P::G1Var::new_witness(cs, || Ok(g)).unwrap();
+    let commitments = openings
+        .iter()
+        .map(|o| g1(cs.clone(), o.commitment))
+        .collect::<Vec<_>>();
+    let values = openings
+        .iter()
+        .map(|o| scalar(cs.clone(), o.value))
+        .collect::<Vec<_>>();
+    let proofs = openings
+        .iter()
+        .map(|o| g1(cs.clone(), o.proof))
+        .collect::<Vec<_>>();
+    let points = points
+        .into_iter()
+        .map(|z| scalar(cs.clone(), z))
+        .collect::<Vec<_>>();
+    let challenge = scalar(cs.clone(), E::ScalarField::rand(&mut rng));
+
+    // Single openings.
+    let valid =
+        KZGVerifierGadget::verify(&vk_var, &commitments[0], &points[0], &values[0], &proofs[0])
+            .unwrap();
+    let invalid =
+        KZGVerifierGadget::verify(&vk_var, &commitments[0], &points[0], &values[1], &proofs[0])
+            .unwrap();
+    assert!(valid.value().unwrap());
+    assert!(!invalid.value().unwrap());
+
+    // Openings at different points.
+    let valid = KZGVerifierGadget::batch_verify(
+        &vk_var,
+        &commitments,
+        &points,
+        &values,
+        &proofs,
+        &challenge,
+    )
+    .unwrap();
+    let invalid = KZGVerifierGadget::batch_verify(
+        &vk_var,
+        &commitments,
+        &points,
+        &[values[0].clone(), values[2].clone(), values[1].clone()],
+        &proofs,
+        &challenge,
+    )
+    .unwrap();
+    assert!(valid.value().unwrap());
+    assert!(!invalid.value().unwrap());
+
+    // Openings of several polynomials at a single point, with a single proof
+    // for the linear combination of the polynomials.
+    let r = challenge.value().unwrap();
+    let point = E::ScalarField::rand(&mut rng);
+    let openings = polynomials
+        .iter()
+        .map(|p| open(&vk, tau, p, point))
+        .collect::<Vec<_>>();
+    let proof = openings
+        .iter()
+        .enumerate()
+        .map(|(i, o)| o.proof * r.pow([i as u64]))
+        .sum::<E::G1>();
+    let values = openings
+        .iter()
+        .map(|o| scalar(cs.clone(), o.value))
+        .collect::<Vec<_>>();
+    let proof = g1(cs.clone(), proof);
+    let point = scalar(cs.clone(), point);
+    let valid = KZGVerifierGadget::verify_combined(
+        &vk_var,
+        &commitments,
+        &point,
+        &values,
+        &proof,
+        &challenge,
+    )
+    .unwrap();
+    let invalid = KZGVerifierGadget::verify_combined(
+        &vk_var,
+        &commitments,
+        &point,
+        &values,
+        &proofs[0],
+        &challenge,
+    )
+    .unwrap();
+    assert!(valid.value().unwrap());
+    assert!(!invalid.value().unwrap());
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn bls12_377_kzg_test() {
+    type E = ark_bls12_377::Bls12_377;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_377::Config>;
+
+    kzg_test::<E, P, ark_bls12_377::Fq>(AllocationMode::Constant);
+}
+
+#[test]
+fn mnt4_298_kzg_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config>;
+
+    kzg_test::<E, P, ark_mnt4_298::Fq>(AllocationMode::Witness);
+}
@@ -0,0 +1,809 @@
+use ark_ec::{
+    mnt4::{MNT4Config, MNT4},
+    pairing::Pairing,
+    CurveGroup,
+};
+use ark_ff::{BigInt, BigInteger, Field, Fp2, PrimeField};
+use ark_r1cs_std::{
+    alloc::{AllocVar, AllocationMode::Witness},
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{
+        fp::FpVar,
+        fp12::Fp12VarWithBase,
+        fp2::{Fp2Var, Fp2VarWithBase},
+        fp3::Fp3Var,
+        fp4::{Fp4Var, Fp4VarWithBase},
+        fp6_2over3::{Fp6Var, Fp6VarWithBase},
+        nonnative::NonNativeFieldVar,
+        FieldVar,
+    },
+    groups::CurveVar,
+    pairing::PairingVar,
+    R1CSVar,
+};
+use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef};
+use ark_std::UniformRand;
+
+fn pairing_test<E: Pairing, P: PairingVar<E, CF>, CF: ark_ff::PrimeField>(
+    cs: ConstraintSystemRef<CF>,
+) {
+    let mut rng = ark_std::test_rng();
+    let a = E::G1::rand(&mut rng);
+    let b = E::G2::rand(&mut rng);
+
+    let a_var = P::G1Var::new_witness(cs.clone(), || Ok(a)).unwrap();
+    let b_var = P::G2Var::new_witness(cs.clone(), || Ok(b)).unwrap();
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+    let b_prep = P::prepare_g2(&b_var).unwrap();
+
+    let result = P::pairing(a_prep, b_prep).unwrap();
+
+    assert_eq!(result.value().unwrap(), E::pairing(a, b).0);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn mnt4_298_pairing_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config>;
+
+    let cs = ConstraintSystem::<ark_mnt4_298::Fq>::new_ref();
+    pairing_test::<E, P, _>(cs);
+}
+
+#[test]
+fn mnt6_298_pairing_test() {
+    type E = ark_mnt6_298::MNT6_298;
+    type P = ark_r1cs_std::pairing::mnt6::PairingVar<ark_mnt6_298::Config>;
+
+    let cs = ConstraintSystem::<ark_mnt6_298::Fq>::new_ref();
+    pairing_test::<E, P, _>(cs);
+}
+
+#[test]
+fn bn254_pairing_test() {
+    type E = ark_bn254::Bn254;
+    type P = ark_r1cs_std::pairing::bn::PairingVar<ark_bn254::Config>;
+
+    let cs = ConstraintSystem::<ark_bn254::Fq>::new_ref();
+    pairing_test::<E, P, _>(cs);
+}
+
+#[test]
+fn bw6_761_pairing_test() {
+    type E = ark_bw6_761::BW6_761;
+    type P = ark_r1cs_std::pairing::bw6::PairingVar<ark_bw6_761::Config>;
+
+    let cs = ConstraintSystem::<ark_bw6_761::Fq>::new_ref();
+    pairing_test::<E, P, _>(cs);
+}
+
+// A full pairing over an emulated base field needs far more memory than CI
+// provides. These tests are ignored by default; run them on a large machine
+// with `cargo test --test pairing_test -- --ignored emulated_pairing`.
+// `mnt4_298_emulated_witness_pairing_test` below runs the same code on
+// witness points in CI, with a shortened Miller loop.
+
+#[test]
+#[ignore]
+fn bls12_381_emulated_pairing_test() {
+    type E = ark_bls12_381::Bls12_381;
+    type F = NonNativeFieldVar<ark_bls12_381::Fq, ark_bn254::Fr>;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_381::Config, F, ark_bn254::Fr>;
+
+    let cs = ConstraintSystem::<ark_bn254::Fr>::new_ref();
+    pairing_test::<E, P, _>(cs);
+}
+
+#[test]
+#[ignore]
+fn bn254_emulated_pairing_test() {
+    type E = ark_bn254::Bn254;
+    type F = NonNativeFieldVar<ark_bn254::Fq, ark_bls12_381::Fr>;
+    type P = ark_r1cs_std::pairing::bn::PairingVar<ark_bn254::Config, F, ark_bls12_381::Fr>;
+
+    let cs = ConstraintSystem::<ark_bls12_381::Fr>::new_ref();
+    pairing_test::<E, P, _>(cs);
+}
+
+#[test]
+#[ignore]
+fn mnt4_298_emulated_pairing_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type F = NonNativeFieldVar<ark_mnt4_298::Fq, ark_bls12_381::Fr>;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config, F, ark_bls12_381::Fr>;
+
+    let cs = ConstraintSystem::<ark_bls12_381::Fr>::new_ref();
+    pairing_test::<E, P, _>(cs);
+}
+
+/// The MNT4-298 configuration, but with a Miller loop of a few steps and a
+/// short exponent in the hard part of the final exponentiation. This keeps the
+/// result well-defined, if not bilinear, while making a pairing on witness
+/// points over an emulated field small enough for CI.
+struct ShortMNT4_298;
+
+impl MNT4Config for ShortMNT4_298 {
+    const TWIST: Fp2<Self::Fp2Config> = ark_mnt4_298::Config::TWIST;
+    const TWIST_COEFF_A: Fp2<Self::Fp2Config> = ark_mnt4_298::Config::TWIST_COEFF_A;
+    // This covers doubling steps followed by addition steps for both signs,
+    // and by none, as well as the final addition step for a negative count.
+    const ATE_LOOP_COUNT: &'static [i8] = &[1, 0, -1, 1];
+    const ATE_IS_LOOP_COUNT_NEG: bool = true;
+    const FINAL_EXPONENT_LAST_CHUNK_1: BigInt<5> = BigInt::new([1, 0, 0, 0, 0]);
+    const FINAL_EXPONENT_LAST_CHUNK_W0_IS_NEG: bool = true;
+    const FINAL_EXPONENT_LAST_CHUNK_ABS_OF_W0: BigInt<5> = BigInt::new([0b1011, 0, 0, 0, 0]);
+    type Fp = ark_mnt4_298::Fq;
+    type Fr = ark_mnt4_298::Fr;
+    type Fp2Config = ark_mnt4_298::Fq2Config;
+    type Fp4Config = ark_mnt4_298::Fq4Config;
+    type G1Config = ark_mnt4_298::g1::Config;
+    type G2Config = ark_mnt4_298::g2::Config;
+}
+
+#[test]
+fn mnt4_298_emulated_witness_pairing_test() {
+    type E = MNT4<ShortMNT4_298>;
+    type CF = ark_mnt4_753::Fr;
+    type F = NonNativeFieldVar<ark_mnt4_298::Fq, CF>;
+    type G1 = ark_r1cs_std::groups::mnt4::G1Var<ShortMNT4_298, F, CF>;
+    type G2 = ark_r1cs_std::groups::mnt4::G2Var<ShortMNT4_298, F, CF>;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ShortMNT4_298, F, CF>;
+
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let a = ark_mnt4_298::G1Projective::rand(&mut rng);
+    let b = ark_mnt4_298::G2Projective::rand(&mut rng);
+    let a_var = G1::new_variable_omit_prime_order_check(cs.clone(), || Ok(a), Witness).unwrap();
+    let b_var = G2::new_variable_omit_prime_order_check(cs.clone(), || Ok(b), Witness).unwrap();
+
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+    let b_prep = P::prepare_g2(&b_var).unwrap();
+    let result = P::miller_loop(&[a_prep], &[b_prep]).unwrap();
+    let expected = E::multi_miller_loop([a], [b]);
+    assert_eq!(result.value().unwrap(), expected.0);
+
+    let result = P::final_exponentiation(&result).unwrap();
+    assert_eq!(
+        result.value().unwrap(),
+        E::final_exponentiation(expected).unwrap().0
+    );
+    assert!(cs.is_satisfied().unwrap());
+}
+
+// The tests below check the building blocks of emulated pairings against
+// native values instead: preparing a `G1` point, and one step of the Miller
+// loop, i.e., squaring the accumulator and multiplying it by a line
+// evaluated at the prepared point. The `G1` points are allocated without the
+// prime-order check, whose emulated scalar multiplication is as costly as
+// the rest of the test combined.
+
+#[test]
+fn bls12_381_emulated_miller_loop_step_test() {
+    type CF = ark_bn254::Fr;
+    type F = NonNativeFieldVar<ark_bls12_381::Fq, CF>;
+    type G1 = ark_r1cs_std::groups::bls12::G1Var<ark_bls12_381::Config, F, CF>;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_381::Config, F, CF>;
+    type Fq2Var = Fp2VarWithBase<ark_bls12_381::Fq2Config, F, CF>;
+    type Fq12Var = Fp12VarWithBase<ark_bls12_381::Fq12Config, F, CF>;
+
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let a = ark_bls12_381::G1Projective::rand(&mut rng);
+    let a_var = G1::new_variable_omit_prime_order_check(cs.clone(), || Ok(a), Witness).unwrap();
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+    let a = a.into_affine();
+    assert_eq!(a_prep.value().unwrap().0, a);
+
+    let f = ark_bls12_381::Fq12::rand(&mut rng);
+    let [c0, c1] = [(); 2].map(|_| ark_bls12_381::Fq2::rand(&mut rng));
+    let f_var = Fq12Var::new_witness(cs.clone(), || Ok(f)).unwrap();
+    let [c0_var, c1_var] = [c0, c1].map(|c| Fq2Var::new_witness(cs.clone(), || Ok(c)).unwrap());
+
+    // BLS12-381 has an M-type twist.
+    let p = &a_prep.0;
+    let c1_var = Fq2Var::new(&c1_var.c0 * &p.x, &c1_var.c1 * &p.x);
+    let c2_var = Fq2Var::new(p.y.clone(), F::zero());
+    let result = f_var
+        .square()
+        .unwrap()
+        .mul_by_014(&c0_var, &c1_var, &c2_var)
+        .unwrap();
+
+    let mut expected = f.square();
+    expected.mul_by_014(
+        &c0,
+        &ark_bls12_381::Fq2::new(c1.c0 * a.x, c1.c1 * a.x),
+        &ark_bls12_381::Fq2::new(a.y, ark_bls12_381::Fq::from(0u8)),
+    );
+    assert_eq!(result.value().unwrap(), expected);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn bn254_emulated_miller_loop_step_test() {
+    type CF = ark_bls12_381::Fr;
+    type F = NonNativeFieldVar<ark_bn254::Fq, CF>;
+    type G1 = ark_r1cs_std::groups::bn::G1Var<ark_bn254::Config, F, CF>;
+    type P = ark_r1cs_std::pairing::bn::PairingVar<ark_bn254::Config, F, CF>;
+    type Fq2Var = Fp2VarWithBase<ark_bn254::Fq2Config, F, CF>;
+    type Fq12Var = Fp12VarWithBase<ark_bn254::Fq12Config, F, CF>;
+
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let a = ark_bn254::G1Projective::rand(&mut rng);
+    let a_var = G1::new_variable_omit_prime_order_check(cs.clone(), || Ok(a), Witness).unwrap();
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+    let a = a.into_affine();
+    assert_eq!(a_prep.value().unwrap().0, a);
+
+    let f = ark_bn254::Fq12::rand(&mut rng);
+    let [c0, c1] = [(); 2].map(|_| ark_bn254::Fq2::rand(&mut rng));
+    let f_var = Fq12Var::new_witness(cs.clone(), || Ok(f)).unwrap();
+    let [c0_var, c1_var] = [c0, c1].map(|c| Fq2Var::new_witness(cs.clone(), || Ok(c)).unwrap());
+
+    // BN254 has a D-type twist.
+    let p = &a_prep.0;
+    let y_var = Fq2Var::new(p.y.clone(), F::zero());
+    let c0_var = Fq2Var::new(&c0_var.c0 * &p.x, &c0_var.c1 * &p.x);
+    let result = f_var
+        .square()
+        .unwrap()
+        .mul_by_034(&y_var, &c0_var, &c1_var)
+        .unwrap();
+
+    let mut expected = f.square();
+    expected.mul_by_034(
+        &ark_bn254::Fq2::new(a.y, ark_bn254::Fq::from(0u8)),
+        &ark_bn254::Fq2::new(c0.c0 * a.x, c0.c1 * a.x),
+        &c1,
+    );
+    assert_eq!(result.value().unwrap(), expected);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn bw6_761_emulated_miller_loop_step_test() {
+    type CF = ark_bls12_381::Fr;
+    type F = NonNativeFieldVar<ark_bw6_761::Fq, CF>;
+    type G1 = ark_r1cs_std::groups::bw6::G1Var<ark_bw6_761::Config, F, CF>;
+    type P = ark_r1cs_std::pairing::bw6::PairingVar<ark_bw6_761::Config, F, CF>;
+    type Fq6Var = Fp6VarWithBase<ark_bw6_761::Fq6Config, F, CF>;
+
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let a = ark_bw6_761::G1Projective::rand(&mut rng);
+    let a_var = G1::new_variable_omit_prime_order_check(cs.clone(), || Ok(a), Witness).unwrap();
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+    let a = a.into_affine();
+    assert_eq!(a_prep.value().unwrap().0, a);
+
+    let f = ark_bw6_761::Fq6::rand(&mut rng);
+    let [c0, c1] = [(); 2].map(|_| ark_bw6_761::Fq::rand(&mut rng));
+    let f_var = Fq6Var::new_witness(cs.clone(), || Ok(f)).unwrap();
+    let [c0_var, c1_var] = [c0, c1].map(|c| F::new_witness(cs.clone(), || Ok(c)).unwrap());
+
+    // BW6-761 has an M-type twist.
+    let p = &a_prep.0;
+    let result = f_var
+        .square()
+        .unwrap()
+        .mul_by_014(&c0_var, &(&c1_var * &p.x), &p.y)
+        .unwrap();
+
+    let mut expected = f.square();
+    expected.mul_by_014(&c0, &(c1 * a.x), &a.y);
+    assert_eq!(result.value().unwrap(), expected);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn mnt4_298_emulated_miller_loop_step_test() {
+    type CF = ark_bls12_381::Fr;
+    type F = NonNativeFieldVar<ark_mnt4_298::Fq, CF>;
+    type G1 = ark_r1cs_std::groups::mnt4::G1Var<ark_mnt4_298::Config, F, CF>;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config, F, CF>;
+    type Fq2Var = Fp2VarWithBase<ark_mnt4_298::Fq2Config, F, CF>;
+    type Fq4Var = Fp4VarWithBase<ark_mnt4_298::Fq4Config, F, CF>;
+
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let a = ark_mnt4_298::G1Projective::rand(&mut rng);
+    let a_var = G1::new_variable_omit_prime_order_check(cs.clone(), || Ok(a), Witness).unwrap();
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+    let expected = ark_ec::mnt4::G1Prepared::<ark_mnt4_298::Config>::from(a);
+    assert_eq!(a_prep.x.value().unwrap(), expected.x);
+    assert_eq!(a_prep.y.value().unwrap(), expected.y);
+    assert_eq!(a_prep.x_twist.value().unwrap(), expected.x_twist);
+    assert_eq!(a_prep.y_twist.value().unwrap(), expected.y_twist);
+
+    let f = ark_mnt4_298::Fq4::rand(&mut rng);
+    let [c0, c1] = [(); 2].map(|_| ark_mnt4_298::Fq2::rand(&mut rng));
+    let f_var = Fq4Var::new_witness(cs.clone(), || Ok(f)).unwrap();
+    let [c0_var, c1_var] = [c0, c1].map(|c| Fq2Var::new_witness(cs.clone(), || Ok(c)).unwrap());
+
+    let result = f_var
+        .square()
+        .unwrap()
+        .mul_by_c0_yc1(&c0_var, &c1_var, &a_prep.y)
+        .unwrap();
+
+    let y = expected.y;
+    let expected =
+        f.square() * ark_mnt4_298::Fq4::new(c0, ark_mnt4_298::Fq2::new(c1.c0 * y, c1.c1 * y));
+    assert_eq!(result.value().unwrap(), expected);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+/// Checks the Miller loop of `P` on two pairs, and its final exponentiation,
+/// against the native ones. The inputs are constants, so that the test runs
+/// the generic code of the emulated pairing without the millions of
+/// constraints that witness inputs need.
+///
+/// The Miller loops of the BLS12, BN and BW6 gadgets differ from the native ones
+/// by a factor that the final exponentiation cancels, so they are only
+/// compared directly if `exact_miller_loop` is set.
+fn emulated_constant_pairing_test<E, P, CF>(
+    constant: impl Fn(E::G2Prepared) -> P::G2PreparedVar,
+    exact_miller_loop: bool,
+) where
+    E: Pairing,
+    P: PairingVar<E, CF>,
+    CF: PrimeField,
+{
+    let mut rng = ark_std::test_rng();
+    let a = [(); 2].map(|_| E::G1::rand(&mut rng));
+    let b = [(); 2].map(|_| E::G2::rand(&mut rng));
+
+    let a_prep = a.map(|a| P::prepare_g1(&P::G1Var::constant(a)).unwrap());
+    let b_prep = b.map(|b| constant(E::G2Prepared::from(b)));
+    let result = P::miller_loop(&a_prep, &b_prep).unwrap();
+    let expected = E::multi_miller_loop(a, b);
+    if exact_miller_loop {
+        assert_eq!(result.value().unwrap(), expected.0);
+    }
+
+    let result = P::final_exponentiation(&result).unwrap();
+    assert_eq!(
+        result.value().unwrap(),
+        E::final_exponentiation(expected).unwrap().0
+    );
+}
+
+#[test]
+fn bls12_381_emulated_constant_pairing_test() {
+    type E = ark_bls12_381::Bls12_381;
+    type CF = ark_bn254::Fr;
+    type F = NonNativeFieldVar<ark_bls12_381::Fq, CF>;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_381::Config, F, CF>;
+
+    emulated_constant_pairing_test::<E, P, CF>(
+        ark_r1cs_std::groups::bls12::G2PreparedVar::constant,
+        false,
+    );
+}
+
+#[test]
+fn bn254_emulated_constant_pairing_test() {
+    type E = ark_bn254::Bn254;
+    type CF = ark_bls12_381::Fr;
+    type F = NonNativeFieldVar<ark_bn254::Fq, CF>;
+    type P = ark_r1cs_std::pairing::bn::PairingVar<ark_bn254::Config, F, CF>;
+
+    emulated_constant_pairing_test::<E, P, CF>(
+        |q| AllocVar::new_constant(ConstraintSystemRef::None, q).unwrap(),
+        false,
+    );
+}
+
+#[test]
+fn bw6_761_emulated_constant_pairing_test() {
+    type E = ark_bw6_761::BW6_761;
+    type CF = ark_bls12_381::Fr;
+    type F = NonNativeFieldVar<ark_bw6_761::Fq, CF>;
+    type P = ark_r1cs_std::pairing::bw6::PairingVar<ark_bw6_761::Config, F, CF>;
+
+    emulated_constant_pairing_test::<E, P, CF>(
+        |q| AllocVar::new_constant(ConstraintSystemRef::None, q).unwrap(),
+        false,
+    );
+}
+
+#[test]
+fn mnt4_298_emulated_constant_pairing_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type CF = ark_bls12_381::Fr;
+    type F = NonNativeFieldVar<ark_mnt4_298::Fq, CF>;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config, F, CF>;
+
+    emulated_constant_pairing_test::<E, P, CF>(
+        ark_r1cs_std::groups::mnt4::G2PreparedVar::constant,
+        true,
+    );
+}
+
+#[test]
+fn mnt6_298_emulated_constant_pairing_test() {
+    type E = ark_mnt6_298::MNT6_298;
+    type CF = ark_bls12_381::Fr;
+    type F = NonNativeFieldVar<ark_mnt6_298::Fq, CF>;
+    type P = ark_r1cs_std::pairing::mnt6::PairingVar<ark_mnt6_298::Config, F, CF>;
+
+    emulated_constant_pairing_test::<E, P, CF>(
+        ark_r1cs_std::groups::mnt6::G2PreparedVar::constant,
+        true,
+    );
+}
+
+#[test]
+fn mnt4_298_sparse_mul_test() {
+    type Fq = ark_mnt4_298::Fq;
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<Fq>::new_ref();
+
+    let f = Fp4Var::new_witness(cs.clone(), || Ok(ark_mnt4_298::Fq4::rand(&mut rng))).unwrap();
+    let c0 = Fp2Var::new_witness(cs.clone(), || Ok(ark_mnt4_298::Fq2::rand(&mut rng))).unwrap();
+    let c1 = Fp2Var::new_witness(cs.clone(), || Ok(ark_mnt4_298::Fq2::rand(&mut rng))).unwrap();
+    let y = FpVar::new_witness(cs.clone(), || Ok(Fq::rand(&mut rng))).unwrap();
+    let twist = ark_mnt4_298::Fq2::new(Fq::from(0u8), Fq::from(1u8));
+    // As in an allocated `G1PreparedVar`, `y_twist` is a witness of its own.
+    let y_twist = Fp2Var::new_witness(cs.clone(), || {
+        let y = y.value()?;
+        Ok(ark_mnt4_298::Fq2::new(twist.c0 * y, twist.c1 * y))
+    })
+    .unwrap();
+
+    // Before: multiply by the twisted coordinate, then do a dense
+    // multiplication.
+    let num_constraints = cs.num_constraints();
+    let dense = &f * Fp4Var::new(c0.clone(), &c1 * &y_twist);
+    let dense_constraints = cs.num_constraints() - num_constraints;
+
+    // After: scale by the base prime field element inside the sparse
+    // multiplication.
+    let num_constraints = cs.num_constraints();
+    let sparse = f.mul_by_c0_yc1(&c0, &(&c1 * twist), &y).unwrap();
+    let sparse_constraints = cs.num_constraints() - num_constraints;
+
+    assert_eq!(dense.value().unwrap(), sparse.value().unwrap());
+    assert_eq!(dense_constraints, 12);
+    assert_eq!(sparse_constraints, 9);
+
+    let dense = &f * Fp4Var::new(&c0 * &y_twist, c1.clone());
+    let sparse = f.mul_by_yc0_c1(&(&c0 * twist), &c1, &y).unwrap();
+    assert_eq!(dense.value().unwrap(), sparse.value().unwrap());
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn mnt6_298_sparse_mul_test() {
+    type Fq = ark_mnt6_298::Fq;
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<Fq>::new_ref();
+
+    let f = Fp6Var::new_witness(cs.clone(), || Ok(ark_mnt6_298::Fq6::rand(&mut rng))).unwrap();
+    let c0 = Fp3Var::new_witness(cs.clone(), || Ok(ark_mnt6_298::Fq3::rand(&mut rng))).unwrap();
+    let c1 = Fp3Var::new_witness(cs.clone(), || Ok(ark_mnt6_298::Fq3::rand(&mut rng))).unwrap();
+    let y = FpVar::new_witness(cs.clone(), || Ok(Fq::rand(&mut rng))).unwrap();
+    let twist = ark_mnt6_298::Fq3::new(Fq::from(0u8), Fq::from(1u8), Fq::from(0u8));
+    // As in an allocated `G1PreparedVar`, `y_twist` is a witness of its own.
+    let y_twist = Fp3Var::new_witness(cs.clone(), || {
+        let y = y.value()?;
+        Ok(ark_mnt6_298::Fq3::new(
+            twist.c0 * y,
+            twist.c1 * y,
+            twist.c2 * y,
+        ))
+    })
+    .unwrap();
+
+    // Before: multiply by the twisted coordinate, then do a dense
+    // multiplication.
+    let num_constraints = cs.num_constraints();
+    let dense = &f * Fp6Var::new(c0.clone(), &c1 * &y_twist);
+    let dense_constraints = cs.num_constraints() - num_constraints;
+
+    // After: scale by the base prime field element inside the sparse
+    // multiplication.
+    let num_constraints = cs.num_constraints();
+    let sparse = f.mul_by_c0_yc1(&c0, &(&c1 * twist), &y).unwrap();
+    let sparse_constraints = cs.num_constraints() - num_constraints;
+
+    assert_eq!(dense.value().unwrap(), sparse.value().unwrap());
+    assert_eq!(dense_constraints, 24);
+    assert_eq!(sparse_constraints, 14);
+
+    let dense = &f * Fp6Var::new(&c0 * &y_twist, c1.clone());
+    let sparse = f.mul_by_yc0_c1(&(&c0 * twist), &c1, &y).unwrap();
+    assert_eq!(dense.value().unwrap(), sparse.value().unwrap());
+    assert!(cs.is_satisfied().unwrap());
+}
+
+fn miller_loop_constraints<E: Pairing, P: PairingVar<E, CF>, CF: ark_ff::PrimeField>() -> usize {
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let a = E::G1::rand(&mut rng);
+    let b = E::G2::rand(&mut rng);
+    let a_var = P::G1Var::new_witness(cs.clone(), || Ok(a)).unwrap();
+    let b_var = P::G2Var::new_witness(cs.clone(), || Ok(b)).unwrap();
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+    let b_prep = P::prepare_g2(&b_var).unwrap();
+
+    let num_constraints = cs.num_constraints();
+    let result = P::miller_loop(&[a_prep], &[b_prep]).unwrap();
+    let num_constraints = cs.num_constraints() - num_constraints;
+
+    assert_eq!(result.value().unwrap(), E::miller_loop(a, b).0);
+    assert!(cs.is_satisfied().unwrap());
+    num_constraints
+}
+
+#[test]
+fn mnt4_298_sparse_miller_loop_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config>;
+
+    // Before the sparse line multiplications, the Miller loop took 3615
+    // constraints.
+    assert_eq!(miller_loop_constraints::<E, P, ark_mnt4_298::Fq>(), 3225);
+}
+
+#[test]
+fn mnt6_298_sparse_miller_loop_test() {
+    type E = ark_mnt6_298::MNT6_298;
+    type P = ark_r1cs_std::pairing::mnt6::PairingVar<ark_mnt6_298::Config>;
+
+    // Before the sparse line multiplications, the Miller loop took 7974
+    // constraints.
+    assert_eq!(miller_loop_constraints::<E, P, ark_mnt6_298::Fq>(), 5564);
+}
+
+#[test]
+fn bw6_761_sparse_mul_test() {
+    type Fq = ark_bw6_761::Fq;
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<Fq>::new_ref();
+
+    let f_value = ark_bw6_761::Fq6::rand(&mut rng);
+    let f = Fp6Var::new_witness(cs.clone(), || Ok(f_value)).unwrap();
+    let [c0, c1, c2] = [(); 3].map(|_| Fq::rand(&mut rng));
+    let [c0_var, c1_var, c2_var] =
+        [c0, c1, c2].map(|c| FpVar::new_witness(cs.clone(), || Ok(c)).unwrap());
+
+    let mut expected = f_value;
+    expected.mul_by_014(&c0, &c1, &c2);
+    let sparse = f.mul_by_014(&c0_var, &c1_var, &c2_var).unwrap();
+    assert_eq!(sparse.value().unwrap(), expected);
+
+    let mut expected = f_value;
+    expected.mul_by_034(&c0, &c1, &c2);
+    let sparse = f.mul_by_034(&c0_var, &c1_var, &c2_var).unwrap();
+    assert_eq!(sparse.value().unwrap(), expected);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+fn pairing_product_is_one_test<E: Pairing, P: PairingVar<E, CF>, CF: ark_ff::PrimeField>() {
+    let mut rng = ark_std::test_rng();
+    let a = E::G1::rand(&mut rng);
+    let b = E::G2::rand(&mut rng);
+
+    let prepare = |cs: ConstraintSystemRef<CF>, a: E::G1, b: E::G2| {
+        let a_var = P::G1Var::new_witness(cs.clone(), || Ok(a)).unwrap();
+        let b_var = P::G2Var::new_witness(cs.clone(), || Ok(b)).unwrap();
+        (
+            P::prepare_g1(&a_var).unwrap(),
+            P::prepare_g2(&b_var).unwrap(),
+        )
+    };
+
+    // `e(a, b) * e(-a, b) = 1`.
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let (a_prep, b_prep) = prepare(cs.clone(), a, b);
+    let (neg_a_prep, _) = prepare(cs.clone(), -a, b);
+    let num_constraints = cs.num_constraints();
+    P::enforce_pairing_product_is_one(
+        &[a_prep.clone(), neg_a_prep.clone()],
+        &[b_prep.clone(), b_prep.clone()],
+    )
+    .unwrap();
+    let residue_constraints = cs.num_constraints() - num_constraints;
+    assert!(cs.is_satisfied().unwrap());
+
+    let num_constraints = cs.num_constraints();
+    P::product_of_pairings(&[a_prep, neg_a_prep], &[b_prep.clone(), b_prep])
+        .unwrap()
+        .enforce_equal(&P::GTVar::one())
+        .unwrap();
+    let full_constraints = cs.num_constraints() - num_constraints;
+    assert!(cs.is_satisfied().unwrap());
+    assert!(residue_constraints < full_constraints);
+
+    // `e(a, b) * e(a, b) != 1`.
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let (a_prep, b_prep) = prepare(cs.clone(), a, b);
+    P::enforce_pairing_product_is_one(&[a_prep.clone(), a_prep], &[b_prep.clone(), b_prep])
+        .unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn bls12_377_pairing_product_is_one_test() {
+    type E = ark_bls12_377::Bls12_377;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_377::Config>;
+
+    pairing_product_is_one_test::<E, P, ark_bls12_377::Fq>();
+}
+
+#[test]
+fn bls12_381_pairing_product_is_one_test() {
+    // Unlike BLS12-377, BLS12-381 has a negative `x`.
+    type E = ark_bls12_381::Bls12_381;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_381::Config>;
+
+    pairing_product_is_one_test::<E, P, ark_bls12_381::Fq>();
+}
+
+#[test]
+fn bn254_pairing_product_is_one_test() {
+    type E = ark_bn254::Bn254;
+    type P = ark_r1cs_std::pairing::bn::PairingVar<ark_bn254::Config>;
+
+    pairing_product_is_one_test::<E, P, ark_bn254::Fq>();
+}
+
+fn multi_miller_loop_test<E: Pairing, P: PairingVar<E, CF>, CF: ark_ff::PrimeField>() {
+    let mut rng = ark_std::test_rng();
+    let a = [E::G1::rand(&mut rng), E::G1::rand(&mut rng)];
+    let b = [E::G2::rand(&mut rng), E::G2::rand(&mut rng)];
+
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let mut a_prep = vec![];
+    let mut b_prep = vec![];
+    for (a, b) in a.iter().zip(&b) {
+        let a_var = P::G1Var::new_witness(cs.clone(), || Ok(*a)).unwrap();
+        let b_var = P::G2Var::new_witness(cs.clone(), || Ok(*b)).unwrap();
+        a_prep.push(P::prepare_g1(&a_var).unwrap());
+        b_prep.push(P::prepare_g2(&b_var).unwrap());
+    }
+
+    let num_constraints = cs.num_constraints();
+    let result = P::miller_loop(&a_prep, &b_prep).unwrap();
+    let batched_constraints = cs.num_constraints() - num_constraints;
+
+    let num_constraints = cs.num_constraints();
+    let mut expected = P::miller_loop(&a_prep[..1], &b_prep[..1]).unwrap();
+    expected *= P::miller_loop(&a_prep[1..], &b_prep[1..]).unwrap();
+    let separate_constraints = cs.num_constraints() - num_constraints;
+
+    assert_eq!(result.value().unwrap(), expected.value().unwrap());
+    assert_eq!(
+        P::final_exponentiation(&result).unwrap().value().unwrap(),
+        E::multi_pairing(a, b).0
+    );
+    assert!(batched_constraints < separate_constraints);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn mnt4_298_multi_miller_loop_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config>;
+
+    multi_miller_loop_test::<E, P, ark_mnt4_298::Fq>();
+}
+
+#[test]
+fn mnt6_298_multi_miller_loop_test() {
+    type E = ark_mnt6_298::MNT6_298;
+    type P = ark_r1cs_std::pairing::mnt6::PairingVar<ark_mnt6_298::Config>;
+
+    multi_miller_loop_test::<E, P, ark_mnt6_298::Fq>();
+}
+
+fn gt_pow_test<E: Pairing, P: PairingVar<E, CF>, CF: ark_ff::PrimeField>() {
+    let mut rng = ark_std::test_rng();
+    let gt = E::pairing(E::G1::rand(&mut rng), E::G2::rand(&mut rng)).0;
+    let bits = E::ScalarField::rand(&mut rng).into_bigint().to_bits_le();
+
+    // A full-length exponent, and a short even one.
+    for bits in [&bits[..], &[false, true, true, false, true][..]] {
+        let cs = ConstraintSystem::<CF>::new_ref();
+        let gt_var = P::GTVar::new_witness(cs.clone(), || Ok(gt)).unwrap();
+        let bits_var = bits
+            .iter()
+            .map(|bit| Boolean::new_witness(cs.clone(), || Ok(*bit)))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let result = P::gt_pow_le(&gt_var, &bits_var).unwrap();
+
+        let exponent = <E::ScalarField as PrimeField>::BigInt::from_bits_le(bits);
+        assert_eq!(result.value().unwrap(), gt.pow(exponent));
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn bls12_377_gt_pow_test() {
+    type E = ark_bls12_377::Bls12_377;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_377::Config>;
+
+    gt_pow_test::<E, P, ark_bls12_377::Fq>();
+}
+
+#[test]
+fn bn254_gt_pow_test() {
+    type E = ark_bn254::Bn254;
+    type P = ark_r1cs_std::pairing::bn::PairingVar<ark_bn254::Config>;
+
+    gt_pow_test::<E, P, ark_bn254::Fq>();
+}
+
+#[test]
+fn mnt4_298_gt_pow_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config>;
+
+    gt_pow_test::<E, P, ark_mnt4_298::Fq>();
+}
+
+fn constant_g2_prepared_test<E: Pairing, P: PairingVar<E, CF>, CF: ark_ff::PrimeField>(
+    constant: impl Fn(E::G2Prepared) -> P::G2PreparedVar,
+) {
+    let mut rng = ark_std::test_rng();
+    let a = E::G1::rand(&mut rng);
+    let b = E::G2::rand(&mut rng);
+
+    let cs = ConstraintSystem::<CF>::new_ref();
+    let a_var = P::G1Var::new_witness(cs.clone(), || Ok(a)).unwrap();
+    let a_prep = P::prepare_g1(&a_var).unwrap();
+
+    let b_var = P::G2Var::new_witness(cs.clone(), || Ok(b)).unwrap();
+    let b_prep = P::prepare_g2(&b_var).unwrap();
+    let num_constraints = cs.num_constraints();
+    let expected = P::miller_loop(&[a_prep.clone()], &[b_prep]).unwrap();
+    let prepared_constraints = cs.num_constraints() - num_constraints;
+
+    let b_prep = constant(E::G2Prepared::from(b));
+    let num_constraints = cs.num_constraints();
+    let result = P::miller_loop(&[a_prep], &[b_prep]).unwrap();
+    let constant_constraints = cs.num_constraints() - num_constraints;
+
+    assert_eq!(result.value().unwrap(), expected.value().unwrap());
+    assert_eq!(
+        P::final_exponentiation(&result).unwrap().value().unwrap(),
+        E::pairing(a, b).0
+    );
+    assert!(constant_constraints < prepared_constraints);
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn bls12_377_constant_g2_prepared_test() {
+    type E = ark_bls12_377::Bls12_377;
+    type P = ark_r1cs_std::pairing::bls12::PairingVar<ark_bls12_377::Config>;
+
+    constant_g2_prepared_test::<E, P, ark_bls12_377::Fq>(
+        ark_r1cs_std::groups::bls12::G2PreparedVar::constant,
+    );
+}
+
+#[test]
+fn mnt4_298_constant_g2_prepared_test() {
+    type E = ark_mnt4_298::MNT4_298;
+    type P = ark_r1cs_std::pairing::mnt4::PairingVar<ark_mnt4_298::Config>;
+
+    constant_g2_prepared_test::<E, P, ark_mnt4_298::Fq>(
+        ark_r1cs_std::groups::mnt4::G2PreparedVar::constant,
+    );
+}
+
+#[test]
+fn mnt6_298_constant_g2_prepared_test() {
+    type E = ark_mnt6_298::MNT6_298;
+    type P = ark_r1cs_std::pairing::mnt6::PairingVar<ark_mnt6_298::Config>;
+
+    constant_g2_prepared_test::<E, P, ark_mnt6_298::Fq>(
+        ark_r1cs_std::groups::mnt6::G2PreparedVar::constant,
+    );
+}
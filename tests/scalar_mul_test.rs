@@ -0,0 +1,408 @@
+use ark_ec::{CurveGroup, Group};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    boolean::Boolean,
+    fields::fp::FpVar,
+    groups::{
+        curves::{short_weierstrass::ProjectiveVar, twisted_edwards::AffineVar},
+        CurveVar, FixedBaseTable,
+    },
+    R1CSVar,
+};
+use ark_relations::r1cs::ConstraintSystem;
+use ark_std::{vec::Vec, UniformRand, Zero};
+use num_bigint::BigUint;
+
+type SWVar = ProjectiveVar<ark_bls12_381::g1::Config, FpVar<ark_bls12_381::Fq>>;
+type TEVar = AffineVar<ark_ed_on_bls12_377::EdwardsConfig, FpVar<ark_ed_on_bls12_377::Fq>>;
+
+/// The number of constraints used by each scalar multiplication strategy.
+#[derive(Debug)]
+struct Costs {
+    double_and_add: usize,
+    windowed: Vec<usize>,
+    signed_digit: Vec<usize>,
+}
+
+const WINDOW_SIZES: [usize; 3] = [2, 3, 4];
+
+fn check_strategies<C, GG>(num_bits: usize, zero_point: bool) -> Costs
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+    GG: CurveVar<C, C::BaseField>,
+{
+    let mut rng = ark_std::test_rng();
+    let point = if zero_point {
+        C::zero()
+    } else {
+        C::rand(&mut rng)
+    };
+    let mut bits = C::ScalarField::rand(&mut rng).into_bigint().to_bits_le();
+    bits.resize(num_bits, true);
+    let scalar = bits
+        .iter()
+        .rev()
+        .fold(BigUint::zero(), |acc, b| (acc << 1) + *b as u8);
+    let expected = point * C::ScalarField::from(scalar);
+
+    let cs = ConstraintSystem::<C::BaseField>::new_ref();
+    let point_var = GG::new_witness(cs.clone(), || Ok(point)).unwrap();
+    let bits = bits
+        .iter()
+        .map(|b| Boolean::new_witness(cs.clone(), || Ok(*b)).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut num_constraints = cs.num_constraints();
+    let mut cost = || {
+        let new_num_constraints = cs.num_constraints();
+        let cost = new_num_constraints - num_constraints;
+        num_constraints = new_num_constraints;
+        cost
+    };
+
+    let result = point_var.scalar_mul_le(bits.iter()).unwrap();
+    assert_eq!(result.value().unwrap(), expected);
+    let double_and_add = cost();
+
+    let mut windowed = Vec::new();
+    let mut signed_digit = Vec::new();
+    for window_size in WINDOW_SIZES {
+        let result = point_var
+            .scalar_mul_le_windowed(bits.iter(), window_size)
+            .unwrap();
+        assert_eq!(result.value().unwrap(), expected);
+        windowed.push(cost());
+
+        let result = point_var
+            .scalar_mul_le_signed_digit(bits.iter(), window_size)
+            .unwrap();
+        assert_eq!(result.value().unwrap(), expected);
+        signed_digit.push(cost());
+    }
+    assert!(cs.is_satisfied().unwrap());
+
+    Costs {
+        double_and_add,
+        windowed,
+        signed_digit,
+    }
+}
+
+#[test]
+fn sw_windowed_scalar_mul_test() {
+    for num_bits in [1, 2, 7, 64, 255] {
+        check_strategies::<ark_bls12_381::G1Projective, SWVar>(num_bits, false);
+        check_strategies::<ark_bls12_381::G1Projective, SWVar>(num_bits, true);
+    }
+}
+
+#[test]
+fn te_windowed_scalar_mul_test() {
+    for num_bits in [1, 2, 7, 64, 251, 300] {
+        check_strategies::<ark_ed_on_bls12_377::EdwardsProjective, TEVar>(num_bits, false);
+        check_strategies::<ark_ed_on_bls12_377::EdwardsProjective, TEVar>(num_bits, true);
+    }
+}
+
+#[test]
+fn windowed_scalar_mul_cost_test() {
+    // With the complete twisted Edwards formulae, 2-bit windows and 3-bit signed
+    // digits are cheaper than bit-by-bit double-and-add.
+    let costs = check_strategies::<ark_ed_on_bls12_377::EdwardsProjective, TEVar>(251, false);
+    assert!(costs.windowed[0] < costs.double_and_add);
+    assert!(10 * costs.signed_digit[1] < 9 * costs.double_and_add);
+    // Signed digits halve the table, which pays off for larger windows.
+    assert!(costs.signed_digit[1] < costs.windowed[1]);
+    assert!(costs.signed_digit[2] < costs.windowed[2]);
+
+    // Short Weierstrass `ProjectiveVar`s implement `scalar_mul_le` with cheaper
+    // incomplete affine formulae, which beat the windowed methods.
+    let costs = check_strategies::<ark_bls12_381::G1Projective, SWVar>(255, false);
+    assert!(costs.double_and_add < costs.windowed[0]);
+    assert!(costs.signed_digit[1] < costs.windowed[1]);
+}
+
+/// Checks `variable_base_msm`, and `variable_base_msm_with_window` for several
+/// window sizes, against the native computation, and returns the cost of
+/// `variable_base_msm` together with the cost of computing each term
+/// separately with `scalar_mul_le_signed_digit`.
+fn check_msm<C, GG>(num_bits: &[usize], zero_points: &[bool]) -> (usize, usize)
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+    GG: CurveVar<C, C::BaseField>,
+{
+    let mut rng = ark_std::test_rng();
+    let cs = ConstraintSystem::<C::BaseField>::new_ref();
+    let mut expected = C::zero();
+    let mut bases = Vec::new();
+    let mut scalars = Vec::new();
+    for (&num_bits, &zero_point) in num_bits.iter().zip(zero_points) {
+        let point = if zero_point {
+            C::zero()
+        } else {
+            C::rand(&mut rng)
+        };
+        let mut bits = C::ScalarField::rand(&mut rng).into_bigint().to_bits_le();
+        bits.resize(num_bits, true);
+        let scalar = bits
+            .iter()
+            .rev()
+            .fold(BigUint::zero(), |acc, b| (acc << 1) + *b as u8);
+        expected += point * C::ScalarField::from(scalar);
+
+        bases.push(GG::new_witness(cs.clone(), || Ok(point)).unwrap());
+        scalars.push(
+            bits.iter()
+                .map(|b| Boolean::new_witness(cs.clone(), || Ok(*b)).unwrap())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    let num_constraints = cs.num_constraints();
+    let result = GG::variable_base_msm(&bases, &scalars).unwrap();
+    assert_eq!(result.value().unwrap(), expected);
+    let msm = cs.num_constraints() - num_constraints;
+
+    for window_size in [1, 2, 4] {
+        let result = GG::variable_base_msm_with_window(&bases, &scalars, window_size).unwrap();
+        assert_eq!(result.value().unwrap(), expected);
+    }
+
+    let num_constraints = cs.num_constraints();
+    let mut result = GG::zero();
+    for (base, bits) in bases.iter().zip(&scalars) {
+        result += base.scalar_mul_le_signed_digit(bits.iter(), 3).unwrap();
+    }
+    assert_eq!(result.value().unwrap(), expected);
+    let separate = cs.num_constraints() - num_constraints;
+
+    assert!(cs.is_satisfied().unwrap());
+    (msm, separate)
+}
+
+#[test]
+fn variable_base_msm_test() {
+    type SW = ark_bls12_381::G1Projective;
+    type TE = ark_ed_on_bls12_377::EdwardsProjective;
+
+    assert!(SWVar::variable_base_msm(&[], &[])
+        .unwrap()
+        .value()
+        .unwrap()
+        .is_zero());
+    check_msm::<SW, SWVar>(&[0, 5], &[false, false]);
+    check_msm::<SW, SWVar>(&[1, 7, 64], &[false, true, false]);
+    check_msm::<SW, SWVar>(&[255, 100, 255], &[false, false, true]);
+    check_msm::<TE, TEVar>(&[2, 3], &[true, true]);
+    check_msm::<TE, TEVar>(&[251, 64, 1, 251], &[false, true, false, false]);
+
+    // Sharing the doublings pays off as the number of terms grows.
+    let (msm, separate) = check_msm::<TE, TEVar>(&[251; 4], &[false; 4]);
+    assert!(3 * msm < 2 * separate);
+    let (msm, separate) = check_msm::<SW, SWVar>(&[255; 4], &[false; 4]);
+    assert!(msm < separate);
+}
+
+#[test]
+#[should_panic(expected = "the window size must be positive")]
+fn variable_base_msm_zero_window_test() {
+    let _ = SWVar::variable_base_msm_with_window(&[], &[], 0);
+}
+
+/// Checks that multiplying a constant point (which uses precomputed
+/// power-of-two multiples) agrees with multiplying a witness point, and
+/// returns the costs of both multiplications.
+fn check_constant_base<C, GG>(
+    num_bits: usize,
+    zero_point: bool,
+    constant_bits: bool,
+) -> (usize, usize)
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+    GG: CurveVar<C, C::BaseField>,
+{
+    let mut rng = ark_std::test_rng();
+    let point = if zero_point {
+        C::zero()
+    } else {
+        C::rand(&mut rng)
+    };
+    let mut bits = C::ScalarField::rand(&mut rng).into_bigint().to_bits_le();
+    bits.resize(num_bits, true);
+    let scalar = bits
+        .iter()
+        .rev()
+        .fold(BigUint::zero(), |acc, b| (acc << 1) + *b as u8);
+    let expected = point * C::ScalarField::from(scalar);
+
+    let cs = ConstraintSystem::<C::BaseField>::new_ref();
+    let bits = bits
+        .iter()
+        .map(|b| {
+            if constant_bits {
+                Boolean::constant(*b)
+            } else {
+                Boolean::new_witness(cs.clone(), || Ok(*b)).unwrap()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let num_constraints = cs.num_constraints();
+    let result = GG::constant(point).scalar_mul_le(bits.iter()).unwrap();
+    assert_eq!(result.value().unwrap(), expected);
+    let constant_cost = cs.num_constraints() - num_constraints;
+
+    // The constant path is the same as using precomputed power-of-two bases.
+    let mut multiple = point;
+    let multiples = bits
+        .iter()
+        .map(|_| {
+            let current = multiple;
+            multiple.double_in_place();
+            current
+        })
+        .collect::<Vec<_>>();
+    let num_constraints = cs.num_constraints();
+    let mut result = GG::zero();
+    result
+        .precomputed_base_scalar_mul_le(bits.iter().zip(&multiples))
+        .unwrap();
+    assert_eq!(result.value().unwrap(), expected);
+    assert_eq!(cs.num_constraints() - num_constraints, constant_cost);
+
+    // The precomputed path adds to `self` rather than overwriting it.
+    let mut result = GG::constant(point);
+    result
+        .precomputed_base_scalar_mul_le(bits.iter().zip(&multiples))
+        .unwrap();
+    assert_eq!(result.value().unwrap(), expected + point);
+
+    let point_var = GG::new_witness(cs.clone(), || Ok(point)).unwrap();
+    let num_constraints = cs.num_constraints();
+    let result = point_var.scalar_mul_le(bits.iter()).unwrap();
+    assert_eq!(result.value().unwrap(), expected);
+    let witness_cost = cs.num_constraints() - num_constraints;
+
+    assert!(cs.is_satisfied().unwrap());
+    (constant_cost, witness_cost)
+}
+
+#[test]
+fn constant_base_scalar_mul_test() {
+    type SW = ark_bls12_381::G1Projective;
+    type TE = ark_ed_on_bls12_377::EdwardsProjective;
+
+    // `scalar_mul_le` used to skip the precomputed path for constant points
+    // because of a bug with twisted Edwards curves, so cover `TE` for every
+    // scalar shape below.
+    for num_bits in [0, 1, 2, 3, 64, 251, 300] {
+        for constant_bits in [false, true] {
+            for zero_point in [false, true] {
+                let costs = [
+                    check_constant_base::<SW, SWVar>(num_bits, zero_point, constant_bits),
+                    check_constant_base::<TE, TEVar>(num_bits, zero_point, constant_bits),
+                ];
+                for (constant, witness) in costs {
+                    assert!(constant <= witness);
+                    assert!(num_bits == 0 || constant < witness);
+                }
+                // For short Weierstrass curves, every bit of a scalar that fits in
+                // a single chunk costs one incomplete affine addition and one
+                // selection, and no doubling.
+                if num_bits < 253 {
+                    assert!(costs[0].0 <= 5 * num_bits + 1, "{:?}", costs[0]);
+                }
+            }
+        }
+    }
+}
+
+/// Checks `fixed_base_scalar_mul_le` against the native computation for scalars
+/// of various lengths, reusing a single table, and returns its cost together
+/// with the cost of `scalar_mul_le` on a constant point for `num_bits` bits.
+fn check_fixed_base<C, GG>(
+    zero_point: bool,
+    num_bits: usize,
+    fixed_base_scalar_mul: impl Fn(&FixedBaseTable<C>, &[Boolean<C::BaseField>]) -> GG,
+) -> (usize, usize)
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+    GG: CurveVar<C, C::BaseField>,
+{
+    let mut rng = ark_std::test_rng();
+    let point = if zero_point {
+        C::zero()
+    } else {
+        C::rand(&mut rng)
+    };
+    let table = FixedBaseTable::new(point, 300);
+    assert_eq!(table.max_num_bits(), 300);
+
+    let mut costs = (0, 0);
+    for n in [0, 1, 2, 3, 4, 64, num_bits, 300] {
+        for constant_bits in [false, true] {
+            let mut bits = C::ScalarField::rand(&mut rng).into_bigint().to_bits_le();
+            bits.resize(n, true);
+            let scalar = bits
+                .iter()
+                .rev()
+                .fold(BigUint::zero(), |acc, b| (acc << 1) + *b as u8);
+            let expected = point * C::ScalarField::from(scalar);
+
+            let cs = ConstraintSystem::<C::BaseField>::new_ref();
+            let bits = bits
+                .iter()
+                .map(|b| {
+                    if constant_bits {
+                        Boolean::constant(*b)
+                    } else {
+                        Boolean::new_witness(cs.clone(), || Ok(*b)).unwrap()
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let num_constraints = cs.num_constraints();
+            let result = fixed_base_scalar_mul(&table, &bits);
+            assert_eq!(result.value().unwrap(), expected);
+            let fixed_base = cs.num_constraints() - num_constraints;
+
+            let num_constraints = cs.num_constraints();
+            GG::constant(point).scalar_mul_le(bits.iter()).unwrap();
+            let constant = cs.num_constraints() - num_constraints;
+
+            assert!(cs.is_satisfied().unwrap());
+            if n == num_bits && !constant_bits {
+                costs = (fixed_base, constant);
+            }
+        }
+    }
+    costs
+}
+
+#[test]
+fn fixed_base_scalar_mul_test() {
+    type SW = ark_bls12_381::G1Projective;
+    type TE = ark_ed_on_bls12_377::EdwardsProjective;
+
+    // Both save at least a quarter of the constraints compared to the
+    // precomputed power-of-two bases used by `scalar_mul_le` for constant points.
+    for zero_point in [false, true] {
+        let (fixed_base, constant) = check_fixed_base::<SW, SWVar>(zero_point, 255, |t, b| {
+            SWVar::fixed_base_scalar_mul_le(t, b.iter()).unwrap()
+        });
+        if !zero_point {
+            assert!(4 * fixed_base < 3 * constant);
+        }
+        let (fixed_base, constant) = check_fixed_base::<TE, TEVar>(zero_point, 251, |t, b| {
+            TEVar::fixed_base_scalar_mul_le(t, b.iter()).unwrap()
+        });
+        if !zero_point {
+            assert!(4 * fixed_base < 3 * constant);
+        }
+    }
+}